@@ -0,0 +1,199 @@
+//! Expected answers for checking solutions, one file per year.
+//!
+//! Each non-empty line of an answers file is `<day> <part> <answer>`. Lines starting with `#` are
+//! ignored. Multi-line answers are written on a single line with `\n` separating the lines:
+//!
+//! ```text
+//! # 2016
+//! 1 1 252
+//! 1 2 143
+//! 8 2 .##..\n#..#.\n#..#.
+//! ```
+
+use crate::simpleerror::ErrorKind;
+use crate::SimpleError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl CheckStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Fail => "FAIL",
+            Self::Missing => "MISSING",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Mismatch {
+    pub part: u32,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Check {
+    pub status: CheckStatus,
+    pub mismatches: Vec<Mismatch>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct AnswerKey {
+    answers: HashMap<(u32, u32), String>,
+}
+
+impl AnswerKey {
+    pub fn parse(s: &str) -> Result<Self, SimpleError> {
+        let mut answers = HashMap::new();
+
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut split = line.splitn(3, ' ');
+            let (day, part, answer) = match (split.next(), split.next(), split.next()) {
+                (Some(day), Some(part), Some(answer)) => (day, part, answer),
+                _ => {
+                    return Err(SimpleError::new(format!(
+                        "invalid answers line, expected '<day> <part> <answer>': {line}"
+                    )))
+                }
+            };
+
+            let day: u32 = day.parse()?;
+            let part: u32 = part.parse()?;
+            if part != 1 && part != 2 {
+                return Err(SimpleError::new(format!("invalid part in line: {line}")));
+            }
+
+            answers.insert((day, part), String::from(answer.trim_end()));
+        }
+
+        Ok(Self { answers })
+    }
+
+    // A missing answers file is treated as an empty one
+    pub fn load(path: &Path) -> Result<Self, SimpleError> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(SimpleError::with_source(
+                ErrorKind::Runtime,
+                format!("could not read answers from {}", path.display()),
+                err,
+            )),
+        }
+    }
+
+    pub fn expected(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u32, answers: (&str, &str)) -> Check {
+        let mut status = CheckStatus::Pass;
+        let mut mismatches = Vec::new();
+
+        for (part, actual) in [(1, answers.0), (2, answers.1)] {
+            let actual = normalize(actual);
+            match self.expected(day, part) {
+                Some(expected) => {
+                    if expected != actual {
+                        status = CheckStatus::Fail;
+                        mismatches.push(Mismatch {
+                            part,
+                            expected: String::from(expected),
+                            actual,
+                        });
+                    }
+                }
+                // Nothing to check for the empty part 2 of day 25
+                None if actual.is_empty() => {}
+                None => {
+                    if status == CheckStatus::Pass {
+                        status = CheckStatus::Missing;
+                    }
+                }
+            }
+        }
+
+        Check { status, mismatches }
+    }
+}
+
+pub fn answers_path(answers_dir: &Path, year: u32) -> PathBuf {
+    answers_dir.join(format!("{year}.txt"))
+}
+
+// Renders an answer the way it's written in an answers file
+fn normalize(answer: &str) -> String {
+    let lines: Vec<_> = answer
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| line.is_empty())
+        .collect();
+    String::from(lines.join("\\n").trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_ANSWERS: &str = "\
+# sample
+1 1 252
+1 2 143
+
+8 1 110
+8 2 #..#\\n####
+25 1 12345
+";
+
+    #[test]
+    fn test_parse() {
+        let key = AnswerKey::parse(SAMPLE_ANSWERS).unwrap();
+
+        assert_eq!(Some("252"), key.expected(1, 1));
+        assert_eq!(Some("#..#\\n####"), key.expected(8, 2));
+        assert_eq!(None, key.expected(25, 2));
+
+        assert!(AnswerKey::parse("1 1").is_err());
+        assert!(AnswerKey::parse("1 3 5").is_err());
+        assert!(AnswerKey::parse("x 1 5").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let key = AnswerKey::parse(SAMPLE_ANSWERS).unwrap();
+
+        assert_eq!(CheckStatus::Pass, key.check(1, ("252", "143")).status);
+        assert_eq!(
+            CheckStatus::Pass,
+            key.check(8, ("110", "\n#..#  \n####\n")).status
+        );
+        assert_eq!(CheckStatus::Pass, key.check(25, ("12345", "")).status);
+        assert_eq!(CheckStatus::Missing, key.check(2, ("1", "2")).status);
+
+        assert_eq!(
+            Check {
+                status: CheckStatus::Fail,
+                mismatches: vec![Mismatch {
+                    part: 2,
+                    expected: String::from("143"),
+                    actual: String::from("144"),
+                }],
+            },
+            key.check(1, ("252", "144"))
+        );
+    }
+}
@@ -1,6 +1,9 @@
 #![forbid(unsafe_code)]
 
+pub mod answers;
+pub mod runner;
 pub mod simpleerror;
+pub mod timing;
 
 pub mod y2015;
 pub mod y2016;
@@ -9,25 +12,307 @@ pub mod y2018;
 pub mod y2019;
 pub mod y2020;
 
-use std::error::Error;
-use std::fmt::Display;
-use std::io;
-use std::io::Read;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::{fs, io, panic};
 
+pub use runner::OutputFormat;
+use simpleerror::ErrorKind;
 pub use simpleerror::SimpleError;
 
-fn run_solution<T1, T2, F>(solution: F) -> Result<(), Box<dyn Error>>
-where
-    T1: Display,
-    T2: Display,
-    F: Fn(&str) -> Result<(T1, T2), Box<dyn Error>>,
-{
-    let input = read_input()?;
+pub type SolveFn = fn(&str) -> Result<(String, String), SimpleError>;
 
-    let (solution1, solution2) = solution(&input)?;
+pub type WriteFramesFn = fn(&str, &mut dyn Write) -> Result<(), SimpleError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub solve: SolveFn,
+}
+
+// Wraps a day's solve function so that both answers are rendered via Display
+macro_rules! solution {
+    ($year:literal, $day:literal, $title:literal, $solve:path) => {
+        Solution {
+            year: $year,
+            day: $day,
+            title: $title,
+            solve: |input| {
+                let (solution1, solution2) = $solve(input)?;
+                Ok((solution1.to_string(), solution2.to_string()))
+            },
+        }
+    };
+}
+
+#[rustfmt::skip]
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(2015, 1, "Not Quite Lisp", y2015::day1::solve),
+    solution!(2015, 2, "I Was Told There Would Be No Math", y2015::day2::solve),
+    solution!(2015, 3, "Perfectly Spherical Houses in a Vacuum", y2015::day3::solve),
+    solution!(2015, 4, "The Ideal Stocking Stuffer", y2015::day4::solve),
+    solution!(2015, 5, "Doesn't He Have Intern-Elves For This?", y2015::day5::solve),
+    solution!(2015, 6, "Probably a Fire Hazard", y2015::day6::solve),
+    solution!(2015, 7, "Some Assembly Required", y2015::day7::solve),
+    solution!(2015, 8, "Matchsticks", y2015::day8::solve),
+    solution!(2015, 9, "All in a Single Night", y2015::day9::solve),
+    solution!(2015, 10, "Elves Look, Elves Say", y2015::day10::solve),
+    solution!(2015, 11, "Corporate Policy", y2015::day11::solve),
+    solution!(2015, 12, "JSAbacusFramework.io", y2015::day12::solve),
+    solution!(2015, 13, "Knights of the Dinner Table", y2015::day13::solve),
+    solution!(2015, 14, "Reindeer Olympics", y2015::day14::solve),
+    solution!(2015, 15, "Science for Hungry People", y2015::day15::solve),
+    solution!(2015, 16, "Aunt Sue", y2015::day16::solve),
+    solution!(2015, 17, "No Such Thing as Too Much", y2015::day17::solve),
+    solution!(2015, 18, "Like a GIF For Your Yard", y2015::day18::solve),
+    solution!(2015, 19, "Medicine for Rudolph", y2015::day19::solve),
+    solution!(2015, 20, "Infinite Elves and Infinite Houses", y2015::day20::solve),
+    solution!(2015, 21, "RPG Simulator 20XX", y2015::day21::solve),
+    solution!(2015, 22, "Wizard Simulator 20XX", y2015::day22::solve),
+    solution!(2015, 23, "Opening the Turing Lock", y2015::day23::solve),
+    solution!(2015, 24, "It Hangs in the Balance", y2015::day24::solve),
+    solution!(2015, 25, "Let It Snow", y2015::day25::solve),
+    solution!(2016, 1, "No Time for a Taxicab", y2016::day1::solve),
+    solution!(2016, 2, "Bathroom Security", y2016::day2::solve),
+    solution!(2016, 3, "Squares With Three Sides", y2016::day3::solve),
+    solution!(2016, 4, "Security Through Obscurity", y2016::day4::solve),
+    solution!(2016, 5, "How About a Nice Game of Chess?", y2016::day5::solve),
+    solution!(2016, 6, "Signals and Noise", y2016::day6::solve),
+    solution!(2016, 7, "Internet Protocol Version 7", y2016::day7::solve),
+    solution!(2016, 8, "Two-Factor Authentication", y2016::day8::solve),
+    solution!(2016, 9, "Explosives in Cyberspace", y2016::day9::solve),
+    solution!(2016, 10, "Balance Bots", y2016::day10::solve),
+    solution!(2016, 11, "Radioisotope Thermoelectric Generators", y2016::day11::solve),
+    solution!(2016, 12, "Leonardo's Monorail", y2016::day12::solve),
+    solution!(2016, 13, "A Maze of Twisty Little Cubicles", y2016::day13::solve),
+    solution!(2016, 14, "One-Time Pad", y2016::day14::solve),
+    solution!(2016, 15, "Timing is Everything", y2016::day15::solve),
+    solution!(2016, 16, "Dragon Checksum", y2016::day16::solve),
+    solution!(2016, 17, "Two Steps Forward", y2016::day17::solve),
+    solution!(2016, 18, "Like a Rogue", y2016::day18::solve),
+    solution!(2016, 19, "An Elephant Named Joseph", y2016::day19::solve),
+    solution!(2016, 20, "Firewall Rules", y2016::day20::solve),
+    solution!(2016, 21, "Scrambled Letters and Hash", y2016::day21::solve),
+    solution!(2016, 22, "Grid Computing", y2016::day22::solve),
+    solution!(2016, 23, "Safe Cracking", y2016::day23::solve),
+    solution!(2016, 24, "Air Duct Spelunking", y2016::day24::solve),
+    solution!(2016, 25, "Clock Signal", y2016::day25::solve),
+    solution!(2017, 1, "Inverse Captcha", y2017::day1::solve),
+    solution!(2017, 2, "Corruption Checksum", y2017::day2::solve),
+    solution!(2017, 3, "Spiral Memory", y2017::day3::solve),
+    solution!(2017, 4, "High-Entropy Passphrases", y2017::day4::solve),
+    solution!(2017, 5, "A Maze of Twisty Trampolines, All Alike", y2017::day5::solve),
+    solution!(2017, 6, "Memory Reallocation", y2017::day6::solve),
+    solution!(2017, 7, "Recursive Circus", y2017::day7::solve),
+    solution!(2017, 8, "I Heard You Like Registers", y2017::day8::solve),
+    solution!(2017, 9, "Stream Processing", y2017::day9::solve),
+    solution!(2017, 10, "Knot Hash", y2017::day10::solve),
+    solution!(2017, 11, "Hex Ed", y2017::day11::solve),
+    solution!(2017, 12, "Digital Plumber", y2017::day12::solve),
+    solution!(2017, 13, "Packet Scanners", y2017::day13::solve),
+    solution!(2017, 14, "Disk Defragmentation", y2017::day14::solve),
+    solution!(2017, 15, "Dueling Generators", y2017::day15::solve),
+    solution!(2017, 16, "Permutation Promenade", y2017::day16::solve),
+    solution!(2017, 17, "Spinlock", y2017::day17::solve),
+    solution!(2017, 18, "Duet", y2017::day18::solve),
+    solution!(2017, 19, "A Series of Tubes", y2017::day19::solve),
+    solution!(2017, 20, "Particle Swarm", y2017::day20::solve),
+    solution!(2017, 21, "Fractal Art", y2017::day21::solve),
+    solution!(2017, 22, "Sporifica Virus", y2017::day22::solve),
+    solution!(2017, 23, "Coprocessor Conflagration", y2017::day23::solve),
+    solution!(2017, 24, "Electromagnetic Moat", y2017::day24::solve),
+    solution!(2017, 25, "The Halting Problem", y2017::day25::solve),
+    solution!(2018, 1, "Chronal Calibration", y2018::day1::solve),
+    solution!(2018, 2, "Inventory Management System", y2018::day2::solve),
+    solution!(2018, 3, "No Matter How You Slice It", y2018::day3::solve),
+    solution!(2018, 4, "Repose Record", y2018::day4::solve),
+    solution!(2018, 5, "Alchemical Reduction", y2018::day5::solve),
+    solution!(2018, 6, "Chronal Coordinates", y2018::day6::solve),
+    solution!(2018, 7, "The Sum of Its Parts", y2018::day7::solve),
+    solution!(2018, 8, "Memory Maneuver", y2018::day8::solve),
+    solution!(2018, 9, "Marble Mania", y2018::day9::solve),
+    solution!(2018, 10, "The Stars Align", y2018::day10::solve),
+    solution!(2018, 11, "Chronal Charge", y2018::day11::solve),
+    solution!(2018, 12, "Subterranean Sustainability", y2018::day12::solve),
+    solution!(2018, 13, "Mine Cart Madness", y2018::day13::solve),
+    solution!(2018, 14, "Chocolate Charts", y2018::day14::solve),
+    solution!(2018, 15, "Beverage Bandits", y2018::day15::solve),
+    solution!(2018, 16, "Chronal Classification", y2018::day16::solve),
+    solution!(2018, 17, "Reservoir Research", y2018::day17::solve),
+    solution!(2018, 18, "Settlers of The North Pole", y2018::day18::solve),
+    solution!(2018, 19, "Go With The Flow", y2018::day19::solve),
+    solution!(2018, 20, "A Regular Map", y2018::day20::solve),
+    solution!(2018, 21, "Chronal Conversion", y2018::day21::solve),
+    solution!(2018, 22, "Mode Maze", y2018::day22::solve),
+    solution!(2018, 23, "Experimental Emergency Teleportation", y2018::day23::solve),
+    solution!(2018, 24, "Immune System Simulator 20XX", y2018::day24::solve),
+    solution!(2018, 25, "Four-Dimensional Adventure", y2018::day25::solve),
+    solution!(2019, 1, "The Tyranny of the Rocket Equation", y2019::day1::solve),
+    solution!(2019, 2, "1202 Program Alarm", y2019::day2::solve),
+    solution!(2019, 3, "Crossed Wires", y2019::day3::solve),
+    solution!(2019, 4, "Secure Container", y2019::day4::solve),
+    solution!(2019, 5, "Sunny with a Chance of Asteroids", y2019::day5::solve),
+    solution!(2019, 6, "Universal Orbit Map", y2019::day6::solve),
+    solution!(2019, 7, "Amplification Circuit", y2019::day7::solve),
+    solution!(2019, 8, "Space Image Format", y2019::day8::solve),
+    solution!(2019, 9, "Sensor Boost", y2019::day9::solve),
+    solution!(2019, 10, "Monitoring Station", y2019::day10::solve),
+    solution!(2019, 11, "Space Police", y2019::day11::solve),
+    solution!(2019, 12, "The N-Body Problem", y2019::day12::solve),
+    solution!(2019, 13, "Care Package", y2019::day13::solve),
+    solution!(2019, 14, "Space Stoichiometry", y2019::day14::solve),
+    solution!(2019, 15, "Oxygen System", y2019::day15::solve),
+    solution!(2019, 16, "Flawed Frequency Transmission", y2019::day16::solve),
+    solution!(2019, 17, "Set and Forget", y2019::day17::solve),
+    solution!(2019, 18, "Many-Worlds Interpretation", y2019::day18::solve),
+    solution!(2019, 19, "Tractor Beam", y2019::day19::solve),
+    solution!(2019, 20, "Donut Maze", y2019::day20::solve),
+    solution!(2019, 21, "Springdroid Adventure", y2019::day21::solve),
+    solution!(2019, 22, "Slam Shuffle", y2019::day22::solve),
+    solution!(2019, 23, "Category Six", y2019::day23::solve),
+    solution!(2019, 24, "Planet of Discord", y2019::day24::solve),
+    solution!(2019, 25, "Cryostasis", y2019::day25::solve),
+    solution!(2020, 1, "Report Repair", y2020::day1::solve),
+    solution!(2020, 2, "Password Philosophy", y2020::day2::solve),
+    solution!(2020, 3, "Toboggan Trajectory", y2020::day3::solve),
+    solution!(2020, 4, "Passport Processing", y2020::day4::solve),
+    solution!(2020, 5, "Binary Boarding", y2020::day5::solve),
+    solution!(2020, 6, "Custom Customs", y2020::day6::solve),
+    solution!(2020, 7, "Handy Haversacks", y2020::day7::solve),
+    solution!(2020, 8, "Handheld Halting", y2020::day8::solve),
+    solution!(2020, 9, "Encoding Error", y2020::day9::solve),
+    solution!(2020, 10, "Adapter Array", y2020::day10::solve),
+    solution!(2020, 11, "Seating System", y2020::day11::solve),
+    solution!(2020, 12, "Rain Risk", y2020::day12::solve),
+    solution!(2020, 13, "Shuttle Search", y2020::day13::solve),
+    solution!(2020, 14, "Docking Data", y2020::day14::solve),
+    solution!(2020, 15, "Rambunctious Recitation", y2020::day15::solve),
+    solution!(2020, 16, "Ticket Translation", y2020::day16::solve),
+    solution!(2020, 17, "Conway Cubes", y2020::day17::solve),
+    solution!(2020, 18, "Operation Order", y2020::day18::solve),
+    solution!(2020, 19, "Monster Messages", y2020::day19::solve),
+    solution!(2020, 20, "Jurassic Jigsaw", y2020::day20::solve),
+    solution!(2020, 21, "Allergen Assessment", y2020::day21::solve),
+    solution!(2020, 22, "Crab Combat", y2020::day22::solve),
+    solution!(2020, 23, "Crab Cups", y2020::day23::solve),
+    solution!(2020, 24, "Lobby Layout", y2020::day24::solve),
+    solution!(2020, 25, "Combo Breaker", y2020::day25::solve),
+];
+
+pub fn find_solution(year: u32, day: u32) -> Option<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.year == year && solution.day == day)
+}
+
+// For days that draw with an Intcode screen, runs the part that draws and writes each frame to a
+// writer, for intcode replay
+pub fn find_frame_writer(solution: &Solution) -> Option<WriteFramesFn> {
+    match (solution.year, solution.day) {
+        (2019, 11) => Some(y2019::day11::write_frames),
+        (2019, 13) => Some(y2019::day13::write_frames),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+    // Puzzle input passed directly, e.g. the door ID for 2016 day 5
+    Str(String),
+}
+
+impl InputSource {
+    pub fn read(&self) -> Result<String, SimpleError> {
+        match self {
+            Self::Stdin => Ok(read_input()?),
+            Self::File(path) => fs::read_to_string(path).map_err(|err| {
+                SimpleError::with_source(
+                    ErrorKind::Runtime,
+                    format!("could not read input from {}", path.display()),
+                    err,
+                )
+            }),
+            Self::Str(s) => Ok(s.clone()),
+        }
+    }
+}
+
+// How many addresses --profile lists
+const PROFILE_ADDRESSES: usize = 20;
+
+pub fn run_solution(
+    solution: &Solution,
+    input: &InputSource,
+    format: OutputFormat,
+    show_times: bool,
+    show_profile: bool,
+) -> Result<(), SimpleError> {
+    let input = input.read()?;
+
+    if format == OutputFormat::Json {
+        let result = runner::without_panic_output(|| runner::run_day(solution, &input));
+        println!("{}", runner::format_json_record(&result));
+
+        // Exit the same way as text mode would have, the details are already in the record
+        let failed = format!("{} day {} failed", solution.year, solution.day);
+        return match result.outcome {
+            runner::Outcome::Error(kind, ..) => Err(SimpleError::with_kind(kind, failed)),
+            runner::Outcome::Panic(_) => panic::resume_unwind(Box::new(failed)),
+            _ => Ok(()),
+        };
+    }
+
+    // Only trace Intcode programs when asked to, since tracing slows them down
+    let ((result, profile), times) = timing::record(|| {
+        if show_profile {
+            let (result, profile) = y2019::intcode::tracer::record(|| (solution.solve)(&input));
+            (result, Some(profile))
+        } else {
+            ((solution.solve)(&input), None)
+        }
+    });
+    let (solution1, solution2) = result?;
     println!("{solution1}");
     println!("{solution2}");
 
+    if show_times {
+        eprint!("{}", runner::format_phase_times(&times));
+    }
+
+    if let Some(profile) = profile {
+        if profile.total() == 0 {
+            eprintln!("no Intcode instructions were executed");
+        } else {
+            eprint!("{}", profile.format_hottest(PROFILE_ADDRESSES));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn bench_solution(
+    solution: &Solution,
+    input: &InputSource,
+    runs: usize,
+) -> Result<(), SimpleError> {
+    let input = input.read()?;
+
+    let result = runner::bench_day(solution, &input, runs)?;
+    let (solution1, solution2) = &result.answers;
+    println!("{solution1}");
+    println!("{solution2}");
+
+    println!();
+    println!(
+        "{} day {}: {} ({runs} runs)",
+        solution.year, solution.day, solution.title
+    );
+    print!("{}", runner::format_bench_summary(&result.times));
+
     Ok(())
 }
 
@@ -39,6 +324,18 @@ fn read_input() -> io::Result<String> {
     Ok(s)
 }
 
+// Parses every line of the input, attaching the line number to any error
+fn parse_lines<T>(
+    input: &str,
+    parse_line: impl Fn(&str) -> Result<T, SimpleError>,
+) -> Result<Vec<T>, SimpleError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|err| err.at_line(i + 1, line)))
+        .collect()
+}
+
 fn read_single_line(input: &str) -> Result<&str, SimpleError> {
     match input.lines().next() {
         Some(line) => Ok(line),
@@ -47,3 +344,40 @@ fn read_single_line(input: &str) -> Result<&str, SimpleError> {
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solutions_sorted_and_unique() {
+        for window in SOLUTIONS.windows(2) {
+            assert!(
+                (window[0].year, window[0].day) < (window[1].year, window[1].day),
+                "{}/{} listed before {}/{}",
+                window[0].year,
+                window[0].day,
+                window[1].year,
+                window[1].day
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_solution() {
+        let solution = find_solution(2019, 7).unwrap();
+        assert_eq!("Amplification Circuit", solution.title);
+
+        assert!(find_solution(2019, 26).is_none());
+        assert!(find_solution(2014, 1).is_none());
+    }
+
+    #[test]
+    fn test_input_source() {
+        let input = InputSource::Str(String::from("abc")).read().unwrap();
+        assert_eq!("abc", input);
+
+        let missing = InputSource::File(PathBuf::from("inputs/0000/day00.txt"));
+        assert!(missing.read().is_err());
+    }
+}
@@ -1,23 +1,276 @@
-use advent_of_code_past::{y2015, y2016, y2017, y2018, y2019, y2020};
+use advent_of_code_past::simpleerror::ErrorKind;
+use advent_of_code_past::y2019::intcode::{self, ascii, assembler, debugger, disassembler, screen};
+use advent_of_code_past::{runner, InputSource, OutputFormat, SimpleError, WriteFramesFn};
 use std::env;
-use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-const USAGE: &str = "ARGS: year day\ninput should be passed via stdin";
+const USAGE: &str = "ARGS: year day [--input <path> | --input-str <input>] [--time | --bench <runs>] [--profile] [--no-predecode] [--frames <path>] [--format <text | json>]
+input is read from stdin unless --input or --input-str is given
+--time prints per-phase timings to stderr
+--profile prints the most executed Intcode addresses to stderr
+--no-predecode decodes every Intcode instruction as it's executed, for comparing against the default
+--frames writes every frame drawn by an Intcode screen in part 2 to a file, for intcode replay
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let mut args = env::args().skip(1);
+ARGS: run <year | start-end | all> [--inputs <dir>] [--jobs <n>] [--check] [--answers <dir>] [--format <text | json>]
+inputs are read from <dir>/<year>/dayNN.txt, default dir is inputs
+--jobs runs up to n days at a time on separate threads, default is 1
+--check compares answers against <dir>/<year>.txt, default dir is answers
+--format json prints one JSON record per day
 
-    let year = args.next().expect(USAGE);
-    let day = args.next().expect(USAGE);
-    let day: usize = day.parse().expect("day should be a non-negative integer");
+ARGS: intcode <disasm | asm | debug | play | replay> [--input <path> | --input-str <input>] [--fps <n>]
+disasm prints an annotated listing of an Intcode program
+asm assembles Intcode source into a comma-separated program
+debug steps through an Intcode program interactively, reading commands from stdin
+play runs an ASCII Intcode program, sending it lines from stdin and printing its output
+replay plays back frames written with --frames, at 20 frames per second unless --fps is given
+the program or source is read from stdin by default, except for debug and play which require --input
 
-    match year.as_str() {
-        "2015" => y2015::run_day(day),
-        "2016" => y2016::run_day(day),
-        "2017" => y2017::run_day(day),
-        "2018" => y2018::run_day(day),
-        "2019" => y2019::run_day(day),
-        "2020" => y2020::run_day(day),
-        _ => panic!("unexpected year: {year}"),
+exit codes: 2 usage error, 3 parse error, 4 runtime error, 5 unsupported input, 101 panic";
+
+fn usage_error(msg: String) -> SimpleError {
+    SimpleError::usage(format!("{msg}\n{USAGE}"))
+}
+
+fn next_arg(args: &mut impl Iterator<Item = String>, name: &str) -> Result<String, SimpleError> {
+    args.next()
+        .ok_or_else(|| usage_error(format!("missing argument: {name}")))
+}
+
+fn parse_arg<T: std::str::FromStr>(arg: &str, name: &str) -> Result<T, SimpleError> {
+    arg.parse()
+        .map_err(|_| usage_error(format!("invalid {name}: {arg}")))
+}
+
+fn run_years(mut args: impl Iterator<Item = String>) -> Result<(), SimpleError> {
+    let years = next_arg(&mut args, "years")?;
+    let years = runner::parse_year_range(&years)
+        .map_err(|err| usage_error(format!("invalid years: {err}")))?;
+
+    let mut inputs_dir = PathBuf::from("inputs");
+    let mut answers_dir: Option<PathBuf> = None;
+    let mut format = OutputFormat::Text;
+    let mut jobs = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = parse_arg(&next_arg(&mut args, "format")?, "format")?,
+            "--inputs" => inputs_dir = PathBuf::from(next_arg(&mut args, "inputs dir")?),
+            "--jobs" => jobs = parse_arg(&next_arg(&mut args, "jobs")?, "jobs")?,
+            "--check" => {
+                answers_dir.get_or_insert_with(|| PathBuf::from("answers"));
+            }
+            "--answers" => answers_dir = Some(PathBuf::from(next_arg(&mut args, "answers dir")?)),
+            _ => return Err(usage_error(format!("unexpected argument: {arg}"))),
+        }
+    }
+
+    if jobs == 0 {
+        return Err(usage_error(String::from("invalid jobs: 0")));
+    }
+
+    let solutions = runner::select_solutions(&years);
+    let mut results = runner::run_solutions(&solutions, &inputs_dir, jobs)?;
+    if let Some(answers_dir) = &answers_dir {
+        runner::check_results(&mut results, answers_dir)?;
+    }
+
+    match format {
+        OutputFormat::Text => {
+            print!("{}", runner::format_table(&results));
+            print!("{}", runner::format_mismatches(&results));
+        }
+        OutputFormat::Json => print!("{}", runner::format_json(&results)),
+    }
+
+    let failures = results.iter().filter(|result| result.is_failure()).count();
+    if failures > 0 {
+        return Err(SimpleError::runtime(format!(
+            "{failures} of {} days failed",
+            results.len()
+        )));
+    }
+
+    Ok(())
+}
+
+fn print_listing(input: &str) -> Result<(), SimpleError> {
+    let program = intcode::parse_program(input)?;
+    print!(
+        "{}",
+        disassembler::format_listing(&disassembler::disassemble(&program))
+    );
+    Ok(())
+}
+
+fn print_assembled(input: &str) -> Result<(), SimpleError> {
+    let program: Vec<_> = assembler::assemble(input)?
+        .iter()
+        .map(i64::to_string)
+        .collect();
+    println!("{}", program.join(","));
+    Ok(())
+}
+
+fn run_debugger(input: &str) -> Result<(), SimpleError> {
+    let program = intcode::parse_program(input)?;
+    debugger::run_repl(program, io::stdin().lock(), io::stdout().lock())
+}
+
+fn run_ascii_program(input: &str) -> Result<(), SimpleError> {
+    let program = intcode::parse_program(input)?;
+    ascii::AsciiProgram::new(program).play(io::stdin().lock(), io::stdout().lock())
+}
+
+fn run_intcode(mut args: impl Iterator<Item = String>) -> Result<(), SimpleError> {
+    let name = next_arg(&mut args, "intcode command")?;
+
+    let mut input = InputSource::Stdin;
+    let mut fps = 20;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input = InputSource::File(PathBuf::from(next_arg(&mut args, "input")?)),
+            "--input-str" => input = InputSource::Str(next_arg(&mut args, "input string")?),
+            "--fps" if name == "replay" => fps = parse_arg(&next_arg(&mut args, "fps")?, "fps")?,
+            _ => return Err(usage_error(format!("unexpected argument: {arg}"))),
+        }
+    }
+
+    // The debugger and play mode read from stdin, so the program has to come from elsewhere
+    if (name == "debug" || name == "play") && matches!(input, InputSource::Stdin) {
+        return Err(usage_error(format!(
+            "intcode {name} requires --input or --input-str"
+        )));
+    }
+
+    match name.as_str() {
+        "disasm" => print_listing(&input.read()?),
+        "asm" => print_assembled(&input.read()?),
+        "debug" => run_debugger(&input.read()?),
+        "play" => run_ascii_program(&input.read()?),
+        "replay" => screen::replay(&input.read()?, fps, io::stdout().lock()),
+        _ => Err(usage_error(format!("unknown intcode command: {name}"))),
+    }
+}
+
+fn write_frame_file(
+    path: &Path,
+    input: &str,
+    write_frames: WriteFramesFn,
+) -> Result<(), SimpleError> {
+    let io_error = |action: &str, err| {
+        SimpleError::with_source(
+            ErrorKind::Runtime,
+            format!("could not {action} {}", path.display()),
+            err,
+        )
+    };
+
+    let file = File::create(path).map_err(|err| io_error("create", err))?;
+    let mut out = BufWriter::new(file);
+    write_frames(input, &mut out)?;
+    out.flush().map_err(|err| io_error("write frames to", err))
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), SimpleError> {
+    let year = next_arg(&mut args, "year")?;
+    if year == "run" {
+        return run_years(args);
+    }
+    if year == "intcode" {
+        return run_intcode(args);
+    }
+
+    let year: u32 = parse_arg(&year, "year")?;
+    let day: u32 = parse_arg(&next_arg(&mut args, "day")?, "day")?;
+
+    let solution = advent_of_code_past::find_solution(year, day)
+        .ok_or_else(|| usage_error(format!("no solution for year {year} day {day}")))?;
+
+    let mut input = InputSource::Stdin;
+    let mut show_times = false;
+    let mut show_profile = false;
+    let mut predecode = true;
+    let mut bench_runs: Option<usize> = None;
+    let mut format = OutputFormat::Text;
+    let mut frames: Option<PathBuf> = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input = InputSource::File(PathBuf::from(next_arg(&mut args, "input")?)),
+            "--input-str" => input = InputSource::Str(next_arg(&mut args, "input string")?),
+            "--time" => show_times = true,
+            "--profile" => show_profile = true,
+            "--no-predecode" => predecode = false,
+            "--frames" => frames = Some(PathBuf::from(next_arg(&mut args, "frames")?)),
+            "--bench" => bench_runs = Some(parse_arg(&next_arg(&mut args, "runs")?, "runs")?),
+            "--format" => format = parse_arg(&next_arg(&mut args, "format")?, "format")?,
+            _ => return Err(usage_error(format!("unexpected argument: {arg}"))),
+        }
+    }
+
+    // Benchmarks print their own report, which has no room for any of these
+    if bench_runs.is_some() {
+        let conflicts = [
+            ("--time", show_times),
+            ("--profile", show_profile),
+            ("--format json", format == OutputFormat::Json),
+            ("--frames", frames.is_some()),
+        ];
+        if let Some((flag, _)) = conflicts.into_iter().find(|&(_, given)| given) {
+            return Err(usage_error(format!("{flag} can't be used with --bench")));
+        }
+    }
+
+    let mut frame_writer = None;
+    if let Some(path) = &frames {
+        let write_frames = advent_of_code_past::find_frame_writer(solution)
+            .ok_or_else(|| usage_error(format!("year {year} day {day} doesn't draw any frames")))?;
+        frame_writer = Some((path, write_frames));
+
+        // The frames come from a second run, and stdin can only be read once
+        input = InputSource::Str(input.read()?);
+    }
+
+    let run = || {
+        match bench_runs {
+            Some(runs) => advent_of_code_past::bench_solution(solution, &input, runs)?,
+            None => advent_of_code_past::run_solution(
+                solution,
+                &input,
+                format,
+                show_times,
+                show_profile,
+            )?,
+        }
+
+        match frame_writer {
+            Some((path, write_frames)) => write_frame_file(path, &input.read()?, write_frames),
+            None => Ok(()),
+        }
+    };
+
+    if predecode {
+        run()
+    } else {
+        intcode::without_predecode(run)
+    }
+}
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            if let Some(location) = err.location() {
+                eprintln!(" --> {location}");
+                eprint!("{}", location.render());
+            }
+            for source in runner::error_sources(&err) {
+                eprintln!("caused by: {source}");
+            }
+
+            ExitCode::from(err.kind().exit_code())
+        }
     }
 }
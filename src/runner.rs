@@ -0,0 +1,779 @@
+use crate::answers::{AnswerKey, Check, CheckStatus};
+use crate::simpleerror::ErrorKind;
+use crate::timing::{Phase, PhaseTimes, Summary};
+use crate::{answers, timing, SimpleError, Solution, SOLUTIONS};
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+// Some solutions recurse deeply, so give workers as much stack as the main thread gets
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(SimpleError::usage(format!("invalid output format: {s}"))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    Solved(String, String),
+    // Error message followed by the messages of its source chain
+    Error(ErrorKind, String, Vec<String>),
+    Panic(String),
+    MissingInput(PathBuf),
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Error(..) | Self::Panic(..))
+    }
+
+    fn status(&self) -> &'static str {
+        match self {
+            Self::Solved(..) => "ok",
+            Self::Error(kind, ..) => match kind {
+                ErrorKind::Usage => "usage error",
+                ErrorKind::Parse => "parse error",
+                ErrorKind::Runtime => "runtime error",
+                ErrorKind::UnsupportedInput => "unsupported",
+            },
+            Self::Panic(..) => "panic",
+            Self::MissingInput(..) => "no input",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub year: u32,
+    pub day: u32,
+    pub outcome: Outcome,
+    pub times: PhaseTimes,
+    pub check: Option<Check>,
+}
+
+impl DayResult {
+    pub fn is_failure(&self) -> bool {
+        let check_failed = self
+            .check
+            .as_ref()
+            .is_some_and(|check| check.status == CheckStatus::Fail);
+        self.outcome.is_failure() || check_failed
+    }
+}
+
+// Parses "all", a single year such as "2018", or an inclusive range such as "2015-2017"
+pub fn parse_year_range(s: &str) -> Result<RangeInclusive<u32>, SimpleError> {
+    let range = if s == "all" {
+        match (SOLUTIONS.first(), SOLUTIONS.last()) {
+            (Some(first), Some(last)) => first.year..=last.year,
+            _ => {
+                return Err(SimpleError::runtime(String::from(
+                    "no solutions registered",
+                )))
+            }
+        }
+    } else if let Some((start, end)) = s.split_once('-') {
+        start.parse()?..=end.parse()?
+    } else {
+        let year = s.parse()?;
+        year..=year
+    };
+
+    if range.is_empty() {
+        return Err(SimpleError::usage(format!("invalid year range: {s}")));
+    }
+
+    Ok(range)
+}
+
+pub fn select_solutions(years: &RangeInclusive<u32>) -> Vec<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .filter(|solution| years.contains(&solution.year))
+        .collect()
+}
+
+pub fn input_path(inputs_dir: &Path, solution: &Solution) -> PathBuf {
+    inputs_dir
+        .join(solution.year.to_string())
+        .join(format!("day{:02}.txt", solution.day))
+}
+
+pub fn run_day(solution: &Solution, input: &str) -> DayResult {
+    let (result, times) = timing::record(|| panic::catch_unwind(|| (solution.solve)(input)));
+
+    let outcome = match result {
+        Ok(Ok((solution1, solution2))) => Outcome::Solved(solution1, solution2),
+        Ok(Err(err)) => Outcome::Error(err.kind(), err.to_string(), error_sources(&err)),
+        Err(payload) => Outcome::Panic(panic_message(payload.as_ref())),
+    };
+
+    DayResult {
+        year: solution.year,
+        day: solution.day,
+        outcome,
+        times,
+        check: None,
+    }
+}
+
+// Runs every solution on its input from inputs_dir, using up to the given number of worker threads.
+// Results are returned in the same order as the solutions
+pub fn run_solutions(
+    solutions: &[&Solution],
+    inputs_dir: &Path,
+    jobs: usize,
+) -> Result<Vec<DayResult>, SimpleError> {
+    without_panic_output(|| {
+        if jobs <= 1 {
+            return Ok(solutions
+                .iter()
+                .map(|&solution| run_day_from_dir(solution, inputs_dir))
+                .collect());
+        }
+
+        let next_index = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| -> Result<(), SimpleError> {
+            for _ in 0..jobs.min(solutions.len()) {
+                let next_index = &next_index;
+                let sender = sender.clone();
+                thread::Builder::new()
+                    .stack_size(WORKER_STACK_SIZE)
+                    .spawn_scoped(scope, move || loop {
+                        let i = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(&solution) = solutions.get(i) else {
+                            break;
+                        };
+                        let _ = sender.send((i, run_day_from_dir(solution, inputs_dir)));
+                    })?;
+            }
+            Ok(())
+        })?;
+        drop(sender);
+
+        let mut results: Vec<Option<DayResult>> = vec![None; solutions.len()];
+        for (i, result) in receiver {
+            results[i] = Some(result);
+        }
+        Ok(results.into_iter().flatten().collect())
+    })
+}
+
+fn run_day_from_dir(solution: &Solution, inputs_dir: &Path) -> DayResult {
+    let path = input_path(inputs_dir, solution);
+    match fs::read_to_string(&path) {
+        Ok(input) => run_day(solution, &input),
+        Err(_) => DayResult {
+            year: solution.year,
+            day: solution.day,
+            outcome: Outcome::MissingInput(path),
+            times: PhaseTimes::default(),
+            check: None,
+        },
+    }
+}
+
+// Panics are reported in the results, don't also dump them to stderr
+pub fn without_panic_output<T>(f: impl FnOnce() -> T) -> T {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = f();
+
+    panic::set_hook(default_hook);
+
+    result
+}
+
+pub fn error_sources(err: &dyn Error) -> Vec<String> {
+    let mut sources = Vec::new();
+
+    let mut source = err.source();
+    while let Some(err) = source {
+        sources.push(err.to_string());
+        source = err.source();
+    }
+
+    sources
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        String::from(*s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("unknown panic payload")
+    }
+}
+
+// Compares every solved day against <answers_dir>/<year>.txt
+pub fn check_results(results: &mut [DayResult], answers_dir: &Path) -> Result<(), SimpleError> {
+    let mut answer_keys: HashMap<u32, AnswerKey> = HashMap::new();
+
+    for result in results {
+        if let Outcome::Solved(solution1, solution2) = &result.outcome {
+            let answer_key = match answer_keys.entry(result.year) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let path = answers::answers_path(answers_dir, result.year);
+                    entry.insert(AnswerKey::load(&path)?)
+                }
+            };
+            result.check = Some(answer_key.check(result.day, (solution1, solution2)));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub answers: (String, String),
+    pub times: Vec<PhaseTimes>,
+}
+
+// Runs a day the given number of times and collects the phase times of every run
+pub fn bench_day(
+    solution: &Solution,
+    input: &str,
+    runs: usize,
+) -> Result<BenchResult, SimpleError> {
+    let mut answers = None;
+    let mut all_times = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let (result, times) = timing::record(|| (solution.solve)(input));
+        answers = Some(result?);
+        all_times.push(times);
+    }
+
+    let answers =
+        answers.ok_or_else(|| SimpleError::usage(String::from("bench requires at least 1 run")))?;
+
+    Ok(BenchResult {
+        answers,
+        times: all_times,
+    })
+}
+
+pub fn format_table(results: &[DayResult]) -> String {
+    let mut header = vec![
+        "Year", "Day", "Status", "Part 1", "Part 2", "Parse", "Time 1", "Time 2", "Total",
+    ];
+
+    let checked = results.iter().any(|result| result.check.is_some());
+    if checked {
+        header.push("Check");
+    }
+
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            let (part1, part2) = match &result.outcome {
+                Outcome::Solved(solution1, solution2) => (solution1.clone(), solution2.clone()),
+                Outcome::Error(_, msg, _) | Outcome::Panic(msg) => (msg.clone(), String::new()),
+                Outcome::MissingInput(path) => (path.display().to_string(), String::new()),
+            };
+
+            let mut row = vec![
+                result.year.to_string(),
+                result.day.to_string(),
+                String::from(result.outcome.status()),
+                part1,
+                part2,
+            ];
+
+            match result.outcome {
+                Outcome::MissingInput(..) => row.extend(vec![String::new(); 4]),
+                _ => {
+                    row.extend(Phase::ALL.map(|phase| format_time(result.times.get(phase))));
+                    row.push(format_time(Some(result.times.total)));
+                }
+            }
+
+            if checked {
+                let check = result.check.as_ref().map(|check| check.status.name());
+                row.push(String::from(check.unwrap_or("-")));
+            }
+
+            row
+        })
+        .collect();
+
+    format_rows(&header, &rows)
+}
+
+// One JSON object per line
+pub fn format_json(results: &[DayResult]) -> String {
+    let mut s = String::new();
+    for result in results {
+        s.push_str(&format_json_record(result));
+        s.push('\n');
+    }
+    s
+}
+
+pub fn format_json_record(result: &DayResult) -> String {
+    let (part1, part2) = match &result.outcome {
+        Outcome::Solved(solution1, solution2) => (json_string(solution1), json_string(solution2)),
+        _ => (String::from("null"), String::from("null")),
+    };
+
+    let error = match &result.outcome {
+        Outcome::Solved(..) => String::from("null"),
+        Outcome::Error(kind, msg, sources) => json_error(kind.name(), msg, sources),
+        Outcome::Panic(msg) => json_error("panic", msg, &[]),
+        Outcome::MissingInput(path) => json_error(
+            "no input",
+            &format!("could not read {}", path.display()),
+            &[],
+        ),
+    };
+
+    let elapsed_ms = result.times.total.as_secs_f64() * 1000.0;
+
+    let mut record = format!(
+        "{{\"year\":{},\"day\":{},\"part1\":{part1},\"part2\":{part2},\"elapsed_ms\":{elapsed_ms:.3},\"error\":{error}",
+        result.year, result.day
+    );
+    if let Some(check) = &result.check {
+        record.push_str(&format!(",\"check\":\"{}\"", check.status.name()));
+    }
+    record.push('}');
+
+    record
+}
+
+fn json_error(kind: &str, msg: &str, sources: &[String]) -> String {
+    let sources: Vec<_> = sources.iter().map(|source| json_string(source)).collect();
+    format!(
+        "{{\"kind\":{},\"message\":{},\"sources\":[{}]}}",
+        json_string(kind),
+        json_string(msg),
+        sources.join(",")
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub fn format_mismatches(results: &[DayResult]) -> String {
+    let mut s = String::new();
+
+    for result in results {
+        let Some(check) = &result.check else {
+            continue;
+        };
+
+        for mismatch in &check.mismatches {
+            s.push_str(&format!(
+                "{} day {} part {}: expected {}, got {}\n",
+                result.year, result.day, mismatch.part, mismatch.expected, mismatch.actual
+            ));
+        }
+    }
+
+    s
+}
+
+pub fn format_phase_times(times: &PhaseTimes) -> String {
+    let rows: Vec<Vec<String>> = Phase::ALL
+        .iter()
+        .filter_map(|&phase| {
+            let time = times.get(phase)?;
+            Some(vec![String::from(phase.name()), format_time(Some(time))])
+        })
+        .chain(std::iter::once(vec![
+            String::from("total"),
+            format_time(Some(times.total)),
+        ]))
+        .collect();
+
+    format_rows(&["Phase", "Time"], &rows)
+}
+
+pub fn format_bench_summary(all_times: &[PhaseTimes]) -> String {
+    let mut rows = Vec::new();
+
+    for phase in Phase::ALL {
+        let durations: Vec<_> = all_times
+            .iter()
+            .filter_map(|times| times.get(phase))
+            .collect();
+        if let Some(summary) = Summary::from_durations(&durations) {
+            rows.push(summary_row(phase.name(), summary));
+        }
+    }
+
+    let totals: Vec<_> = all_times.iter().map(|times| times.total).collect();
+    if let Some(summary) = Summary::from_durations(&totals) {
+        rows.push(summary_row("total", summary));
+    }
+
+    format_rows(&["Phase", "Min", "Median", "Max"], &rows)
+}
+
+fn summary_row(name: &str, summary: Summary) -> Vec<String> {
+    vec![
+        String::from(name),
+        format_time(Some(summary.min)),
+        format_time(Some(summary.median)),
+        format_time(Some(summary.max)),
+    ]
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    match time {
+        Some(time) => format!("{time:.1?}"),
+        None => String::from("-"),
+    }
+}
+
+fn format_rows(header: &[&str], rows: &[Vec<String>]) -> String {
+    let header: Vec<_> = header.iter().map(|&s| String::from(s)).collect();
+
+    let mut widths = vec![0; header.len()];
+    for row in std::iter::once(&header).chain(rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            let cell_width = cell.lines().map(|line| line.chars().count()).max();
+            *width = (*width).max(cell_width.unwrap_or(0));
+        }
+    }
+
+    let mut s = String::new();
+    push_row(&mut s, &header, &widths);
+    let separator: Vec<_> = widths.iter().map(|&width| "-".repeat(width)).collect();
+    push_row(&mut s, &separator, &widths);
+    for row in rows {
+        push_row(&mut s, row, &widths);
+    }
+
+    s
+}
+
+// Multi-line cells (e.g. rendered letters) continue on the following lines of the same column
+fn push_row(s: &mut String, row: &[String], widths: &[usize]) {
+    let height = row
+        .iter()
+        .map(|cell| cell.lines().count())
+        .max()
+        .unwrap_or(0);
+
+    for i in 0..height.max(1) {
+        let line: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, &width)| {
+                let cell_line = cell.lines().nth(i).unwrap_or("");
+                format!("{cell_line:width$}")
+            })
+            .collect();
+        s.push_str(line.join(" | ").trim_end());
+        s.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Mismatch;
+    use std::{env, process};
+
+    #[test]
+    fn test_parse_year_range() {
+        assert_eq!(Ok(2018..=2018), parse_year_range("2018"));
+        assert_eq!(Ok(2015..=2017), parse_year_range("2015-2017"));
+        assert_eq!(Ok(2015..=2020), parse_year_range("all"));
+
+        assert!(parse_year_range("2017-2015").is_err());
+        assert!(parse_year_range("twenty").is_err());
+    }
+
+    #[test]
+    fn test_select_solutions() {
+        assert_eq!(25, select_solutions(&(2018..=2018)).len());
+        assert_eq!(75, select_solutions(&(2015..=2017)).len());
+        assert!(select_solutions(&(2030..=2030)).is_empty());
+    }
+
+    #[test]
+    fn test_input_path() {
+        let solution = crate::find_solution(2018, 7).unwrap();
+        assert_eq!(
+            Path::new("inputs/2018/day07.txt"),
+            input_path(Path::new("inputs"), solution)
+        );
+    }
+
+    #[test]
+    fn test_run_day() {
+        let solution = crate::find_solution(2015, 1).unwrap();
+
+        let result = run_day(solution, "()())");
+        assert_eq!(
+            Outcome::Solved(String::from("-1"), String::from("5")),
+            result.outcome
+        );
+
+        let result = run_day(solution, "");
+        assert_eq!(
+            Outcome::Error(
+                ErrorKind::Parse,
+                String::from("input is empty, expected a single line"),
+                Vec::new()
+            ),
+            result.outcome
+        );
+    }
+
+    #[test]
+    fn test_run_solutions_parallel() {
+        let inputs_dir = env::temp_dir().join(format!("aoc-runner-test-{}", process::id()));
+        fs::create_dir_all(inputs_dir.join("2015")).unwrap();
+        fs::write(inputs_dir.join("2015/day01.txt"), "())").unwrap();
+        fs::write(inputs_dir.join("2015/day03.txt"), "^v").unwrap();
+
+        let solutions = select_solutions(&(2015..=2015));
+        let serial = run_solutions(&solutions[..4], &inputs_dir, 1).unwrap();
+        let parallel = run_solutions(&solutions[..4], &inputs_dir, 3).unwrap();
+
+        fs::remove_dir_all(&inputs_dir).unwrap();
+
+        let outcomes = |results: &[DayResult]| -> Vec<(u32, Outcome)> {
+            results
+                .iter()
+                .map(|result| (result.day, result.outcome.clone()))
+                .collect()
+        };
+        assert_eq!(outcomes(&serial), outcomes(&parallel));
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            parallel.iter().map(|result| result.day).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Outcome::Solved(String::from("-1"), String::from("3")),
+            parallel[0].outcome
+        );
+        assert!(matches!(parallel[1].outcome, Outcome::MissingInput(..)));
+        assert!(parallel[2].times.part1.is_some());
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let solution = crate::find_solution(2015, 1).unwrap();
+
+        let result = bench_day(solution, "()())", 3).unwrap();
+        assert_eq!((String::from("-1"), String::from("5")), result.answers);
+        assert_eq!(3, result.times.len());
+        assert!(result
+            .times
+            .iter()
+            .all(|times| [Phase::Parse, Phase::Part1, Phase::Part2]
+                .iter()
+                .all(|&phase| times.get(phase).is_some())));
+
+        assert!(bench_day(solution, "()())", 0).is_err());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let results = vec![
+            DayResult {
+                year: 2016,
+                day: 8,
+                outcome: Outcome::Solved(String::from("6"), String::from("#.#\n.#.")),
+                check: None,
+                times: PhaseTimes {
+                    parse: Some(Duration::from_micros(500)),
+                    part1: Some(Duration::from_micros(400)),
+                    part2: None,
+                    total: Duration::from_micros(1500),
+                },
+            },
+            DayResult {
+                year: 2016,
+                day: 9,
+                outcome: Outcome::Panic(String::from("oops")),
+                check: None,
+                times: PhaseTimes {
+                    total: Duration::from_millis(2),
+                    ..PhaseTimes::default()
+                },
+            },
+        ];
+
+        let expected = "\
+Year | Day | Status | Part 1 | Part 2 | Parse   | Time 1  | Time 2 | Total
+---- | --- | ------ | ------ | ------ | ------- | ------- | ------ | -----
+2016 | 8   | ok     | 6      | #.#    | 500.0µs | 400.0µs | -      | 1.5ms
+     |     |        |        | .#.    |         |         |        |
+2016 | 9   | panic  | oops   |        | -       | -       | -      | 2.0ms
+";
+        assert_eq!(expected, format_table(&results));
+    }
+
+    #[test]
+    fn test_format_table_checked() {
+        let solved = |day, check| DayResult {
+            year: 2015,
+            day,
+            outcome: Outcome::Solved(String::from("1"), String::from("2")),
+            times: PhaseTimes::default(),
+            check,
+        };
+
+        let results = vec![
+            solved(
+                1,
+                Some(Check {
+                    status: CheckStatus::Pass,
+                    mismatches: Vec::new(),
+                }),
+            ),
+            solved(
+                2,
+                Some(Check {
+                    status: CheckStatus::Fail,
+                    mismatches: vec![Mismatch {
+                        part: 2,
+                        expected: String::from("3"),
+                        actual: String::from("2"),
+                    }],
+                }),
+            ),
+            DayResult {
+                year: 2015,
+                day: 3,
+                outcome: Outcome::MissingInput(PathBuf::from("day03.txt")),
+                times: PhaseTimes::default(),
+                check: None,
+            },
+        ];
+
+        let expected = "\
+Year | Day | Status   | Part 1    | Part 2 | Parse | Time 1 | Time 2 | Total | Check
+---- | --- | -------- | --------- | ------ | ----- | ------ | ------ | ----- | -----
+2015 | 1   | ok       | 1         | 2      | -     | -      | -      | 0.0ns | PASS
+2015 | 2   | ok       | 1         | 2      | -     | -      | -      | 0.0ns | FAIL
+2015 | 3   | no input | day03.txt |        |       |        |        |       | -
+";
+        assert_eq!(expected, format_table(&results));
+
+        assert_eq!(
+            "2015 day 2 part 2: expected 3, got 2\n",
+            format_mismatches(&results)
+        );
+        assert!(results[1].is_failure());
+        assert!(!results[0].is_failure());
+    }
+
+    #[test]
+    fn test_error_sources() {
+        let err = SimpleError::from("x".parse::<i32>().unwrap_err());
+        assert_eq!(
+            vec![String::from("invalid digit found in string")],
+            error_sources(&err)
+        );
+
+        assert!(error_sources(&SimpleError::new(String::from("no source"))).is_empty());
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(r#""plain""#, json_string("plain"));
+        assert_eq!(r#""a\"b\\c\nd""#, json_string("a\"b\\c\nd"));
+        assert_eq!(r#""\u0001""#, json_string("\u{1}"));
+    }
+
+    #[test]
+    fn test_format_json() {
+        let results = vec![
+            DayResult {
+                year: 2019,
+                day: 8,
+                outcome: Outcome::Solved(String::from("1560"), String::from("#.\n.#")),
+                times: PhaseTimes {
+                    total: Duration::from_micros(1500),
+                    ..PhaseTimes::default()
+                },
+                check: Some(Check {
+                    status: CheckStatus::Pass,
+                    mismatches: Vec::new(),
+                }),
+            },
+            DayResult {
+                year: 2019,
+                day: 9,
+                outcome: Outcome::Error(
+                    ErrorKind::Parse,
+                    String::from("invalid digit found in string"),
+                    vec![String::from("source")],
+                ),
+                times: PhaseTimes::default(),
+                check: None,
+            },
+        ];
+
+        let expected = r##"{"year":2019,"day":8,"part1":"1560","part2":"#.\n.#","elapsed_ms":1.500,"error":null,"check":"PASS"}
+{"year":2019,"day":9,"part1":null,"part2":null,"elapsed_ms":0.000,"error":{"kind":"parse","message":"invalid digit found in string","sources":["source"]}}
+"##;
+        assert_eq!(expected, format_json(&results));
+    }
+
+    #[test]
+    fn test_format_bench_summary() {
+        let all_times = [3, 1, 2].map(|ms| PhaseTimes {
+            part1: Some(Duration::from_millis(ms)),
+            total: Duration::from_millis(ms * 2),
+            ..PhaseTimes::default()
+        });
+
+        let expected = "\
+Phase  | Min   | Median | Max
+------ | ----- | ------ | -----
+part 1 | 1.0ms | 2.0ms  | 3.0ms
+total  | 2.0ms | 4.0ms  | 6.0ms
+";
+        assert_eq!(expected, format_bench_summary(&all_times));
+    }
+}
@@ -1,24 +1,183 @@
 use std::char::ParseCharError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::num::ParseIntError;
+use std::io;
+use std::num::{ParseFloatError, ParseIntError, TryFromIntError};
+use std::str::FromStr;
 use std::string::FromUtf8Error;
 
+// What went wrong, so that scripts can tell bad input apart from other failures. Panics are
+// reserved for bugs and exit with Rust's default panic exit code of 101
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorKind {
+    // Invalid command line arguments
+    Usage,
+    // Malformed puzzle input
+    Parse,
+    // Failure while running a solution, e.g. a puzzle program misbehaving
+    Runtime,
+    // Well-formed input that the solution can't solve
+    UnsupportedInput,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::Usage => 2,
+            Self::Parse => 3,
+            Self::Runtime => 4,
+            Self::UnsupportedInput => 5,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Usage => "usage",
+            Self::Parse => "parse",
+            Self::Runtime => "runtime",
+            Self::UnsupportedInput => "unsupported input",
+        }
+    }
+}
+
+// Where in the puzzle input an error was found
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Location {
+    // 1-based. Parsers that only see a single line leave this for the caller to fill in
+    pub line: Option<usize>,
+    // 1-based, counted in chars
+    pub column: usize,
+    pub snippet: String,
+}
+
+impl Location {
+    // Renders the snippet with a caret under the column, e.g.
+    //   |
+    // 3 | inc e
+    //   |     ^
+    pub fn render(&self) -> String {
+        let line_number = self.line.map(|line| line.to_string()).unwrap_or_default();
+        let gutter = " ".repeat(line_number.len());
+        let indent = " ".repeat(self.column.saturating_sub(1));
+
+        format!(
+            "{gutter} |\n{line_number} | {}\n{gutter} | {indent}^\n",
+            self.snippet
+        )
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}, column {}", self.column),
+            None => write!(f, "column {}", self.column),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SimpleError {
+    kind: ErrorKind,
     msg: String,
-    source: Option<Box<dyn Error>>,
+    source: Option<Box<dyn Error + Send + Sync>>,
+    location: Option<Location>,
 }
 
 impl SimpleError {
+    // An error in malformed input. Failures while solving well-formed input should use runtime or
+    // unsupported_input instead
     pub fn new(msg: String) -> Self {
-        Self { msg, source: None }
+        Self::with_kind(ErrorKind::Parse, msg)
+    }
+
+    pub fn with_kind(kind: ErrorKind, msg: String) -> Self {
+        Self {
+            kind,
+            msg,
+            source: None,
+            location: None,
+        }
+    }
+
+    pub fn with_source(
+        kind: ErrorKind,
+        msg: String,
+        source: impl Error + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            source: Some(Box::new(source)),
+            ..Self::with_kind(kind, msg)
+        }
+    }
+
+    pub fn usage(msg: String) -> Self {
+        Self::with_kind(ErrorKind::Usage, msg)
+    }
+
+    pub fn runtime(msg: String) -> Self {
+        Self::with_kind(ErrorKind::Runtime, msg)
+    }
+
+    pub fn unsupported_input(msg: String) -> Self {
+        Self::with_kind(ErrorKind::UnsupportedInput, msg)
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    // Points the error at token, which should be a substring of line. Keeps any location that
+    // was already attached by a more specific parser
+    pub fn at_token(mut self, line: &str, token: &str) -> Self {
+        if self.location.is_none() {
+            let offset = subslice_offset(line, token)
+                .or_else(|| line.find(token))
+                .unwrap_or(0);
+            self.location = Some(Location {
+                line: None,
+                column: line[..offset].chars().count() + 1,
+                snippet: String::from(line),
+            });
+        }
+        self
+    }
+
+    // Fills in the 1-based line number, pointing at the start of the line if no more specific
+    // location was attached
+    pub fn at_line(self, line_number: usize, line: &str) -> Self {
+        let mut err = self.at_token(line, line);
+        if let Some(location) = &mut err.location {
+            location.line.get_or_insert(line_number);
+        }
+        err
     }
 }
 
+// The byte offset of token within line if token was sliced out of line
+fn subslice_offset(line: &str, token: &str) -> Option<usize> {
+    let offset = (token.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    (offset + token.len() <= line.len()).then_some(offset)
+}
+
+// Parses a token of line, pointing any error at the token
+pub fn parse_token<T>(line: &str, token: &str) -> Result<T, SimpleError>
+where
+    T: FromStr,
+    SimpleError: From<T::Err>,
+{
+    token
+        .parse()
+        .map_err(|err| SimpleError::from(err).at_token(line, token))
+}
+
 impl PartialEq for SimpleError {
     fn eq(&self, other: &Self) -> bool {
-        self.msg == other.msg
+        self.kind == other.kind && self.msg == other.msg
     }
 }
 
@@ -30,23 +189,114 @@ impl Display for SimpleError {
 
 impl Error for SimpleError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.source.as_deref()
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
     }
 }
 
-trait ErrorWrapper: Error + 'static {}
+trait ErrorWrapper: Error + Send + Sync + 'static {
+    // Wrapped errors mostly come from parsing the puzzle input
+    const KIND: ErrorKind = ErrorKind::Parse;
+}
 
 impl ErrorWrapper for ParseIntError {}
 
+impl ErrorWrapper for ParseFloatError {}
+
 impl ErrorWrapper for ParseCharError {}
 
 impl ErrorWrapper for FromUtf8Error {}
 
+impl ErrorWrapper for TryFromIntError {
+    const KIND: ErrorKind = ErrorKind::Runtime;
+}
+
+impl ErrorWrapper for io::Error {
+    const KIND: ErrorKind = ErrorKind::Runtime;
+}
+
 impl<T: ErrorWrapper> From<T> for SimpleError {
     fn from(t: T) -> Self {
         Self {
+            kind: T::KIND,
             msg: t.to_string(),
             source: Some(Box::new(t)),
+            location: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_conversions() {
+        let err = SimpleError::from("1.5x".parse::<f64>().unwrap_err());
+        assert_eq!(ErrorKind::Parse, err.kind());
+        assert!(err.source().is_some());
+
+        let err = SimpleError::from(u8::try_from(300).unwrap_err());
+        assert_eq!(ErrorKind::Runtime, err.kind());
+
+        let err = SimpleError::from(io::Error::other("disk on fire"));
+        assert_eq!(ErrorKind::Runtime, err.kind());
+        assert_eq!("disk on fire", err.to_string());
+
+        // Errors can be sent back from worker threads
+        let err = thread::spawn(|| SimpleError::from("x".parse::<i32>().unwrap_err()))
+            .join()
+            .unwrap();
+        assert_eq!(ErrorKind::Parse, err.kind());
+    }
+
+    #[test]
+    fn test_location() {
+        let line = "cpy 41 e";
+        let err =
+            SimpleError::new(String::from("invalid register id: e")).at_token(line, &line[7..]);
+        let err = err.at_line(3, line);
+
+        assert_eq!(
+            Some(&Location {
+                line: Some(3),
+                column: 8,
+                snippet: String::from(line),
+            }),
+            err.location()
+        );
+        assert_eq!("line 3, column 8", err.location().unwrap().to_string());
+        assert_eq!(
+            "  |\n3 | cpy 41 e\n  |        ^\n",
+            err.location().unwrap().render()
+        );
+
+        let err = parse_token::<i64>("jmp +x", "+x")
+            .unwrap_err()
+            .at_line(10, "jmp +x");
+        assert_eq!(ErrorKind::Parse, err.kind());
+        assert_eq!(5, err.location().unwrap().column);
+
+        let err = SimpleError::new(String::from("invalid line")).at_line(2, "foo");
+        assert_eq!(1, err.location().unwrap().column);
+    }
+
+    #[test]
+    fn test_exit_codes_distinct() {
+        let kinds = [
+            ErrorKind::Usage,
+            ErrorKind::Parse,
+            ErrorKind::Runtime,
+            ErrorKind::UnsupportedInput,
+        ];
+
+        for (i, a) in kinds.iter().enumerate() {
+            for b in &kinds[i + 1..] {
+                assert_ne!(a.exit_code(), b.exit_code());
+            }
+            assert_ne!(101, a.exit_code());
         }
     }
 }
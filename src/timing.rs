@@ -0,0 +1,205 @@
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Self; 3] = [Self::Parse, Self::Part1, Self::Part2];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Part1 => "part 1",
+            Self::Part2 => "part 2",
+        }
+    }
+}
+
+// Time spent in each phase, excluding any phases nested inside it. A phase that never ran is None
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct PhaseTimes {
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+    pub total: Duration,
+}
+
+impl PhaseTimes {
+    pub fn get(&self, phase: Phase) -> Option<Duration> {
+        match phase {
+            Phase::Parse => self.parse,
+            Phase::Part1 => self.part1,
+            Phase::Part2 => self.part2,
+        }
+    }
+
+    fn get_mut(&mut self, phase: Phase) -> &mut Option<Duration> {
+        match phase {
+            Phase::Parse => &mut self.parse,
+            Phase::Part1 => &mut self.part1,
+            Phase::Part2 => &mut self.part2,
+        }
+    }
+}
+
+struct ActivePhase {
+    phase: Phase,
+    start: Instant,
+    nested: Duration,
+}
+
+#[derive(Default)]
+struct Recorder {
+    times: PhaseTimes,
+    stack: Vec<ActivePhase>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+// Attributes the time spent in f to the given phase. This is a plain call unless the current
+// thread is inside of record()
+pub fn timed<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    let recording = RECORDER.with(|recorder| match recorder.borrow_mut().as_mut() {
+        Some(recorder) => {
+            recorder.stack.push(ActivePhase {
+                phase,
+                start: Instant::now(),
+                nested: Duration::ZERO,
+            });
+            true
+        }
+        None => false,
+    });
+
+    let result = f();
+
+    if recording {
+        RECORDER.with(|recorder| {
+            if let Some(recorder) = recorder.borrow_mut().as_mut() {
+                let active = recorder
+                    .stack
+                    .pop()
+                    .expect("phase stack should not be empty");
+                let elapsed = active.start.elapsed();
+
+                let phase_time = recorder.times.get_mut(active.phase);
+                *phase_time.get_or_insert(Duration::ZERO) += elapsed.saturating_sub(active.nested);
+
+                if let Some(parent) = recorder.stack.last_mut() {
+                    parent.nested += elapsed;
+                }
+            }
+        });
+    }
+
+    result
+}
+
+pub fn parse<T>(f: impl FnOnce() -> T) -> T {
+    timed(Phase::Parse, f)
+}
+
+pub fn part_1<T>(f: impl FnOnce() -> T) -> T {
+    timed(Phase::Part1, f)
+}
+
+pub fn part_2<T>(f: impl FnOnce() -> T) -> T {
+    timed(Phase::Part2, f)
+}
+
+// Runs f with phase recording enabled on the current thread
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, PhaseTimes) {
+    let previous = RECORDER.with(|recorder| recorder.replace(Some(Recorder::default())));
+
+    let start = Instant::now();
+    let result = f();
+    let total = start.elapsed();
+
+    let recorder = RECORDER.with(|recorder| recorder.replace(previous));
+    let times = recorder.map(|recorder| recorder.times).unwrap_or_default();
+
+    (result, PhaseTimes { total, ..times })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Summary {
+    pub fn from_durations(durations: &[Duration]) -> Option<Self> {
+        let mut durations = durations.to_vec();
+        durations.sort();
+
+        Some(Self {
+            min: *durations.first()?,
+            median: durations[durations.len() / 2],
+            max: *durations.last()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_not_recording() {
+        assert_eq!(5, part_1(|| 5));
+    }
+
+    #[test]
+    fn test_record_nested() {
+        let (result, times) = record(|| {
+            let a = part_1(|| {
+                parse(|| thread::sleep(Duration::from_millis(50)));
+                thread::sleep(Duration::from_millis(5));
+                1
+            });
+            let b = part_2(|| 2);
+            a + b
+        });
+
+        assert_eq!(3, result);
+
+        let parse_time = times.get(Phase::Parse).unwrap();
+        let part_1_time = times.get(Phase::Part1).unwrap();
+        assert!(parse_time >= Duration::from_millis(50));
+        assert!(part_1_time >= Duration::from_millis(5));
+        assert!(part_1_time < Duration::from_millis(50));
+        assert!(times.get(Phase::Part2).is_some());
+        assert!(times.total >= parse_time + part_1_time);
+    }
+
+    #[test]
+    fn test_record_missing_phase() {
+        let (_, times) = record(|| part_1(|| ()));
+        assert!(times.get(Phase::Parse).is_none());
+        assert!(times.get(Phase::Part2).is_none());
+    }
+
+    #[test]
+    fn test_summary() {
+        let durations = [5, 1, 3, 4, 2].map(Duration::from_millis);
+        assert_eq!(
+            Some(Summary {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }),
+            Summary::from_durations(&durations)
+        );
+
+        assert_eq!(None, Summary::from_durations(&[]));
+    }
+}
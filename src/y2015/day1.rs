@@ -3,20 +3,19 @@
 //! <https://adventofcode.com/2015/day/1>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let increments = parse_input(input)?;
+    let increments = crate::timing::parse(|| parse_input(input))?;
     let floor = increments
         .into_iter()
         .reduce(|a, b| a + b)
-        .expect("input should not be empty");
+        .ok_or_else(|| SimpleError::new(String::from("input should not be empty")))?;
 
     Ok(floor)
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let increments = parse_input(input)?;
+    let increments = crate::timing::parse(|| parse_input(input))?;
 
     let mut floor = 0;
     for (i, step) in increments.into_iter().enumerate() {
@@ -26,7 +25,7 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "no solution found for part 2",
     )))
 }
@@ -43,9 +42,9 @@ fn parse_input(input: &str) -> Result<Vec<i32>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(i32, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
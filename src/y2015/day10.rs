@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2015/day/10>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part(input: &str, iterations: usize) -> Result<usize, SimpleError> {
     let line = crate::read_single_line(input)?;
@@ -39,9 +38,9 @@ fn solve_part(input: &str, iterations: usize) -> Result<usize, SimpleError> {
     Ok(current_sequence.len())
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, 40)?;
-    let solution2 = solve_part(input, 50)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, 40))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, 50))?;
 
     Ok((solution1, solution2))
 }
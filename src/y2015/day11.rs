@@ -4,23 +4,27 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 
 fn solve_part(input: &str) -> Result<String, SimpleError> {
     let line = crate::read_single_line(input)?;
 
-    if line.len() != 8 {
+    if line.len() != 8 || !line.bytes().all(|b| b.is_ascii_lowercase()) {
         return Err(SimpleError::new(format!("invalid line format: {line}")));
     }
 
     let mut s = line.as_bytes().to_vec();
     loop {
-        *s.last_mut().unwrap() += 1;
-
         let mut i = s.len() - 1;
+        s[i] += 1;
+
         while s[i] == b'z' + 1 {
             s[i] = b'a';
 
+            if i == 0 {
+                return Err(SimpleError::unsupported_input(format!(
+                    "no valid password after {line}"
+                )));
+            }
             i -= 1;
             s[i] += 1;
 
@@ -56,9 +60,9 @@ fn is_valid(s: &[u8]) -> bool {
         >= 2
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part(input)?;
-    let solution2 = solve_part(&solution1)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part(&solution1))?;
 
     Ok((solution1, solution2))
 }
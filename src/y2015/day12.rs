@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 use std::iter::Peekable;
 
 #[derive(Debug)]
@@ -40,13 +39,13 @@ impl JsonValue {
 }
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let root_value = parse_input(input)?;
+    let root_value = crate::timing::parse(|| parse_input(input))?;
 
     Ok(root_value.sum_all_numbers(|_| true))
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let root_value = parse_input(input)?;
+    let root_value = crate::timing::parse(|| parse_input(input))?;
 
     Ok(root_value.sum_all_numbers(|json_value| match json_value {
         JsonValue::Object(object) => !contains_red_value(object),
@@ -201,9 +200,9 @@ where
     }
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
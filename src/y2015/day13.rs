@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug)]
 struct Person {
@@ -22,7 +21,7 @@ impl Person {
 }
 
 fn solve_part(input: &str, include_you: bool) -> Result<i32, SimpleError> {
-    let mut people = parse_input(input)?;
+    let mut people = crate::timing::parse(|| parse_input(input))?;
 
     if include_you {
         let you_name = "__you__";
@@ -40,7 +39,20 @@ fn solve_part(input: &str, include_you: bool) -> Result<i32, SimpleError> {
 
     let arrangements = permutations(&people.values().collect());
 
-    let max_happiness = arrangements
+    let happiness = |person: &Person, other: &Person| {
+        person
+            .happiness_relations
+            .get(&other.name)
+            .copied()
+            .ok_or_else(|| {
+                SimpleError::new(format!(
+                    "no happiness given for {} sitting next to {}",
+                    person.name, other.name
+                ))
+            })
+    };
+
+    let totals = arrangements
         .into_iter()
         .map(|permutation| {
             let mut total_happiness = 0;
@@ -49,21 +61,14 @@ fn solve_part(input: &str, include_you: bool) -> Result<i32, SimpleError> {
                 let next_index = (i + 1) % permutation.len();
 
                 let person = permutation[i];
-                total_happiness += person
-                    .happiness_relations
-                    .get(&permutation[prev_index].name)
-                    .unwrap();
-                total_happiness += person
-                    .happiness_relations
-                    .get(&permutation[next_index].name)
-                    .unwrap();
+                total_happiness += happiness(person, permutation[prev_index])?;
+                total_happiness += happiness(person, permutation[next_index])?;
             }
-            total_happiness
+            Ok(total_happiness)
         })
-        .max()
-        .unwrap_or(0);
+        .collect::<Result<Vec<_>, SimpleError>>()?;
 
-    Ok(max_happiness)
+    Ok(totals.into_iter().max().unwrap_or(0))
 }
 
 fn permutations<'a, T>(items: &Vec<&'a T>) -> Vec<Vec<&'a T>> {
@@ -101,8 +106,9 @@ fn parse_input(input: &str) -> Result<HashMap<String, Person>, SimpleError> {
         }
 
         let person_name = split[0];
-        let mut other_name = split[split.len() - 1];
-        other_name = &other_name[..other_name.len() - 1];
+        let other_name = split[split.len() - 1].strip_suffix('.').ok_or_else(|| {
+            SimpleError::new(format!("expected line to end with a period: {line}"))
+        })?;
 
         let mut happiness_change: i32 = split[3].parse()?;
         if split[2] == "lose" {
@@ -125,9 +131,9 @@ fn parse_input(input: &str) -> Result<HashMap<String, Person>, SimpleError> {
     Ok(people)
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
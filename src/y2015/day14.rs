@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 struct Reindeer {
     speed: u32,
@@ -13,7 +12,7 @@ struct Reindeer {
 }
 
 fn solve_part_1(input: &str, target_second: u32) -> Result<u32, SimpleError> {
-    let reindeer = parse_input(input)?;
+    let reindeer = crate::timing::parse(|| parse_input(input))?;
 
     let max_distance = reindeer
         .into_iter()
@@ -35,11 +34,7 @@ fn solve_part_1(input: &str, target_second: u32) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str, target_second: u32) -> Result<u32, SimpleError> {
-    let reindeer = parse_input(input)?;
-
-    if reindeer.is_empty() {
-        return Err(SimpleError::new(String::from("no reindeer in input")));
-    }
+    let reindeer = crate::timing::parse(|| parse_input(input))?;
 
     let mut distances = vec![0; reindeer.len()];
     let mut scores = vec![0; reindeer.len()];
@@ -52,7 +47,7 @@ fn solve_part_2(input: &str, target_second: u32) -> Result<u32, SimpleError> {
             }
         }
 
-        let max_distance = distances.iter().copied().max().unwrap();
+        let max_distance = distances.iter().copied().max().unwrap_or(0);
         for i in 0..reindeer.len() {
             if distances[i] == max_distance {
                 scores[i] += 1;
@@ -60,7 +55,10 @@ fn solve_part_2(input: &str, target_second: u32) -> Result<u32, SimpleError> {
         }
     }
 
-    Ok(scores.into_iter().max().unwrap())
+    scores
+        .into_iter()
+        .max()
+        .ok_or_else(|| SimpleError::new(String::from("no reindeer in input")))
 }
 
 fn parse_input(input: &str) -> Result<Vec<Reindeer>, SimpleError> {
@@ -77,6 +75,12 @@ fn parse_input(input: &str) -> Result<Vec<Reindeer>, SimpleError> {
             let fly_time: u32 = split[6].parse()?;
             let rest_time: u32 = split[13].parse()?;
 
+            if fly_time == 0 && rest_time == 0 {
+                return Err(SimpleError::new(format!(
+                    "reindeer needs to fly or rest for at least a second: {line}"
+                )));
+            }
+
             Ok(Reindeer {
                 speed,
                 fly_time,
@@ -86,9 +90,9 @@ fn parse_input(input: &str) -> Result<Vec<Reindeer>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 2503)?;
-    let solution2 = solve_part_2(input, 2503)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 2503))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, 2503))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 use std::num::ParseIntError;
 
 struct Ingredient {
@@ -16,13 +15,13 @@ struct Ingredient {
 }
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let ingredients = parse_input(input)?;
+    let ingredients = crate::timing::parse(|| parse_input(input))?;
 
     Ok(search_for_max(&ingredients, Vec::new(), 100, None))
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let ingredients = parse_input(input)?;
+    let ingredients = crate::timing::parse(|| parse_input(input))?;
 
     Ok(search_for_max(&ingredients, Vec::new(), 100, Some(500)))
 }
@@ -109,9 +108,9 @@ fn parse_int(s: &str) -> Result<i32, ParseIntError> {
     s.strip_suffix(',').unwrap_or(s).parse()
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Compound {
@@ -57,7 +56,7 @@ const TARGET_VALUES: [(Compound, u32); 10] = [
 ];
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let aunt_sues = parse_input(input)?;
+    let aunt_sues = crate::timing::parse(|| parse_input(input))?;
 
     let target_values: HashMap<_, _> = TARGET_VALUES.iter().copied().collect();
 
@@ -71,11 +70,13 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let aunt_sues = parse_input(input)?;
+    let aunt_sues = crate::timing::parse(|| parse_input(input))?;
 
     let target_values: HashMap<_, _> = TARGET_VALUES.iter().copied().collect();
 
@@ -97,7 +98,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<Vec<AuntSue>, SimpleError> {
@@ -123,9 +126,9 @@ fn parse_input(input: &str) -> Result<Vec<AuntSue>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,17 +4,16 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 use std::num::ParseIntError;
 
 fn solve_part_1(input: &str, target: u32) -> Result<usize, SimpleError> {
-    let container_sizes = parse_input(input)?;
+    let container_sizes = crate::timing::parse(|| parse_input(input))?;
 
     Ok(search_combinations(&container_sizes, target, 0, 0, None))
 }
 
 fn solve_part_2(input: &str, target: u32) -> Result<usize, SimpleError> {
-    let container_sizes = parse_input(input)?;
+    let container_sizes = crate::timing::parse(|| parse_input(input))?;
 
     let min_containers = find_min_containers(&container_sizes, target, 0, 0);
 
@@ -97,9 +96,9 @@ fn parse_input(input: &str) -> Result<Vec<u32>, ParseIntError> {
     input.lines().map(|line| line.parse::<u32>()).collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 150)?;
-    let solution2 = solve_part_2(input, 150)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 150))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, 150))?;
 
     Ok((solution1, solution2))
 }
@@ -3,10 +3,9 @@
 //! <https://adventofcode.com/2015/day/18>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part(input: &str, steps: usize, locked_corners: bool) -> Result<usize, SimpleError> {
-    let mut grid = parse_input(input);
+    let mut grid = crate::timing::parse(|| parse_input(input));
     if grid.is_empty() {
         return Err(SimpleError::new(String::from("input grid is empty")));
     }
@@ -91,9 +90,9 @@ fn parse_input(input: &str) -> Vec<Vec<bool>> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, 100, false)?;
-    let solution2 = solve_part(input, 100, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, 100, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, 100, true))?;
 
     Ok((solution1, solution2))
 }
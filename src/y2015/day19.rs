@@ -5,12 +5,15 @@
 use crate::SimpleError;
 use std::cmp;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let (replacements_map, molecule) = parse_input(input)?;
+    let (replacements_map, molecule) = crate::timing::parse(|| parse_input(input))?;
 
-    let max_replacement_len = replacements_map.keys().map(String::len).max().unwrap();
+    let max_replacement_len = replacements_map
+        .keys()
+        .map(String::len)
+        .max()
+        .ok_or_else(|| SimpleError::new(String::from("no replacements in input")))?;
 
     let mut unique_molecules: HashSet<String> = HashSet::new();
     for i in 0..molecule.len() {
@@ -31,14 +34,20 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let (replacements_map, target_molecule) = parse_input(input)?;
+    let (replacements_map, target_molecule) = crate::timing::parse(|| parse_input(input))?;
     let replacements_map = reverse_map(&replacements_map);
 
-    let max_replacement_len = replacements_map.keys().map(String::len).max().unwrap();
+    let max_replacement_len = replacements_map
+        .keys()
+        .map(String::len)
+        .max()
+        .ok_or_else(|| SimpleError::new(String::from("no replacements in input")))?;
 
     match search_reverse(&target_molecule, &replacements_map, max_replacement_len) {
         Some(result) => Ok(result),
-        None => Err(SimpleError::new(String::from("no solution found"))),
+        None => Err(SimpleError::unsupported_input(String::from(
+            "no solution found",
+        ))),
     }
 }
 
@@ -131,9 +140,9 @@ fn parse_input(input: &str) -> Result<(HashMap<String, Vec<String>>, String), Si
     Ok((replacements_map, molecule_line))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
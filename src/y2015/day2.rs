@@ -4,10 +4,9 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let result = parse_input(input)?
+    let result = crate::timing::parse(|| parse_input(input))?
         .into_iter()
         .map(|(l, w, h)| {
             2 * l * w + 2 * l * h + 2 * w * h + cmp::min(l * w, cmp::min(l * h, w * h))
@@ -18,7 +17,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let result = parse_input(input)?
+    let result = crate::timing::parse(|| parse_input(input))?
         .into_iter()
         .map(|(l, w, h)| {
             let smallest_perimeter = 2 * cmp::min(l + w, cmp::min(l + h, w + h));
@@ -50,9 +49,9 @@ fn parse_input(input: &str) -> Result<Vec<(u32, u32, u32)>, SimpleError> {
     dimensions
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
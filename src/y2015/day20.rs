@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2015/day/20>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     let line = crate::read_single_line(input)?;
@@ -24,7 +23,9 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
@@ -46,12 +47,14 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 #[derive(Debug)]
 struct Weapon {
@@ -97,7 +96,7 @@ const RINGS: [Ring; 6] = [
 ];
 
 fn solve_both_parts(input: &str) -> Result<(u32, u32), SimpleError> {
-    let boss = parse_input(input)?;
+    let boss = crate::timing::parse(|| parse_input(input))?;
 
     let ring_combinations = generate_ring_combinations(&RINGS, 2);
 
@@ -188,7 +187,7 @@ fn parse_number_end_of_line(line: &str) -> Result<i32, SimpleError> {
     Ok(num.parse()?)
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
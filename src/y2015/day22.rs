@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 struct Boss {
     hit_points: i32,
@@ -97,7 +96,7 @@ impl SearchState {
 }
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let boss = parse_input(input)?;
+    let boss = crate::timing::parse(|| parse_input(input))?;
 
     Ok(search_for_min_cost(
         &boss,
@@ -108,7 +107,7 @@ fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let boss = parse_input(input)?;
+    let boss = crate::timing::parse(|| parse_input(input))?;
 
     Ok(search_for_min_cost(
         &boss,
@@ -239,9 +238,9 @@ fn parse_input(input: &str) -> Result<Boss, SimpleError> {
     Ok(Boss { hit_points, damage })
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
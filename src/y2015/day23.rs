@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2015/day/23>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
@@ -28,9 +27,9 @@ impl Instruction {
         }
 
         let instruction = match operator {
-            "hlf" => Self::Half(operands.chars().next().unwrap()),
-            "tpl" => Self::Triple(operands.chars().next().unwrap()),
-            "inc" => Self::Increment(operands.chars().next().unwrap()),
+            "hlf" => Self::Half(parse_register(operands)?),
+            "tpl" => Self::Triple(parse_register(operands)?),
+            "inc" => Self::Increment(parse_register(operands)?),
             "jmp" => Self::Jump(operands.parse()?),
             "jie" => {
                 let (register, offset) = parse_jump_if_operands(operands)?;
@@ -48,7 +47,7 @@ impl Instruction {
 }
 
 fn solve_part(input: &str, initial_a_value: u64) -> Result<u64, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut a = initial_a_value;
     let mut b = 0;
@@ -112,22 +111,24 @@ fn parse_jump_if_operands(operands: &str) -> Result<(char, i32), SimpleError> {
         ))
     })?;
 
-    if register.is_empty() {
-        return Err(SimpleError::new(format!(
-            "invalid line format for jie, missing register: {operands}"
-        )));
-    }
+    Ok((parse_register(register)?, offset.parse()?))
+}
 
-    Ok((register.chars().next().unwrap(), offset.parse()?))
+fn parse_register(s: &str) -> Result<char, SimpleError> {
+    match s {
+        "a" => Ok('a'),
+        "b" => Ok('b'),
+        _ => Err(SimpleError::new(format!("invalid register: {s}"))),
+    }
 }
 
 fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
     input.lines().map(Instruction::from_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part(input, 0)?;
-    let solution2 = solve_part(input, 1)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, 0))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, 1))?;
 
     Ok((solution1, solution2))
 }
@@ -5,11 +5,10 @@
 use crate::SimpleError;
 use std::cmp;
 use std::collections::HashSet;
-use std::error::Error;
 use std::num::ParseIntError;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let weights = parse_input(input)?;
+    let weights = crate::timing::parse(|| parse_input(input))?;
 
     let total_weight: u64 = weights.iter().copied().sum();
 
@@ -28,7 +27,7 @@ fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let weights = parse_input(input)?;
+    let weights = crate::timing::parse(|| parse_input(input))?;
 
     let total_weight: u64 = weights.iter().copied().sum();
 
@@ -197,9 +196,9 @@ fn parse_input(input: &str) -> Result<Vec<u64>, ParseIntError> {
     input.lines().map(|line| line.parse::<u64>()).collect()
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,10 +3,9 @@
 //! <https://adventofcode.com/2015/day/25>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let (row, col) = parse_input(input)?;
+    let (row, col) = crate::timing::parse(|| parse_input(input))?;
 
     let row = row - 1;
     let col = col - 1;
@@ -47,8 +46,8 @@ fn parse_input(input: &str) -> Result<(usize, usize), SimpleError> {
     Ok((row, col))
 }
 
-pub fn solve(input: &str) -> Result<(u64, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
+pub fn solve(input: &str) -> Result<(u64, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
 
     Ok((solution1, String::new()))
 }
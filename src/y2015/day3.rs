@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 use std::mem;
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -72,9 +71,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     Ok(visited.len())
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2015/day/4>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part(input: &str, target_prefix: &str) -> Result<usize, SimpleError> {
     let line = crate::read_single_line(input)?;
@@ -18,14 +17,14 @@ fn solve_part(input: &str, target_prefix: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "no solution found for part 1",
     )))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, "00000")?;
-    let solution2 = solve_part(input, "000000")?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, "00000"))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, "000000"))?;
 
     Ok((solution1, solution2))
 }
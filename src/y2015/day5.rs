@@ -2,7 +2,7 @@
 //!
 //! <https://adventofcode.com/2015/day/5>
 
-use std::error::Error;
+use crate::SimpleError;
 
 fn solve_part_1(input: &str) -> usize {
     input.lines().filter(|line| is_nice(line)).count()
@@ -49,9 +49,9 @@ fn is_nice_2(line: &str) -> bool {
     false
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input);
-    let solution2 = solve_part_2(input);
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input));
+    let solution2 = crate::timing::part_2(|| solve_part_2(input));
 
     Ok((solution1, solution2))
 }
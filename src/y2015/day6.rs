@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cmp::Ordering;
-use std::error::Error;
 use std::ops::Not;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -59,7 +58,7 @@ impl Action {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let actions = parse_input(input)?;
+    let actions = crate::timing::parse(|| parse_input(input))?;
 
     let mut grid = vec![vec![false; 1000]; 1000];
 
@@ -84,7 +83,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let actions = parse_input(input)?;
+    let actions = crate::timing::parse(|| parse_input(input))?;
 
     let mut grid = vec![vec![0_u32; 1000]; 1000];
 
@@ -149,9 +148,9 @@ fn parse_point(s: &str) -> Result<Point, SimpleError> {
     Ok(Point::new(x.parse()?, y.parse()?))
 }
 
-pub fn solve(input: &str) -> Result<(usize, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 use std::ops::Not;
 
 enum Operation {
@@ -107,14 +106,16 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u16, u16), Box<dyn Error>> {
-    let instructions = parse_input(input)?;
+pub fn solve(input: &str) -> Result<(u16, u16), SimpleError> {
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
-    let solution1 = EvaluationContext::new(&instructions).evaluate("a")?;
+    let solution1 = crate::timing::part_1(|| EvaluationContext::new(&instructions).evaluate("a"))?;
 
-    let solution2 = EvaluationContext::new(&instructions)
-        .with_value("b", solution1)
-        .evaluate("a")?;
+    let solution2 = crate::timing::part_2(|| {
+        EvaluationContext::new(&instructions)
+            .with_value("b", solution1)
+            .evaluate("a")
+    })?;
 
     Ok((solution1, solution2))
 }
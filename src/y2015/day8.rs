@@ -3,30 +3,26 @@
 //! <https://adventofcode.com/2015/day/8>
 
 use crate::SimpleError;
-use std::error::Error;
-use std::iter::Peekable;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     input
         .lines()
         .map(|line| {
-            let original_len = line.len();
             let mut removed_chars = 2;
 
-            let mut chars = line[1..original_len - 1].chars().peekable();
-            while chars.peek().is_some() {
-                let c = chars.next().unwrap();
+            let mut chars = line
+                .strip_prefix('"')
+                .and_then(|line| line.strip_suffix('"'))
+                .ok_or_else(|| SimpleError::new(format!("line is not in quotes: {line}")))?
+                .chars();
+            while let Some(c) = chars.next() {
                 if c == '\\' {
-                    error_if_exhausted(&mut chars, line)?;
-
                     removed_chars += 1;
 
-                    let next_c = chars.next().unwrap();
+                    let next_c = next_char(&mut chars, line)?;
                     if next_c == 'x' {
-                        error_if_exhausted(&mut chars, line)?;
-                        chars.next();
-                        error_if_exhausted(&mut chars, line)?;
-                        chars.next();
+                        next_char(&mut chars, line)?;
+                        next_char(&mut chars, line)?;
 
                         removed_chars += 2;
                     }
@@ -55,19 +51,14 @@ fn solve_part_2(input: &str) -> usize {
         .sum()
 }
 
-fn error_if_exhausted<I>(iter: &mut Peekable<I>, line: &str) -> Result<(), SimpleError>
-where
-    I: Iterator<Item = char>,
-{
-    match iter.peek() {
-        Some(_) => Ok(()),
-        None => Err(SimpleError::new(format!("invalid line: {line}"))),
-    }
+fn next_char(iter: &mut impl Iterator<Item = char>, line: &str) -> Result<char, SimpleError> {
+    iter.next()
+        .ok_or_else(|| SimpleError::new(format!("invalid line: {line}")))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input);
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input));
 
     Ok((solution1, solution2))
 }
@@ -83,6 +74,13 @@ mod tests {
         assert_eq!(Ok(12), solve_part_1(SAMPLE_INPUT));
     }
 
+    #[test]
+    fn test_invalid_lines() {
+        assert!(solve_part_1("\"").is_err());
+        assert!(solve_part_1("abc").is_err());
+        assert!(solve_part_1("\"\\x4\"").is_err());
+    }
+
     #[test]
     fn test_sample_input_part_2() {
         assert_eq!(19, solve_part_2(SAMPLE_INPUT));
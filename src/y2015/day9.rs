@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 
 struct Location {
     distances: HashMap<String, u32>,
@@ -19,11 +18,14 @@ impl Location {
 }
 
 fn solve_part(input: &str, reverse: bool) -> Result<u32, SimpleError> {
-    let locations = parse_input(input)?;
+    let locations = crate::timing::parse(|| parse_input(input))?;
+    if locations.is_empty() {
+        return Err(SimpleError::new(String::from("input should not be empty")));
+    }
 
     let minimum_distance = locations
         .keys()
-        .map(|start| {
+        .filter_map(|start| {
             let mut visited: HashSet<String> = HashSet::new();
             visited.insert(start.clone());
             find_minimum_distance(&locations, visited, start, reverse)
@@ -35,7 +37,9 @@ fn solve_part(input: &str, reverse: bool) -> Result<u32, SimpleError> {
                 distance as i32
             }
         })
-        .ok_or_else(|| SimpleError::new(String::from("input should not be empty")))?;
+        .ok_or_else(|| {
+            SimpleError::unsupported_input(String::from("no route visits every location"))
+        })?;
 
     Ok(minimum_distance)
 }
@@ -45,21 +49,21 @@ fn find_minimum_distance(
     visited: HashSet<String>,
     current_location: &str,
     reverse: bool,
-) -> u32 {
+) -> Option<u32> {
     if visited.len() == locations.len() {
-        return 0;
+        return Some(0);
     }
 
-    let location = locations.get(current_location).unwrap();
+    let location = locations.get(current_location)?;
 
     location
         .distances
         .iter()
         .filter(|(name, _)| !visited.contains(*name))
-        .map(|(name, distance)| {
+        .filter_map(|(name, distance)| {
             let mut new_visited = visited.clone();
             new_visited.insert(name.clone());
-            *distance + find_minimum_distance(locations, new_visited, name, reverse)
+            Some(*distance + find_minimum_distance(locations, new_visited, name, reverse)?)
         })
         .min_by_key(|&distance| {
             if reverse {
@@ -68,7 +72,6 @@ fn find_minimum_distance(
                 distance as i32
             }
         })
-        .unwrap()
 }
 
 fn parse_input(input: &str) -> Result<HashMap<String, Location>, SimpleError> {
@@ -100,9 +103,9 @@ fn update_location(locations: &mut HashMap<String, Location>, a: &str, b: &str,
     }
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
@@ -122,4 +125,11 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(982), solve_part(SAMPLE_INPUT, true));
     }
+
+    #[test]
+    fn test_no_route() {
+        let input = "A to B = 1\nA to C = 2\nA to D = 3";
+        assert!(solve_part(input, false).is_err());
+        assert!(solve_part("", false).is_err());
+    }
 }
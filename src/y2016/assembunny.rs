@@ -1,250 +1,789 @@
 use crate::SimpleError;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
 
-#[derive(Debug, Clone, Copy)]
+const REGISTER_NAMES: [char; 4] = ['a', 'b', 'c', 'd'];
+
+// One of the registers a to d. These can only be created from a valid name, so indexing
+// Registers with one never fails
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Register(usize);
+
+impl Register {
+    pub const A: Self = Self(0);
+
+    pub fn from_name(name: char) -> Option<Self> {
+        REGISTER_NAMES.iter().position(|&n| n == name).map(Self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Registers([i64; 4]);
+
+impl From<[i64; 4]> for Registers {
+    fn from(values: [i64; 4]) -> Self {
+        Self(values)
+    }
+}
+
+impl Index<Register> for Registers {
+    type Output = i64;
+
+    fn index(&self, r: Register) -> &Self::Output {
+        &self.0[r.0]
+    }
+}
+
+impl IndexMut<Register> for Registers {
+    fn index_mut(&mut self, r: Register) -> &mut Self::Output {
+        &mut self.0[r.0]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum InstructionArg {
-    Register(char),
+    Register(Register),
     Constant(i64),
 }
 
-#[derive(Debug, Clone, Copy)]
+impl InstructionArg {
+    fn value(self, registers: &Registers) -> i64 {
+        match self {
+            Self::Constant(n) => n,
+            Self::Register(r) => registers[r],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum AssembunnyInstruction {
     Copy(InstructionArg, InstructionArg),
-    Increment(char),
-    Decrement(char),
+    Increment(Register),
+    Decrement(Register),
     JumpNotZero(InstructionArg, InstructionArg),
-    Toggle(char),
-    Out(char),
-    Add(char, char),
-    MultiplyAdd(char, char, char),
-    Nop,
+    Toggle(Register),
+    Out(Register),
+}
+
+// What an instruction needs the program to do besides updating the registers and pc
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Action {
+    None,
+    Output(i64),
+    // Toggle the instruction at this index
+    Toggle(usize),
 }
 
 impl AssembunnyInstruction {
     fn from_line(line: &str) -> Result<Self, SimpleError> {
+        let argument = |s| parse_argument(s).map_err(|err| err.at_token(line, s));
+        let register = |s| parse_register(s).map_err(|err| err.at_token(line, s));
+
         let split: Vec<_> = line.split(' ').collect();
         match split.as_slice() {
-            ["cpy", x, y] => Ok(Self::Copy(parse_argument(x)?, parse_argument(y)?)),
-            ["inc", x] => Ok(Self::Increment(as_register_id(x)?)),
-            ["dec", x] => Ok(Self::Decrement(as_register_id(x)?)),
-            ["jnz", x, y] => Ok(Self::JumpNotZero(parse_argument(x)?, parse_argument(y)?)),
-            ["tgl", x] => Ok(Self::Toggle(as_register_id(x)?)),
-            ["out", x] => Ok(Self::Out(as_register_id(x)?)),
-            _ => Err(SimpleError::new(format!("invalid line: {line}"))),
+            ["cpy", x, y] => Ok(Self::Copy(argument(x)?, argument(y)?)),
+            ["inc", x] => Ok(Self::Increment(register(x)?)),
+            ["dec", x] => Ok(Self::Decrement(register(x)?)),
+            ["jnz", x, y] => Ok(Self::JumpNotZero(argument(x)?, argument(y)?)),
+            ["tgl", x] => Ok(Self::Toggle(register(x)?)),
+            ["out", x] => Ok(Self::Out(register(x)?)),
+            _ => Err(SimpleError::new(format!("invalid line: {line}")).at_token(line, line)),
         }
     }
 
-    fn execute(
-        &self,
-        registers: &mut HashMap<char, i64>,
-        pc: &mut usize,
-        program: &mut Vec<Self>,
-    ) -> Option<i64> {
+    fn execute(&self, registers: &mut Registers, pc: &mut usize, len: usize) -> Action {
+        let mut action = Action::None;
         match *self {
             Self::Copy(x, y) => {
-                match y {
-                    InstructionArg::Register(y) => {
-                        *registers.get_mut(&y).unwrap() = read_arg_value(x, registers);
-                    }
-                    InstructionArg::Constant(_) => {}
+                if let InstructionArg::Register(y) = y {
+                    registers[y] = x.value(registers);
                 }
-                *pc += 1;
-            }
-            Self::Increment(x) => {
-                *registers.get_mut(&x).unwrap() += 1;
-                *pc += 1;
-            }
-            Self::Decrement(x) => {
-                *registers.get_mut(&x).unwrap() -= 1;
-                *pc += 1;
             }
+            // Registers wrap around rather than overflowing, and jumps that overflow leave the
+            // program
+            Self::Increment(x) => registers[x] = registers[x].wrapping_add(1),
+            Self::Decrement(x) => registers[x] = registers[x].wrapping_sub(1),
             Self::JumpNotZero(x, y) => {
-                let x = read_arg_value(x, registers);
-                let y = read_arg_value(y, registers);
-                if x != 0 {
-                    *pc = ((*pc as i64) + y) as usize;
-                } else {
-                    *pc += 1;
+                if x.value(registers) != 0 {
+                    *pc = (*pc as i64).wrapping_add(y.value(registers)) as usize;
+                    return Action::None;
                 }
             }
             Self::Toggle(x) => {
-                let value = *registers.get_mut(&x).unwrap() + (*pc as i64);
-                if value >= 0 && value < program.len() as i64 {
-                    let new_instruction = match program[value as usize] {
-                        Self::Copy(x, y) => Self::JumpNotZero(x, y),
-                        Self::JumpNotZero(x, y) => Self::Copy(x, y),
-                        Self::Increment(x) => Self::Decrement(x),
-                        Self::Decrement(x) | Self::Toggle(x) => Self::Increment(x),
-                        Self::Out(..) => panic!("cannot toggle out"),
-                        Self::Add(..) | Self::MultiplyAdd(..) | Self::Nop => panic!(
-                            "cannot toggle {:?}, optimizations failed",
-                            program[value as usize]
-                        ),
-                    };
-                    program[value as usize] = new_instruction;
+                if let Some(target) = registers[x].checked_add(*pc as i64) {
+                    if target >= 0 && target < len as i64 {
+                        action = Action::Toggle(target as usize);
+                    }
                 }
-
-                *pc += 1;
-            }
-            Self::Out(x) => {
-                *pc += 1;
-                return Some(*registers.get(&x).unwrap());
-            }
-            Self::Add(x, y) => {
-                *registers.get_mut(&x).unwrap() += *registers.get(&y).unwrap();
-                *pc += 1;
-            }
-            Self::MultiplyAdd(x, y, z) => {
-                *registers.get_mut(&x).unwrap() +=
-                    *registers.get(&y).unwrap() * *registers.get(&z).unwrap();
-                *pc += 1;
-            }
-            Self::Nop => {
-                *pc += 1;
             }
+            Self::Out(x) => action = Action::Output(registers[x]),
         }
 
-        None
+        *pc += 1;
+        action
+    }
+
+    fn toggled(self) -> Option<Self> {
+        match self {
+            Self::Copy(x, y) => Some(Self::JumpNotZero(x, y)),
+            Self::JumpNotZero(x, y) => Some(Self::Copy(x, y)),
+            Self::Increment(x) => Some(Self::Decrement(x)),
+            Self::Decrement(x) | Self::Toggle(x) => Some(Self::Increment(x)),
+            Self::Out(..) => None,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AssembunnyProgram {
     instructions: Vec<AssembunnyInstruction>,
+    // Counted loops by start index, outermost first, or None if the program isn't optimized
+    loops: Option<Vec<Vec<CountedLoop>>>,
 }
 
 impl AssembunnyProgram {
     pub fn from_lines(input: &str) -> Result<AssembunnyProgram, SimpleError> {
-        let instructions: Result<Vec<_>, _> = input
-            .lines()
-            .map(AssembunnyInstruction::from_line)
-            .collect();
+        let instructions =
+            crate::timing::parse(|| crate::parse_lines(input, AssembunnyInstruction::from_line))?;
         Ok(AssembunnyProgram {
-            instructions: instructions?,
+            instructions,
+            loops: None,
         })
     }
 
-    pub fn optimize_multiplies(&mut self) {
-        let mut add_instructions = Vec::new();
-        for (i, window) in self.instructions.windows(4).enumerate() {
-            if let AssembunnyInstruction::Copy(
-                InstructionArg::Register(cp_x),
-                InstructionArg::Register(cp_y),
-            ) = window[0]
+    // Finds loops that can be run in one go rather than an iteration at a time. The program is
+    // re-analyzed whenever tgl runs, so loops that tgl rewrites go back to running normally
+    pub fn optimize(&mut self) {
+        self.loops = Some(find_counted_loops(&self.instructions));
+    }
+
+    // Runs the program until it halts. Any instructions that tgl changes stay changed
+    pub fn execute(&mut self, registers: &mut Registers) -> Result<(), SimpleError> {
+        let mut pc = 0;
+        while pc < self.instructions.len() {
+            let tgl_pc = pc;
+            if let Action::Toggle(target) = self.step(registers, &mut pc) {
+                self.toggle(tgl_pc, target)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Runs a copy of the program from the start, yielding the values it sends with out. The copy
+    // only clones the program if tgl changes it, so this is cheap to call repeatedly
+    pub fn outputs(&self, initial_registers: Registers) -> Outputs<'_> {
+        Outputs {
+            program: Cow::Borrowed(self),
+            registers: initial_registers,
+            pc: 0,
+        }
+    }
+
+    // Runs the program until it's in the same state right after two outs, which proves that the
+    // outputs in between repeat forever. Returns None if the program halts first or doesn't repeat
+    // within max_outputs outputs
+    pub fn find_output_cycle(
+        &self,
+        initial_registers: Registers,
+        max_outputs: usize,
+    ) -> Result<Option<OutputCycle>, SimpleError> {
+        let mut outputs = self.outputs(initial_registers);
+        let mut values = Vec::new();
+        // The number of outputs so far when each state was seen
+        let mut seen_states = HashMap::new();
+
+        while values.len() < max_outputs {
+            let Some(value) = outputs.next().transpose()? else {
+                return Ok(None);
+            };
+            values.push(value);
+
+            if let Some(start) = seen_states.insert(outputs.state(), values.len()) {
+                let cycle = values.split_off(start);
+                return Ok(Some(OutputCycle {
+                    prefix: values,
+                    cycle,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn step(&self, registers: &mut Registers, pc: &mut usize) -> Action {
+        if let Some(loops) = &self.loops {
+            if let Some(end) = loops[*pc]
+                .iter()
+                .find_map(|counted_loop| counted_loop.run(registers))
             {
-                if let AssembunnyInstruction::JumpNotZero(
-                    InstructionArg::Register(jnz_x),
-                    InstructionArg::Constant(-2),
-                ) = window[3]
-                {
-                    match (window[1], window[2]) {
-                        (
-                            AssembunnyInstruction::Increment(inc_x),
-                            AssembunnyInstruction::Decrement(dec_x),
-                        )
-                        | (
-                            AssembunnyInstruction::Decrement(dec_x),
-                            AssembunnyInstruction::Increment(inc_x),
-                        ) => {
-                            if cp_y == jnz_x && jnz_x == dec_x && inc_x != dec_x {
-                                add_instructions.push((i, inc_x, cp_x));
-                            }
-                        }
-                        _ => {}
-                    }
-                }
+                *pc = end + 1;
+                return Action::None;
             }
         }
 
-        for &(i, x, y) in &add_instructions {
-            self.instructions[i] = AssembunnyInstruction::Add(x, y);
-            self.instructions[i + 1] = AssembunnyInstruction::Nop;
-            self.instructions[i + 2] = AssembunnyInstruction::Nop;
-            self.instructions[i + 3] = AssembunnyInstruction::Nop;
-        }
-
-        let mut multiply_instructions = Vec::new();
-        for (i, window) in self.instructions.windows(6).enumerate() {
-            if let AssembunnyInstruction::Add(add_x, add_y) = window[0] {
-                if let AssembunnyInstruction::JumpNotZero(
-                    InstructionArg::Register(jnz_x),
-                    InstructionArg::Constant(-5),
-                ) = window[5]
-                {
-                    if let AssembunnyInstruction::Decrement(dec_x) = window[4] {
-                        if dec_x == jnz_x && dec_x != add_x && dec_x != add_y {
-                            multiply_instructions.push((i, add_x, add_y, dec_x));
-                        }
+        self.instructions[*pc].execute(registers, pc, self.instructions.len())
+    }
+
+    fn toggle(&mut self, tgl_pc: usize, target: usize) -> Result<(), SimpleError> {
+        self.instructions[target] = self.instructions[target].toggled().ok_or_else(|| {
+            SimpleError::runtime(format!("tgl at {tgl_pc} cannot toggle out at {target}"))
+        })?;
+
+        if self.loops.is_some() {
+            self.optimize();
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Outputs<'a> {
+    program: Cow<'a, AssembunnyProgram>,
+    registers: Registers,
+    pc: usize,
+}
+
+// Everything that decides what a program does next
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct MachineState {
+    pc: usize,
+    registers: Registers,
+    // The instructions if tgl has changed them
+    instructions: Option<Vec<AssembunnyInstruction>>,
+}
+
+impl Outputs<'_> {
+    fn state(&self) -> MachineState {
+        MachineState {
+            pc: self.pc,
+            registers: self.registers,
+            instructions: match &self.program {
+                Cow::Borrowed(_) => None,
+                Cow::Owned(program) => Some(program.instructions.clone()),
+            },
+        }
+    }
+}
+
+// A program's output that repeats forever: the outputs before the repeating part, then one
+// period of it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OutputCycle {
+    pub prefix: Vec<i64>,
+    pub cycle: Vec<i64>,
+}
+
+impl OutputCycle {
+    // The ith value of the infinite output
+    pub fn get(&self, i: usize) -> i64 {
+        match i.checked_sub(self.prefix.len()) {
+            None => self.prefix[i],
+            Some(j) => self.cycle[j % self.cycle.len()],
+        }
+    }
+}
+
+impl Iterator for Outputs<'_> {
+    type Item = Result<i64, SimpleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pc < self.program.instructions.len() {
+            let tgl_pc = self.pc;
+            match self.program.step(&mut self.registers, &mut self.pc) {
+                Action::None => {}
+                Action::Output(value) => return Some(Ok(value)),
+                Action::Toggle(target) => {
+                    if let Err(err) = self.program.to_mut().toggle(tgl_pc, target) {
+                        self.pc = self.program.instructions.len();
+                        return Some(Err(err));
                     }
                 }
             }
         }
 
-        for &(i, x, y, z) in &multiply_instructions {
-            self.instructions[i] = AssembunnyInstruction::MultiplyAdd(x, y, z);
-            self.instructions[i + 4] = AssembunnyInstruction::Nop;
-            self.instructions[i + 5] = AssembunnyInstruction::Nop;
+        None
+    }
+}
+
+// A constant plus a multiple of each register's value at the start of a loop iteration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Linear {
+    constant: i64,
+    coefficients: [i64; 4],
+}
+
+impl Linear {
+    fn constant(constant: i64) -> Self {
+        Self {
+            constant,
+            coefficients: [0; 4],
         }
     }
 
-    pub fn execute(&mut self, registers: &mut HashMap<char, i64>) {
-        let mut pc = 0;
-        while pc < self.instructions.len() {
-            let instruction = self.instructions[pc];
-            instruction.execute(registers, &mut pc, &mut self.instructions);
+    fn register(r: usize) -> Self {
+        let mut coefficients = [0; 4];
+        coefficients[r] = 1;
+        Self {
+            constant: 0,
+            coefficients,
         }
     }
 
-    pub fn outputs_pattern(
-        &mut self,
-        registers: &mut HashMap<char, i64>,
-        mut pattern: impl Iterator<Item = i64>,
-    ) -> bool {
-        let mut pc = 0;
-        while pc < self.instructions.len() {
-            let instruction = self.instructions[pc];
-            if let Some(value) = instruction.execute(registers, &mut pc, &mut self.instructions) {
-                match pattern.next() {
-                    Some(pattern_value) => {
-                        if value != pattern_value {
-                            return false;
-                        }
-                    }
-                    None => {
-                        return true;
-                    }
-                }
+    // None if any part overflows
+    fn add(self, other: Self) -> Option<Self> {
+        let mut coefficients = self.coefficients;
+        for (coefficient, other) in coefficients.iter_mut().zip(other.coefficients) {
+            *coefficient = coefficient.checked_add(other)?;
+        }
+        Some(Self {
+            constant: self.constant.checked_add(other.constant)?,
+            coefficients,
+        })
+    }
+
+    fn scale(self, n: i64) -> Option<Self> {
+        let mut coefficients = self.coefficients;
+        for coefficient in &mut coefficients {
+            *coefficient = coefficient.checked_mul(n)?;
+        }
+        Some(Self {
+            constant: self.constant.checked_mul(n)?,
+            coefficients,
+        })
+    }
+
+    // Whether this only depends on registers in the given set
+    fn depends_only_on(&self, registers: &[bool; 4]) -> bool {
+        (0..4).all(|r| self.coefficients[r] == 0 || registers[r])
+    }
+
+    fn evaluate(&self, registers: &[i64; 4]) -> Option<i64> {
+        (0..4).try_fold(self.constant, |sum, r| {
+            sum.checked_add(self.coefficients[r].checked_mul(registers[r])?)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoopEffect {
+    Unchanged,
+    // Adds the same amount every iteration
+    Add(Linear),
+    // Sets the register to the same value every iteration
+    Set(Linear),
+}
+
+// A loop ending in jnz that counts a register down (or up) to 0, where every iteration changes the
+// other registers by the same amount. Inner loops are allowed as long as they only increment and
+// decrement registers, which covers zeroing loops like "dec c, jnz c -1", addition and
+// multiplication
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CountedLoop {
+    // Index of the jnz
+    end: usize,
+    counter: usize,
+    // Change in the counter each iteration
+    step: i64,
+    effects: [LoopEffect; 4],
+    // Iteration counts of inner loops, which need to be at least 1
+    inner_counts: Vec<Linear>,
+}
+
+impl CountedLoop {
+    // Runs every iteration of the loop, returning the index of the jnz. Returns None without
+    // changing anything if the loop wouldn't end or the registers would overflow, in which case the
+    // loop should be run normally
+    fn run(&self, registers: &mut Registers) -> Option<usize> {
+        let values = registers.0;
+
+        let counter = values[self.counter];
+        let iterations = counter.checked_neg()?.checked_div(self.step)?;
+        if counter.checked_rem(self.step)? != 0 || iterations < 1 {
+            return None;
+        }
+
+        for count in &self.inner_counts {
+            if count.evaluate(&values)? < 1 {
+                return None;
             }
         }
 
-        pattern.next().is_none()
+        let mut new_values = values;
+        for (r, effect) in self.effects.iter().enumerate() {
+            new_values[r] = match effect {
+                LoopEffect::Unchanged => values[r],
+                LoopEffect::Add(delta) => {
+                    values[r].checked_add(delta.evaluate(&values)?.checked_mul(iterations)?)?
+                }
+                LoopEffect::Set(value) => value.evaluate(&values)?,
+            };
+        }
+        new_values[self.counter] = 0;
+
+        registers.0 = new_values;
+
+        Some(self.end)
+    }
+}
+
+fn find_counted_loops(instructions: &[AssembunnyInstruction]) -> Vec<Vec<CountedLoop>> {
+    let mut loops = vec![Vec::new(); instructions.len()];
+    for end in 0..instructions.len() {
+        let Some((start, counter)) = loop_jump(instructions, end) else {
+            continue;
+        };
+        if let Some(counted_loop) = analyze_loop(instructions, start, end, counter) {
+            loops[start].push(counted_loop);
+        }
+    }
+
+    for start_loops in &mut loops {
+        start_loops.sort_by_key(|counted_loop| std::cmp::Reverse(counted_loop.end));
+    }
+
+    loops
+}
+
+// If the instruction at end is a jnz back to an earlier instruction on a register, returns that
+// instruction's index and the register
+fn loop_jump(instructions: &[AssembunnyInstruction], end: usize) -> Option<(usize, usize)> {
+    match instructions[end] {
+        AssembunnyInstruction::JumpNotZero(
+            InstructionArg::Register(r),
+            InstructionArg::Constant(offset),
+        ) if offset < 0 => {
+            let start = end.checked_sub(offset.unsigned_abs() as usize)?;
+            Some((start, r.0))
+        }
+        _ => None,
+    }
+}
+
+fn analyze_loop(
+    instructions: &[AssembunnyInstruction],
+    start: usize,
+    end: usize,
+    counter: usize,
+) -> Option<CountedLoop> {
+    let (values, inner_counts) = run_symbolically(instructions, start, end)?;
+
+    let step = values[counter].constant;
+    if step == 0 || values[counter].coefficients != Linear::register(counter).coefficients {
+        return None;
+    }
+
+    let invariant: [bool; 4] = std::array::from_fn(|r| values[r] == Linear::register(r));
+    let mut effects = [LoopEffect::Unchanged; 4];
+    for r in (0..4).filter(|&r| r != counter && !invariant[r]) {
+        let value = values[r];
+        let effect = match value.coefficients[r] {
+            1 => LoopEffect::Add(value.add(Linear::register(r).scale(-1)?)?),
+            0 => LoopEffect::Set(value),
+            _ => return None,
+        };
+
+        match effect {
+            LoopEffect::Add(changed) | LoopEffect::Set(changed)
+                if changed.depends_only_on(&invariant) => {}
+            _ => return None,
+        }
+        effects[r] = effect;
+    }
+
+    if !inner_counts
+        .iter()
+        .all(|count| count.depends_only_on(&invariant))
+    {
+        return None;
     }
+
+    Some(CountedLoop {
+        end,
+        counter,
+        step,
+        effects,
+        inner_counts,
+    })
 }
 
-fn read_arg_value(arg: InstructionArg, registers: &HashMap<char, i64>) -> i64 {
-    match arg {
-        InstructionArg::Constant(n) => n,
-        InstructionArg::Register(r) => *registers.get(&r).unwrap(),
+// Runs the instructions from start up to end once, returning the registers' values afterwards in
+// terms of their values beforehand along with the iteration counts of any inner loops. Returns None
+// if the instructions contain anything other than cpy, inc, dec and inner loops that only use inc
+// and dec, or if the values overflow
+fn run_symbolically(
+    instructions: &[AssembunnyInstruction],
+    start: usize,
+    end: usize,
+) -> Option<([Linear; 4], Vec<Linear>)> {
+    let mut values: [Linear; 4] = std::array::from_fn(Linear::register);
+    let mut inner_counts = Vec::new();
+
+    let mut pc = start;
+    while pc < end {
+        // Take the outermost loop that starts here if there's more than one
+        let inner_loop =
+            (pc + 1..end)
+                .rev()
+                .find_map(|inner_end| match loop_jump(instructions, inner_end) {
+                    Some((inner_start, counter)) if inner_start == pc => Some((inner_end, counter)),
+                    _ => None,
+                });
+
+        if let Some((inner_end, counter)) = inner_loop {
+            let (inner_values, nested_counts) = run_symbolically(instructions, pc, inner_end)?;
+            if !nested_counts.is_empty() {
+                return None;
+            }
+            let mut deltas = [0; 4];
+            for r in 0..4 {
+                if inner_values[r].coefficients != Linear::register(r).coefficients {
+                    return None;
+                }
+                deltas[r] = inner_values[r].constant;
+            }
+            if !matches!(deltas[counter], 1 | -1) {
+                return None;
+            }
+
+            let count = values[counter].scale(-deltas[counter])?;
+            for r in (0..4).filter(|&r| r != counter) {
+                values[r] = values[r].add(count.scale(deltas[r])?)?;
+            }
+            values[counter] = Linear::constant(0);
+            inner_counts.push(count);
+
+            pc = inner_end + 1;
+            continue;
+        }
+
+        match instructions[pc] {
+            AssembunnyInstruction::Increment(r) => {
+                values[r.0].constant = values[r.0].constant.checked_add(1)?;
+            }
+            AssembunnyInstruction::Decrement(r) => {
+                values[r.0].constant = values[r.0].constant.checked_sub(1)?;
+            }
+            AssembunnyInstruction::Copy(x, InstructionArg::Register(y)) => {
+                values[y.0] = match x {
+                    InstructionArg::Constant(n) => Linear::constant(n),
+                    InstructionArg::Register(x) => values[x.0],
+                };
+            }
+            AssembunnyInstruction::Copy(_, InstructionArg::Constant(_)) => {}
+            AssembunnyInstruction::JumpNotZero(InstructionArg::Constant(0), _) => {}
+            _ => return None,
+        }
+        pc += 1;
     }
+
+    Some((values, inner_counts))
 }
 
 fn parse_argument(s: &str) -> Result<InstructionArg, SimpleError> {
     match s.parse::<i64>() {
         Ok(n) => Ok(InstructionArg::Constant(n)),
-        Err(_) => Ok(InstructionArg::Register(as_register_id(s)?)),
+        Err(_) => Ok(InstructionArg::Register(parse_register(s)?)),
     }
 }
 
-fn as_register_id(s: &str) -> Result<char, SimpleError> {
-    match s.chars().next() {
-        Some(c) => {
-            if !('a'..='d').contains(&c) {
-                return Err(SimpleError::new(format!("invalid register id: {s}")));
-            }
-            Ok(c)
+fn parse_register(s: &str) -> Result<Register, SimpleError> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Register::from_name(c)
+            .ok_or_else(|| SimpleError::new(format!("invalid register id: {s}"))),
+        (Some(_), Some(_)) => Err(SimpleError::new(format!("invalid register id: {s}"))),
+        (None, _) => Err(SimpleError::new(String::from("missing register id"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Computes a! + 87 * 78, using tgl to rewrite the end of the program
+    const FACTORIAL: &str = "\
+cpy a b
+dec b
+cpy a d
+cpy 0 a
+cpy b c
+inc a
+dec c
+jnz c -2
+dec d
+jnz d -5
+dec b
+cpy b c
+cpy c d
+dec d
+inc c
+jnz d -2
+tgl c
+cpy -16 c
+jnz 1 c
+cpy 87 c
+jnz 78 d
+inc a
+inc d
+jnz d -2
+inc c
+jnz c -5";
+
+    fn run(input: &str, a: i64, optimize: bool) -> i64 {
+        let mut program = AssembunnyProgram::from_lines(input).unwrap();
+        if optimize {
+            program.optimize();
+        }
+
+        let mut registers = Registers::from([a, 0, 0, 0]);
+        program.execute(&mut registers).unwrap();
+        registers[Register::A]
+    }
+
+    #[test]
+    fn test_find_counted_loops() {
+        let program = AssembunnyProgram::from_lines(FACTORIAL).unwrap();
+        let loops = find_counted_loops(&program.instructions);
+
+        // The multiply loop, with the add loop inside it
+        assert_eq!(vec![9], loops[4].iter().map(|l| l.end).collect::<Vec<_>>());
+        assert_eq!(LoopEffect::Add(Linear::register(1)), loops[4][0].effects[0]);
+        assert_eq!(LoopEffect::Set(Linear::constant(0)), loops[4][0].effects[2]);
+        assert_eq!(vec![Linear::register(1)], loops[4][0].inner_counts);
+        assert_eq!(vec![7], loops[5].iter().map(|l| l.end).collect::<Vec<_>>());
+
+        // "inc c" makes this count d down while adding to c
+        assert_eq!(
+            vec![15],
+            loops[13].iter().map(|l| l.end).collect::<Vec<_>>()
+        );
+
+        // Neither of these have been rewritten by tgl yet
+        assert!(loops[20].is_empty());
+        assert_eq!(
+            vec![23],
+            loops[21].iter().map(|l| l.end).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_optimize() {
+        assert_eq!(5040 + 87 * 78, run(FACTORIAL, 7, false));
+        assert_eq!(5040 + 87 * 78, run(FACTORIAL, 7, true));
+        assert_eq!(479001600 + 87 * 78, run(FACTORIAL, 12, true));
+    }
+
+    #[test]
+    fn test_toggle_inside_loop() {
+        // The first pass runs the add loop, then tgl turns its jnz into a cpy so that the second
+        // pass goes through the loop body once, then turns it back
+        let input = "\
+cpy 2 d
+cpy 3 c
+inc a
+dec c
+jnz c -2
+cpy -2 b
+tgl b
+dec d
+jnz d -7";
+        assert_eq!(4, run(input, 0, false));
+        assert_eq!(4, run(input, 0, true));
+    }
+
+    #[test]
+    fn test_outputs() {
+        let program = AssembunnyProgram::from_lines("out a\ndec a\njnz a -2").unwrap();
+        let outputs: Result<Vec<_>, _> = program.outputs(Registers::from([3, 0, 0, 0])).collect();
+        assert_eq!(Ok(vec![3, 2, 1]), outputs);
+
+        // tgl changes a copy of the program, so every run starts from the original
+        let program = AssembunnyProgram::from_lines("cpy 1 b\ntgl b\ninc a\nout a").unwrap();
+        for _ in 0..2 {
+            let outputs: Result<Vec<_>, _> =
+                program.outputs(Registers::from([5, 0, 0, 0])).collect();
+            assert_eq!(Ok(vec![4]), outputs);
         }
-        None => Err(SimpleError::new(String::from(
-            "cannot get first char of empty string",
-        ))),
+
+        let program = AssembunnyProgram::from_lines("tgl a\nout a").unwrap();
+        let mut outputs = program.outputs(Registers::from([1, 0, 0, 0]));
+        assert!(outputs.next().unwrap().is_err());
+        assert!(outputs.next().is_none());
+    }
+
+    #[test]
+    fn test_find_output_cycle() {
+        let program =
+            AssembunnyProgram::from_lines("out a\ninc a\nout a\ndec a\njnz 1 -4").unwrap();
+        let cycle = program
+            .find_output_cycle(Registers::default(), 100)
+            .unwrap()
+            .unwrap();
+        assert_eq!(vec![0], cycle.prefix);
+        assert_eq!(vec![1, 0], cycle.cycle);
+        assert_eq!(
+            vec![0, 1, 0, 1, 0],
+            (0..5).map(|i| cycle.get(i)).collect::<Vec<_>>()
+        );
+
+        // Counting down forever never repeats a state, and halting ends the output
+        let program = AssembunnyProgram::from_lines("out a\ndec a\njnz 1 -2").unwrap();
+        assert_eq!(
+            Ok(None),
+            program.find_output_cycle(Registers::default(), 100)
+        );
+        let program = AssembunnyProgram::from_lines("out a\ndec a\njnz a -2").unwrap();
+        assert_eq!(
+            Ok(None),
+            program.find_output_cycle(Registers::from([3, 0, 0, 0]), 100)
+        );
+    }
+
+    #[test]
+    fn test_loop_without_end() {
+        let program =
+            AssembunnyProgram::from_lines("dec b\njnz b -1\ndec c\ndec c\njnz c -2").unwrap();
+        let loops = find_counted_loops(&program.instructions);
+
+        let mut registers = Registers::from([0, 0, 5, 0]);
+        assert_eq!(None, loops[0][0].run(&mut registers));
+        assert_eq!(None, loops[2][0].run(&mut registers));
+        assert_eq!(Registers::from([0, 0, 5, 0]), registers);
+
+        registers = Registers::from([0, 3, 6, 0]);
+        assert_eq!(Some(1), loops[0][0].run(&mut registers));
+        assert_eq!(Some(4), loops[2][0].run(&mut registers));
+        assert_eq!(Registers::default(), registers);
+
+        // Counting i64::MIN down or up to 0 would overflow
+        let program = AssembunnyProgram::from_lines("dec c\njnz c -1\ninc c\njnz c -1").unwrap();
+        let loops = find_counted_loops(&program.instructions);
+
+        let mut registers = Registers::from([0, 0, i64::MIN, 0]);
+        assert_eq!(None, loops[0][0].run(&mut registers));
+        assert_eq!(None, loops[2][0].run(&mut registers));
+        assert_eq!(Registers::from([0, 0, i64::MIN, 0]), registers);
+    }
+
+    #[test]
+    fn test_optimize_overflow() {
+        // The inner loop would add 2 * i64::MAX to a, so the outer loop can't be optimized
+        let mut program = AssembunnyProgram::from_lines(
+            "cpy 9223372036854775807 c\ninc a\ninc a\ndec c\njnz c -3\ndec d\njnz d -6",
+        )
+        .unwrap();
+        program.optimize();
+        let loops = program.loops.as_ref().unwrap();
+        assert!(loops[0].is_empty());
+        assert_eq!(4, loops[1][0].end);
+
+        let mut registers = Registers::from([0, 0, i64::MAX, 0]);
+        assert_eq!(None, loops[1][0].run(&mut registers));
+
+        let program =
+            AssembunnyProgram::from_lines("cpy 9223372036854775807 a\ninc a\ndec c\njnz c -3");
+        assert!(find_counted_loops(&program.unwrap().instructions)[0].is_empty());
     }
 }
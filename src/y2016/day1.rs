@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 use std::mem;
 
 #[derive(Debug, Clone, Copy)]
@@ -29,7 +28,7 @@ impl Rotation {
 }
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut x = 0;
     let mut y = 0;
@@ -46,7 +45,7 @@ fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut x = 0;
     let mut y = 0;
@@ -67,7 +66,9 @@ fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<Vec<(Rotation, i32)>, SimpleError> {
@@ -87,9 +88,9 @@ fn parse_input(input: &str) -> Result<Vec<(Rotation, i32)>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
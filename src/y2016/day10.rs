@@ -4,8 +4,7 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::collections::HashMap;
-use std::error::Error;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy)]
 enum BotTarget {
@@ -36,7 +35,7 @@ struct Bot {
 type StartingValues = Vec<(u32, usize)>;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let (bots, starting_values) = parse_input(input)?;
+    let (bots, starting_values) = crate::timing::parse(|| parse_input(input))?;
 
     let (values, _) = simulate_bots(bots, starting_values);
     values
@@ -48,23 +47,20 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
                 None
             }
         })
-        .ok_or_else(|| SimpleError::new(String::from("no solution found")))
+        .ok_or_else(|| SimpleError::unsupported_input(String::from("no solution found")))
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let (bots, starting_values) = parse_input(input)?;
+    let (bots, starting_values) = crate::timing::parse(|| parse_input(input))?;
 
     let (_, outputs) = simulate_bots(bots, starting_values);
 
-    for output_number in [0, 1, 2] {
-        if !outputs.contains_key(&output_number) {
-            return Err(SimpleError::new(format!(
-                "output is missing number {output_number}"
-            )));
-        }
-    }
-
-    Ok(outputs.get(&0).unwrap() * outputs.get(&1).unwrap() * outputs.get(&2).unwrap())
+    [0, 1, 2].into_iter().try_fold(1, |product, output_number| {
+        let value = outputs.get(&output_number).ok_or_else(|| {
+            SimpleError::unsupported_input(format!("output is missing number {output_number}"))
+        })?;
+        Ok(product * value)
+    })
 }
 
 fn simulate_bots(
@@ -167,12 +163,32 @@ fn parse_input(input: &str) -> Result<(Vec<Bot>, StartingValues), SimpleError> {
         }
     }
 
+    // Every bot that's given a value needs instructions for what to do with it
+    let numbers: HashSet<_> = bots.iter().map(|bot| bot.number).collect();
+    let target_numbers = bots
+        .iter()
+        .flat_map(|bot| [bot.low_target, bot.high_target])
+        .filter_map(|target| match target {
+            BotTarget::Bot(number) => Some(number),
+            BotTarget::Output(_) => None,
+        });
+    if let Some(number) = starting_values
+        .iter()
+        .map(|&(_, number)| number)
+        .chain(target_numbers)
+        .find(|number| !numbers.contains(number))
+    {
+        return Err(SimpleError::new(format!(
+            "bot {number} is given values but has no instructions"
+        )));
+    }
+
     Ok((bots, starting_values))
 }
 
-pub fn solve(input: &str) -> Result<(usize, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
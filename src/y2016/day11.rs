@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum FloorItem<'a> {
@@ -237,7 +236,9 @@ fn solve_part(input: &str, add_additional_items: bool) -> Result<usize, SimpleEr
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn item_combinations<'a>(
@@ -410,9 +411,9 @@ fn parse_line(line: &str) -> Result<FloorState, SimpleError> {
     Ok(floor)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet, VecDeque};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct Point {
@@ -105,7 +104,9 @@ fn solve_part_1(input: &str, target_x: i64, target_y: i64) -> Result<usize, Simp
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
@@ -137,9 +138,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     Ok(visited.len())
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 31, 39)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 31, 39))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
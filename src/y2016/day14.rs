@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::VecDeque;
-use std::error::Error;
 
 fn solve_part(input: &str, stretch_hashes: bool) -> Result<usize, SimpleError> {
     let salt = crate::read_single_line(input)?;
@@ -60,12 +59,14 @@ fn solve_part(input: &str, stretch_hashes: bool) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
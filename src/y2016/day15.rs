@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/15>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug)]
 struct Disc {
@@ -18,7 +17,7 @@ impl Disc {
 }
 
 fn solve_part(input: &str, add_last_disc: bool) -> Result<usize, SimpleError> {
-    let mut discs = parse_input(input)?;
+    let mut discs = crate::timing::parse(|| parse_input(input))?;
     if add_last_disc {
         discs.push(Disc {
             num_positions: 11,
@@ -36,7 +35,9 @@ fn solve_part(input: &str, add_last_disc: bool) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<Vec<Disc>, SimpleError> {
@@ -59,9 +60,9 @@ fn parse_input(input: &str) -> Result<Vec<Disc>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/16>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part(input: &str, disk_size: usize) -> Result<String, SimpleError> {
     let initial_state = crate::read_single_line(input)?;
@@ -37,9 +36,9 @@ fn checksum(s: &str) -> String {
     result
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part(input, 272)?;
-    let solution2 = solve_part(input, 35651584)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, 272))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, 35651584))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::VecDeque;
-use std::error::Error;
 use std::ops::RangeInclusive;
 
 #[derive(Debug)]
@@ -71,9 +70,12 @@ fn solve_part(input: &str, find_longest_path: bool) -> Result<String, SimpleErro
     }
 
     if find_longest_path {
-        longest_path_so_far.ok_or_else(|| SimpleError::new(String::from("no solution found")))
+        longest_path_so_far
+            .ok_or_else(|| SimpleError::unsupported_input(String::from("no solution found")))
     } else {
-        Err(SimpleError::new(String::from("no solution found")))
+        Err(SimpleError::unsupported_input(String::from(
+            "no solution found",
+        )))
     }
 }
 
@@ -81,9 +83,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     Ok(solve_part(input, true)?.len())
 }
 
-pub fn solve(input: &str) -> Result<(String, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,11 +3,10 @@
 //! <https://adventofcode.com/2016/day/18>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::iter;
 
 fn solve_part(input: &str, row_target: usize) -> Result<usize, SimpleError> {
-    let first_line_traps = parse_input(input)?;
+    let first_line_traps = crate::timing::parse(|| parse_input(input))?;
 
     let mut prev_line = first_line_traps.clone();
     let mut safe_count = first_line_traps.iter().filter(|&&b| !b).count();
@@ -55,9 +54,9 @@ fn parse_input(input: &str) -> Result<Vec<bool>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, 40)?;
-    let solution2 = solve_part(input, 400000)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, 40))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, 400000))?;
 
     Ok((solution1, solution2))
 }
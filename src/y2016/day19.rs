@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/19>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug)]
 struct Elf {
@@ -74,9 +73,9 @@ fn new_elf_circle(elf_count: usize) -> Vec<Elf> {
     elves
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/2>
 
 use crate::SimpleError;
-use std::error::Error;
 
 enum Direction {
     Up,
@@ -37,7 +36,7 @@ const KEYPAD_2: [[char; 5]; 5] = [
 ];
 
 fn solve_part_1(input: &str) -> Result<String, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut i: usize = 1;
     let mut j: usize = 1;
@@ -71,7 +70,7 @@ fn solve_part_1(input: &str) -> Result<String, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<String, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut i = 2;
     let mut j = 0;
@@ -115,9 +114,9 @@ fn parse_input(input: &str) -> Result<Vec<Vec<Direction>>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
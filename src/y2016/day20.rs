@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct IpRange {
@@ -34,11 +33,13 @@ impl IpRange {
 }
 
 fn solve_part_1(input: &str, max_valid_value: u32) -> Result<u32, SimpleError> {
-    let ranges = parse_input(input)?;
+    let ranges = crate::timing::parse(|| parse_input(input))?;
     let combined_ranges = combine_ranges(ranges);
 
     if combined_ranges[0] == IpRange::new(0, max_valid_value) {
-        return Err(SimpleError::new(String::from("no possible solution")));
+        return Err(SimpleError::unsupported_input(String::from(
+            "no possible solution",
+        )));
     }
 
     let min_allowed_value = if combined_ranges[0].start == 0 {
@@ -51,7 +52,7 @@ fn solve_part_1(input: &str, max_valid_value: u32) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str, max_valid_value: u32) -> Result<u32, SimpleError> {
-    let ranges = parse_input(input)?;
+    let ranges = crate::timing::parse(|| parse_input(input))?;
     let combined_ranges = combine_ranges(ranges);
 
     if combined_ranges[0] == IpRange::new(0, max_valid_value) {
@@ -97,7 +98,11 @@ fn parse_input(input: &str) -> Result<Vec<IpRange>, SimpleError> {
                 .split_once('-')
                 .ok_or_else(|| SimpleError::new(format!("invalid line format: {line}")))?;
 
-            Ok(IpRange::new(l.parse()?, r.parse()?))
+            let range = IpRange::new(l.parse()?, r.parse()?);
+            if range.start > range.end {
+                return Err(SimpleError::new(format!("range is backwards: {line}")));
+            }
+            Ok(range)
         })
         .collect();
 
@@ -110,9 +115,9 @@ fn parse_input(input: &str) -> Result<Vec<IpRange>, SimpleError> {
     ranges
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, u32::MAX)?;
-    let solution2 = solve_part_2(input, u32::MAX)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, u32::MAX))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, u32::MAX))?;
 
     Ok((solution1, solution2))
 }
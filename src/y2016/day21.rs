@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/21>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
@@ -118,7 +117,7 @@ fn first_letter(s: &str) -> Result<char, SimpleError> {
 }
 
 fn solve_part_1(input: &str, starting_password: &str) -> Result<String, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut current_password: Vec<_> = starting_password.chars().collect();
     for &instruction in &instructions {
@@ -129,7 +128,7 @@ fn solve_part_1(input: &str, starting_password: &str) -> Result<String, SimpleEr
 }
 
 fn solve_part_2(input: &str) -> Result<String, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut current_password: Vec<_> = "fbgdceah".chars().collect();
     for &instruction in instructions.iter().rev() {
@@ -157,7 +156,7 @@ fn rotate_right(password: &Vec<char>, n: usize) -> Vec<char> {
 
 fn find_letter(password: &Vec<char>, letter: char) -> Result<usize, SimpleError> {
     password.iter().position(|&c| c == letter).ok_or_else(|| {
-        SimpleError::new(format!(
+        SimpleError::unsupported_input(format!(
             "password does not contain letter '{letter}': {password:?}"
         ))
     })
@@ -167,9 +166,9 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
     input.lines().map(Instruction::from_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, "abcdefgh")?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, "abcdefgh"))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
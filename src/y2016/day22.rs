@@ -6,7 +6,6 @@ use crate::SimpleError;
 use std::cmp;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct Node {
@@ -81,7 +80,7 @@ fn distance_between(a: (usize, usize), b: (usize, usize)) -> usize {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let nodes = parse_input(input)?;
+    let nodes = crate::timing::parse(|| parse_input(input))?;
 
     let mut viable_node_pairs = 0;
     for node in &nodes {
@@ -96,7 +95,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let nodes = parse_input(input)?;
+    let nodes = crate::timing::parse(|| parse_input(input))?;
 
     let nodes = gridify_nodes(nodes);
     let node_types = classify_nodes(&nodes);
@@ -158,7 +157,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn find_initial_empty_location(
@@ -172,7 +173,7 @@ fn find_initial_empty_location(
         }
     }
 
-    Err(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "input does not contain empty node",
     )))
 }
@@ -226,12 +227,13 @@ fn parse_input(input: &str) -> Result<Vec<Node>, SimpleError> {
                 )));
             }
 
-            let node_word = &words[0]["/dev/grid/node-".len()..];
-            let (x, y) = node_word.split_once('-').ok_or_else(|| {
-                SimpleError::new(format!("invalid line, no '-' in node word: {line}"))
-            })?;
-            let x: usize = x[1..].parse()?;
-            let y: usize = y[1..].parse()?;
+            let (x, y) = words[0]
+                .strip_prefix("/dev/grid/node-")
+                .and_then(|node_word| node_word.split_once('-'))
+                .and_then(|(x, y)| Some((x.strip_prefix('x')?, y.strip_prefix('y')?)))
+                .ok_or_else(|| SimpleError::new(format!("invalid node name in line: {line}")))?;
+            let x: usize = x.parse()?;
+            let y: usize = y.parse()?;
 
             let size_tb = parse_size(words[1])?;
             let used_tb = parse_size(words[2])?;
@@ -282,9 +284,9 @@ fn parse_size(s: &str) -> Result<usize, SimpleError> {
     Ok(s[..s.len() - 1].parse()?)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
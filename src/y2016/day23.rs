@@ -2,27 +2,23 @@
 //!
 //! <https://adventofcode.com/2016/day/23>
 
-use crate::y2016::assembunny::AssembunnyProgram;
+use crate::y2016::assembunny::{AssembunnyProgram, Register, Registers};
 use crate::SimpleError;
-use std::collections::HashMap;
-use std::error::Error;
 
 fn solve_part(input: &str, initial_a_value: i64) -> Result<i64, SimpleError> {
     let mut program = AssembunnyProgram::from_lines(input)?;
 
-    let mut registers: HashMap<_, _> = [('a', initial_a_value), ('b', 0), ('c', 0), ('d', 0)]
-        .into_iter()
-        .collect();
+    let mut registers = Registers::from([initial_a_value, 0, 0, 0]);
 
-    program.optimize_multiplies();
-    program.execute(&mut registers);
+    program.optimize();
+    program.execute(&mut registers)?;
 
-    Ok(*registers.get(&'a').unwrap())
+    Ok(registers[Register::A])
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part(input, 7)?;
-    let solution2 = solve_part(input, 12)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, 7))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, 12))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct Point {
@@ -56,7 +55,7 @@ impl SearchState {
 }
 
 fn solve_part(input: &str, robot_must_return: bool) -> Result<usize, SimpleError> {
-    let maze = parse_input(input)?;
+    let maze = crate::timing::parse(|| parse_input(input))?;
 
     let rows = maze.walls.len();
     let cols = maze.walls[0].len();
@@ -113,7 +112,9 @@ fn solve_part(input: &str, robot_must_return: bool) -> Result<usize, SimpleError
         }
     }
 
-    Err(SimpleError::new(String::from("no solutions found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solutions found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<Maze, SimpleError> {
@@ -148,22 +149,19 @@ fn parse_input(input: &str) -> Result<Maze, SimpleError> {
         }
     }
 
-    if start.is_none() {
-        return Err(SimpleError::new(String::from(
-            "maze does not contain a '0'",
-        )));
-    }
+    let start =
+        start.ok_or_else(|| SimpleError::new(String::from("maze does not contain a '0'")))?;
 
     Ok(Maze {
         walls: walls?,
         locations,
-        start: start.unwrap(),
+        start,
     })
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
@@ -2,30 +2,121 @@
 //!
 //! <https://adventofcode.com/2016/day/25>
 
-use crate::y2016::assembunny::AssembunnyProgram;
+use crate::y2016::assembunny::{AssembunnyProgram, OutputCycle, Registers};
 use crate::SimpleError;
-use std::collections::HashMap;
-use std::error::Error;
 
-fn solve_part(input: &str) -> Result<i64, SimpleError> {
+// How many outputs to check before looking for a cycle, which rules out most values of a cheaply
+const PREFIX_LENGTH: usize = 20;
+
+// How many outputs to follow a program for while looking for a cycle
+const MAX_OUTPUTS: usize = 10_000;
+
+// The highest value of a to try. Puzzle programs output the bits of a plus a constant in the low
+// thousands, so the answer is always well below this
+const MAX_A: i64 = 1 << 16;
+
+// Returns the lowest value of a that makes the program output 0, 1, 0, 1... forever, along with
+// how many outputs it takes for the program to get back to the same state
+fn solve_part(input: &str) -> Result<(i64, usize), SimpleError> {
     let mut program = AssembunnyProgram::from_lines(input)?;
+    program.optimize();
+
+    for a in 1..=MAX_A {
+        if !starts_with_clock_signal(&program, a)? {
+            continue;
+        }
 
-    for a in 1.. {
-        let mut registers: HashMap<_, _> = [('a', a), ('b', 0), ('c', 0), ('d', 0)]
-            .into_iter()
-            .collect();
+        let registers = Registers::from([a, 0, 0, 0]);
+        let cycle = program
+            .find_output_cycle(registers, MAX_OUTPUTS)?
+            .ok_or_else(|| {
+                SimpleError::unsupported_input(format!(
+                    "output for a={a} starts like a clock signal but does not repeat within \
+                     {MAX_OUTPUTS} outputs"
+                ))
+            })?;
+        if is_clock_signal(&cycle) {
+            return Ok((a, cycle.cycle.len()));
+        }
+    }
+
+    Err(SimpleError::unsupported_input(format!(
+        "no solution found for a up to {MAX_A}"
+    )))
+}
 
-        let pattern = [0, 1].into_iter().cycle().take(20);
-        if program.outputs_pattern(&mut registers, pattern) {
-            return Ok(a);
+fn starts_with_clock_signal(program: &AssembunnyProgram, a: i64) -> Result<bool, SimpleError> {
+    let mut outputs = program.outputs(Registers::from([a, 0, 0, 0]));
+    for expected in [0, 1].into_iter().cycle().take(PREFIX_LENGTH) {
+        if outputs.next().transpose()? != Some(expected) {
+            return Ok(false);
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Ok(true)
 }
 
-pub fn solve(input: &str) -> Result<(i64, String), Box<dyn Error>> {
-    let solution1 = solve_part(input)?;
+// Past the prefix and first cycle the output repeats with the cycle's period, so it alternates
+// forever if it alternates that far and the period is even
+fn is_clock_signal(cycle: &OutputCycle) -> bool {
+    let checked_len = cycle.prefix.len() + cycle.cycle.len();
+    cycle.cycle.len().is_multiple_of(2) && (0..checked_len).all(|i| cycle.get(i) == (i % 2) as i64)
+}
+
+pub fn solve(input: &str) -> Result<(i64, String), SimpleError> {
+    let (solution1, _) = crate::timing::part_1(|| solve_part(input))?;
 
     Ok((solution1, String::new()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Same shape as the puzzle input, outputting the bits of a + 4 * 3 from lowest to highest
+    const CLOCK_PROGRAM: &str = "\
+cpy a d
+cpy 4 c
+cpy 3 b
+inc d
+dec b
+jnz b -2
+dec c
+jnz c -5
+cpy d a
+jnz 0 0
+cpy a b
+cpy 0 a
+cpy 2 c
+jnz b 2
+jnz 1 6
+dec b
+dec c
+jnz c -4
+inc a
+jnz 1 -7
+cpy 2 b
+jnz c 2
+jnz 1 4
+dec b
+dec c
+jnz 1 -4
+jnz 0 0
+out b
+jnz a -19
+jnz 1 -21";
+
+    #[test]
+    fn test_clock_program() {
+        // 30 + 12 = 0b101010, which repeats every 6 outputs
+        assert_eq!(Ok((30, 6)), solve_part(CLOCK_PROGRAM));
+        assert_eq!(Ok((30, String::new())), solve(CLOCK_PROGRAM));
+    }
+
+    #[test]
+    fn test_no_clock_signal() {
+        assert!(solve_part("out 0\njnz 1 -1").is_err());
+        // Starts like a clock signal, but a keeps growing so the state never repeats
+        assert!(solve_part("out 0\nout 1\ninc a\njnz 1 -3").is_err());
+    }
+}
@@ -3,10 +3,9 @@
 //! <https://adventofcode.com/2016/day/3>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let triangle_lengths = parse_input(input)?;
+    let triangle_lengths = crate::timing::parse(|| parse_input(input))?;
 
     let valid = triangle_lengths
         .into_iter()
@@ -17,7 +16,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let triangle_lengths = parse_input(input)?;
+    let triangle_lengths = crate::timing::parse(|| parse_input(input))?;
 
     if triangle_lengths.len() % 3 != 0 {
         return Err(SimpleError::new(format!(
@@ -68,9 +67,9 @@ fn parse_input(input: &str) -> Result<Vec<(u32, u32, u32)>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
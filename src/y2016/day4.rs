@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug)]
 struct RoomData {
@@ -14,7 +13,7 @@ struct RoomData {
 }
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let rooms = parse_input(input)?;
+    let rooms = crate::timing::parse(|| parse_input(input))?;
 
     let valid = rooms
         .iter()
@@ -47,7 +46,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<String, SimpleError> {
-    let rooms = parse_input(input)?;
+    let rooms = crate::timing::parse(|| parse_input(input))?;
 
     for room in &rooms {
         let decrypted_name: Vec<String> = room
@@ -71,7 +70,7 @@ fn solve_part_2(input: &str) -> Result<String, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "no rooms found containing 'north' and 'pole'",
     )))
 }
@@ -81,23 +80,31 @@ fn parse_input(input: &str) -> Result<Vec<RoomData>, SimpleError> {
         .lines()
         .map(|line| {
             let split: Vec<_> = line.split('-').collect();
-            if split.is_empty() {
-                return Err(SimpleError::new(String::from("empty line in input")));
-            }
+            let (last_word, name_words) = split
+                .split_last()
+                .ok_or_else(|| SimpleError::new(String::from("empty line in input")))?;
 
-            let name: Vec<Vec<_>> = split[..split.len() - 1]
+            if !name_words
+                .iter()
+                .all(|word| word.chars().all(|c| c.is_ascii_lowercase()))
+            {
+                return Err(SimpleError::new(format!(
+                    "room name should only have lowercase letters: {line}"
+                )));
+            }
+            let name: Vec<Vec<_>> = name_words
                 .iter()
                 .map(|word| word.chars().collect())
                 .collect();
 
-            let last_word = split.last().unwrap();
-            let bracket_index = last_word
-                .chars()
-                .position(|c| c == '[')
+            let (sector_id, checksum) = last_word
+                .split_once('[')
                 .ok_or_else(|| SimpleError::new(format!("line has no opening bracket: {line}")))?;
-            let sector_id: u32 = last_word[..bracket_index].parse()?;
+            let sector_id: u32 = sector_id.parse()?;
 
-            let checksum: Vec<_> = last_word[bracket_index + 1..last_word.len() - 1]
+            let checksum: Vec<_> = checksum
+                .strip_suffix(']')
+                .ok_or_else(|| SimpleError::new(format!("line has no closing bracket: {line}")))?
                 .chars()
                 .collect();
 
@@ -110,9 +117,9 @@ fn parse_input(input: &str) -> Result<Vec<RoomData>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/5>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<String, SimpleError> {
     let door_id = crate::read_single_line(input)?;
@@ -20,7 +19,9 @@ fn solve_part_1(input: &str) -> Result<String, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<String, SimpleError> {
@@ -45,12 +46,14 @@ fn solve_part_2(input: &str) -> Result<String, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
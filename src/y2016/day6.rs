@@ -4,13 +4,17 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 fn solve_part(input: &str, find_min: bool) -> Result<String, SimpleError> {
-    let chars = parse_input(input);
+    let chars = crate::timing::parse(|| parse_input(input));
     if chars.is_empty() {
         return Err(SimpleError::new(String::from("input is empty")));
     }
+    if chars.iter().any(|row| row.len() != chars[0].len()) {
+        return Err(SimpleError::new(String::from(
+            "lines are not all the same length",
+        )));
+    }
 
     let mut result = String::new();
     for j in 0..chars[0].len() {
@@ -45,9 +49,9 @@ fn parse_input(input: &str) -> Vec<Vec<char>> {
     input.lines().map(|line| line.chars().collect()).collect()
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/7>
 
 use crate::SimpleError;
-use std::error::Error;
 
 struct ParsedIp7String<'a> {
     outer_strings: Vec<&'a str>,
@@ -11,7 +10,7 @@ struct ParsedIp7String<'a> {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let ip_addresses = parse_input(input)?;
+    let ip_addresses = crate::timing::parse(|| parse_input(input))?;
 
     let valid = ip_addresses
         .into_iter()
@@ -30,7 +29,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let ip_addresses = parse_input(input)?;
+    let ip_addresses = crate::timing::parse(|| parse_input(input))?;
 
     let num_valid = ip_addresses
         .into_iter()
@@ -109,9 +108,9 @@ fn parse_input(input: &str) -> Result<Vec<ParsedIp7String>, SimpleError> {
     input.lines().map(partition_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
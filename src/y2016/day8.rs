@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/8>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
@@ -70,7 +69,7 @@ impl Instruction {
 }
 
 fn solve_both_parts(input: &str) -> Result<(usize, String), SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut screen = vec![vec![false; 50]; 6];
     for &instruction in &instructions {
@@ -145,7 +144,7 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
     input.lines().map(Instruction::from_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, String), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(usize, String), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2016/day/9>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     let total_len = input
@@ -95,9 +94,9 @@ fn parse_from_parentheses(s: &str) -> Result<(usize, usize), SimpleError> {
     Ok((l, r))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,10 +3,9 @@
 //! <https://adventofcode.com/2017/day/1>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let mut captcha = parse_input(input)?;
+    let mut captcha = crate::timing::parse(|| parse_input(input))?;
     captcha.push(captcha[0]);
 
     let sum = captcha
@@ -24,7 +23,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let captcha = parse_input(input)?;
+    let captcha = crate::timing::parse(|| parse_input(input))?;
 
     let mut sum = 0;
     for i in 0..captcha.len() {
@@ -43,9 +42,9 @@ fn parse_input(input: &str) -> Result<Vec<usize>, SimpleError> {
         .collect())
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
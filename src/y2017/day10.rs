@@ -4,10 +4,9 @@
 
 use crate::y2017::knothash;
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str, list_len: usize) -> Result<usize, SimpleError> {
-    let lengths = parse_input(input)?;
+    let lengths = crate::timing::parse(|| parse_input(input))?;
 
     let list_end = (list_len - 1) as u8;
     let mut list: Vec<_> = (0..=list_end).into_iter().collect();
@@ -36,9 +35,9 @@ fn parse_input(input: &str) -> Result<Vec<usize>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 256)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 256))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
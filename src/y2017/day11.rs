@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Direction {
@@ -31,7 +30,7 @@ impl Direction {
 }
 
 fn solve_both_parts(input: &str) -> Result<(i32, i32), SimpleError> {
-    let directions = parse_input(input)?;
+    let directions = crate::timing::parse(|| parse_input(input))?;
 
     let mut x = 0_i32;
     let mut y = 0_i32;
@@ -67,7 +66,7 @@ fn parse_input(input: &str) -> Result<Vec<Direction>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 
 #[derive(Debug)]
 struct DisjointSetNode {
@@ -58,7 +57,7 @@ impl DisjointSet {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let input = parse_input(input)?;
+    let input = crate::timing::parse(|| parse_input(input))?;
 
     let mut disjoint_set = DisjointSet::new();
     for (left_node, right_nodes) in input {
@@ -72,7 +71,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let input = parse_input(input)?;
+    let input = crate::timing::parse(|| parse_input(input))?;
 
     let mut disjoint_set = DisjointSet::new();
     for (left_node, right_nodes) in input {
@@ -107,9 +106,9 @@ fn parse_input(input: &str) -> Result<Vec<(usize, Vec<usize>)>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
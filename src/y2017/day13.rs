@@ -3,10 +3,9 @@
 //! <https://adventofcode.com/2017/day/13>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let scanner_layers = parse_input(input)?;
+    let scanner_layers = crate::timing::parse(|| parse_input(input))?;
 
     let total_severity = scanner_layers
         .into_iter()
@@ -23,7 +22,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let scanner_layers = parse_input(input)?;
+    let scanner_layers = crate::timing::parse(|| parse_input(input))?;
 
     for i in 1.. {
         if !scanner_layers
@@ -34,7 +33,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<Vec<(usize, usize)>, SimpleError> {
@@ -49,9 +50,9 @@ fn parse_input(input: &str) -> Result<Vec<(usize, usize)>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
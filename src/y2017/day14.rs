@@ -4,7 +4,6 @@
 
 use crate::y2017::knothash;
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
     let key_string = crate::read_single_line(input)?;
@@ -76,9 +75,9 @@ fn fill_region(
     }
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
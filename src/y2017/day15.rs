@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2017/day/15>
 
 use crate::SimpleError;
-use std::error::Error;
 
 const A_FACTOR: u64 = 16807;
 const B_FACTOR: u64 = 48271;
@@ -58,9 +57,9 @@ fn generate_next_value(value: u64, factor: u64) -> u64 {
     (value * factor) % 2147483647
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false, 40_000_000)?;
-    let solution2 = solve_part(input, true, 5_000_000)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false, 40_000_000))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true, 5_000_000))?;
 
     Ok((solution1, solution2))
 }
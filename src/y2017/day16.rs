@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
 enum DanceMove {
@@ -14,7 +13,29 @@ enum DanceMove {
 }
 
 impl DanceMove {
-    fn from_str(s: &str) -> Result<Self, SimpleError> {
+    fn from_str(s: &str, num_programs: u8) -> Result<Self, SimpleError> {
+        let dance_move = Self::parse(s)?;
+
+        let in_range = match dance_move {
+            Self::Spin(x) => x <= num_programs as usize,
+            Self::Exchange(a, b) => a.max(b) < num_programs as usize,
+            Self::Partner(a, b) => {
+                let programs = b'a'..b'a' + num_programs;
+                [a, b]
+                    .iter()
+                    .all(|&c| c.is_ascii() && programs.contains(&(c as u8)))
+            }
+        };
+        if !in_range {
+            return Err(SimpleError::new(format!(
+                "dance move is out of range for {num_programs} programs: {s}"
+            )));
+        }
+
+        Ok(dance_move)
+    }
+
+    fn parse(s: &str) -> Result<Self, SimpleError> {
         match s.chars().next() {
             Some('s') => Ok(Self::Spin(s[1..].parse()?)),
             Some('x') => {
@@ -26,7 +47,7 @@ impl DanceMove {
             Some('p') => {
                 let (a, b) = s[1..]
                     .split_once('/')
-                    .ok_or_else(|| SimpleError::new(format!("invalid exchange move: {s}")))?;
+                    .ok_or_else(|| SimpleError::new(format!("invalid partner move: {s}")))?;
                 Ok(Self::Partner(a.parse()?, b.parse()?))
             }
             _ => Err(SimpleError::new(format!("invalid dance move string: {s}"))),
@@ -35,7 +56,7 @@ impl DanceMove {
 }
 
 fn solve_part_1(input: &str, num_programs: u8) -> Result<String, SimpleError> {
-    let dance_moves = parse_input(input)?;
+    let dance_moves = crate::timing::parse(|| parse_input(input, num_programs))?;
 
     let programs_end = (b'a' + num_programs) as char;
     let programs: Vec<_> = ('a'..programs_end).collect();
@@ -46,7 +67,7 @@ fn solve_part_1(input: &str, num_programs: u8) -> Result<String, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<String, SimpleError> {
-    let dance_moves = parse_input(input)?;
+    let dance_moves = crate::timing::parse(|| parse_input(input, 16))?;
 
     let mut programs: Vec<_> = ('a'..='p').collect();
 
@@ -68,7 +89,9 @@ fn solve_part_2(input: &str) -> Result<String, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn simulate_dance(mut programs: Vec<char>, dance_moves: &[DanceMove]) -> Vec<char> {
@@ -96,15 +119,17 @@ fn simulate_dance(mut programs: Vec<char>, dance_moves: &[DanceMove]) -> Vec<cha
     programs
 }
 
-fn parse_input(input: &str) -> Result<Vec<DanceMove>, SimpleError> {
+fn parse_input(input: &str, num_programs: u8) -> Result<Vec<DanceMove>, SimpleError> {
     let line = crate::read_single_line(input)?;
 
-    line.split(',').map(DanceMove::from_str).collect()
+    line.split(',')
+        .map(|s| DanceMove::from_str(s, num_programs))
+        .collect()
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 16)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 16))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -117,4 +142,11 @@ mod tests {
     fn test_sample_input_part_1() {
         assert_eq!(Ok(String::from("baedc")), solve_part_1("s1,x3/4,pe/b", 5));
     }
+
+    #[test]
+    fn test_out_of_range_moves() {
+        assert!(solve_part_1("s6", 5).is_err());
+        assert!(solve_part_1("x3/5", 5).is_err());
+        assert!(solve_part_1("pe/f", 5).is_err());
+    }
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::cell::RefCell;
-use std::error::Error;
 use std::rc::{Rc, Weak};
 
 #[derive(Debug)]
@@ -100,9 +99,9 @@ fn populate_circular_buffer(
     current_node
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
-use std::error::Error;
 use std::iter;
 use std::rc::Rc;
 
@@ -174,7 +173,7 @@ fn get_register_value(registers: &HashMap<char, i64>, x: char) -> i64 {
 }
 
 fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let send_queue = Rc::new(RefCell::new(VecDeque::new()));
     let receive_queue = Rc::clone(&send_queue);
@@ -182,11 +181,11 @@ fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
 
     program
         .find_first_received_value()
-        .ok_or_else(|| SimpleError::new(String::from("no solution found")))
+        .ok_or_else(|| SimpleError::unsupported_input(String::from("no solution found")))
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let queue0 = Rc::new(RefCell::new(VecDeque::new()));
     let queue1 = Rc::new(RefCell::new(VecDeque::new()));
@@ -208,9 +207,9 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
     input.lines().map(Instruction::from_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(i64, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2017/day/19>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Space {
@@ -28,7 +27,7 @@ impl Space {
 }
 
 fn solve_both_parts(input: &str) -> Result<(String, usize), SimpleError> {
-    let grid = parse_input(input)?;
+    let grid = crate::timing::parse(|| parse_input(input))?;
 
     let start = match grid[0]
         .iter()
@@ -67,7 +66,7 @@ fn solve_both_parts(input: &str) -> Result<(String, usize), SimpleError> {
                     dj = new_dj;
                 }
                 None => {
-                    return Err(SimpleError::new(format!(
+                    return Err(SimpleError::unsupported_input(format!(
                         "unable to turn at i={i}, j={j}, di={di}, dj={dj}"
                     )))
                 }
@@ -127,7 +126,7 @@ fn parse_input(input: &str) -> Result<Vec<Vec<Space>>, SimpleError> {
     Ok(grid)
 }
 
-pub fn solve(input: &str) -> Result<(String, usize), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(String, usize), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
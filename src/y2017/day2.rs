@@ -4,11 +4,10 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 use std::num::ParseIntError;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let spreadsheet = parse_input(input)?;
+    let spreadsheet = crate::timing::parse(|| parse_input(input))?;
 
     let sum = spreadsheet
         .iter()
@@ -24,7 +23,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let spreadsheet = parse_input(input)?;
+    let spreadsheet = crate::timing::parse(|| parse_input(input))?;
 
     let sum = spreadsheet
         .iter()
@@ -38,7 +37,7 @@ fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
                     }
                 }
             }
-            Err(SimpleError::new(format!(
+            Err(SimpleError::unsupported_input(format!(
                 "row has no evenly divisible pair: {row:?}"
             )))
         })
@@ -56,9 +55,9 @@ fn parse_input(input: &str) -> Result<Vec<Vec<u32>>, ParseIntError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
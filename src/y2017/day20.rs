@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 use std::ops::{Add, AddAssign};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -20,7 +19,10 @@ impl Coords {
     }
 
     fn from_str(s: &str) -> Result<Self, SimpleError> {
-        let s = &s[1..s.len() - 1];
+        let s = s
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .ok_or_else(|| SimpleError::new(format!("invalid coordinate string: {s}")))?;
         let split: Vec<_> = s.split(',').collect();
         if split.len() != 3 {
             return Err(SimpleError::new(format!("invalid coordinate string: {s}")));
@@ -59,6 +61,11 @@ struct Particle {
     acceleration: Coords,
 }
 
+fn strip_field<'a>(s: &'a str, prefix: &str, line: &str) -> Result<&'a str, SimpleError> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| SimpleError::new(format!("expected {prefix} in line: {line}")))
+}
+
 impl Particle {
     fn from_line(line: &str) -> Result<Self, SimpleError> {
         let split: Vec<_> = line.split(", ").collect();
@@ -67,9 +74,9 @@ impl Particle {
             _ => return Err(SimpleError::new(format!("invalid line format: {line}"))),
         };
 
-        let position = Coords::from_str(&p[2..])?;
-        let velocity = Coords::from_str(&v[2..])?;
-        let acceleration = Coords::from_str(&a[2..])?;
+        let position = Coords::from_str(strip_field(p, "p=", line)?)?;
+        let velocity = Coords::from_str(strip_field(v, "v=", line)?)?;
+        let acceleration = Coords::from_str(strip_field(a, "a=", line)?)?;
 
         Ok(Self {
             position,
@@ -89,7 +96,7 @@ impl Particle {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let mut particles = parse_input(input)?;
+    let mut particles = crate::timing::parse(|| parse_input(input))?;
     let mut last_particles = particles.clone();
 
     let destroyed_particles = vec![false; particles.len()];
@@ -110,7 +117,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let mut particles = parse_input(input)?;
+    let mut particles = crate::timing::parse(|| parse_input(input))?;
     let mut last_particles = particles.clone();
 
     let mut destroyed_particles = vec![false; particles.len()];
@@ -206,12 +213,21 @@ fn update_destroyed_particles(particles: &[Particle], destroyed_particles: &mut
 }
 
 fn parse_input(input: &str) -> Result<Vec<Particle>, SimpleError> {
-    input.lines().map(Particle::from_line).collect()
+    let particles: Vec<_> = input
+        .lines()
+        .map(Particle::from_line)
+        .collect::<Result<_, _>>()?;
+
+    if particles.is_empty() {
+        return Err(SimpleError::new(String::from("input should not be empty")));
+    }
+
+    Ok(particles)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug)]
 struct Rule {
@@ -19,7 +18,7 @@ const START_PIXELS: [[bool; 3]; 3] = [
 ];
 
 fn solve_part(input: &str, iterations: usize) -> Result<usize, SimpleError> {
-    let rules = parse_input(input)?;
+    let rules = crate::timing::parse(|| parse_input(input))?;
 
     let rule_map = generate_rule_map(&rules);
 
@@ -46,7 +45,9 @@ fn solve_part(input: &str, iterations: usize) -> Result<usize, SimpleError> {
                 }
 
                 let enhanced = rule_map.get(&pixel_chunk).ok_or_else(|| {
-                    SimpleError::new(format!("no enhancement found for chunk: {pixel_chunk:?}"))
+                    SimpleError::unsupported_input(format!(
+                        "no enhancement found for chunk: {pixel_chunk:?}"
+                    ))
                 })?;
 
                 for k in 0..enhanced.len() {
@@ -131,9 +132,9 @@ fn parse_rule_part(s: &str) -> Vec<Vec<bool>> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, 5)?;
-    let solution2 = solve_part(input, 18)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, 5))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, 18))?;
 
     Ok((solution1, solution2))
 }
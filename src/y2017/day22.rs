@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 use std::mem;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -39,7 +38,9 @@ impl Point {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let mut points: HashSet<_> = parse_input(input)?.into_iter().collect();
+    let mut points: HashSet<_> = crate::timing::parse(|| parse_input(input))?
+        .into_iter()
+        .collect();
 
     let mut x = 0;
     let mut y = 0;
@@ -69,7 +70,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let mut point_states: HashMap<_, _> = parse_input(input)?
+    let mut point_states: HashMap<_, _> = crate::timing::parse(|| parse_input(input))?
         .into_iter()
         .map(|point| (point, NodeState::Infected))
         .collect();
@@ -121,11 +122,11 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Point>, SimpleError> {
-    if input.lines().next().is_none() {
-        return Err(SimpleError::new(String::from("input is empty")));
-    }
-
-    let side_len = input.lines().next().unwrap().len() as i32;
+    let side_len = input
+        .lines()
+        .next()
+        .ok_or_else(|| SimpleError::new(String::from("input is empty")))?
+        .len() as i32;
 
     let mut points = Vec::new();
     for (i, line) in input.lines().enumerate() {
@@ -142,9 +143,9 @@ fn parse_input(input: &str) -> Result<Vec<Point>, SimpleError> {
     Ok(points)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Arg {
@@ -50,7 +49,7 @@ impl Instruction {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut registers = HashMap::new();
     let mut pc = 0;
@@ -90,12 +89,12 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut b = match instructions[0] {
         Instruction::Set('b', Arg::Constant(n)) => n,
         _ => {
-            return Err(SimpleError::new(format!(
+            return Err(SimpleError::unsupported_input(format!(
                 "expected 'set b X', got {:?}",
                 instructions[0]
             )))
@@ -105,7 +104,7 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     b *= match instructions[4] {
         Instruction::Multiply('b', Arg::Constant(n)) => n,
         _ => {
-            return Err(SimpleError::new(format!(
+            return Err(SimpleError::unsupported_input(format!(
                 "expected 'mul b X', got {:?}",
                 instructions[4]
             )))
@@ -115,7 +114,7 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     b -= match instructions[5] {
         Instruction::Subtract('b', Arg::Constant(n)) => n,
         _ => {
-            return Err(SimpleError::new(format!(
+            return Err(SimpleError::unsupported_input(format!(
                 "expected 'sub b X', got {:?}",
                 instructions[5]
             )))
@@ -123,7 +122,7 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     };
 
     if instructions[6] != Instruction::Set('c', Arg::Register('b')) {
-        return Err(SimpleError::new(format!(
+        return Err(SimpleError::unsupported_input(format!(
             "expected 'set c b', got {:?}",
             instructions[6]
         )));
@@ -132,7 +131,7 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     let c = b - match instructions[7] {
         Instruction::Subtract('c', Arg::Constant(n)) => n,
         _ => {
-            return Err(SimpleError::new(format!(
+            return Err(SimpleError::unsupported_input(format!(
                 "expected 'sub c X', got {:?}",
                 instructions[7]
             )))
@@ -143,7 +142,7 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     let step = match instructions[instructions.len() - 2] {
         Instruction::Subtract('b', Arg::Constant(n)) => -n,
         _ => {
-            return Err(SimpleError::new(format!(
+            return Err(SimpleError::unsupported_input(format!(
                 "expected 'sub b X', got {:?}",
                 instructions[instructions.len() - 2]
             )))
@@ -175,9 +174,9 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
     input.lines().map(Instruction::from_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
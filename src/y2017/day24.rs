@@ -4,10 +4,9 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let components = parse_input(input)?;
+    let components = crate::timing::parse(|| parse_input(input))?;
 
     let visited = vec![false; components.len()];
     let max_strength = search(&components, visited, 0, false, 0, None);
@@ -16,7 +15,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let components = parse_input(input)?;
+    let components = crate::timing::parse(|| parse_input(input))?;
 
     let visited = vec![false; components.len()];
     let longest_bridge_len = search(&components, visited.clone(), 0, true, 0, None);
@@ -91,9 +90,9 @@ fn parse_input(input: &str) -> Result<Vec<(u32, u32)>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
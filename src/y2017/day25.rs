@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 
 #[derive(Debug)]
 struct State {
@@ -29,7 +28,7 @@ fn solve_part(input: &str) -> Result<usize, SimpleError> {
         states,
         starting_state,
         checksum_steps,
-    } = parse_input(input)?;
+    } = crate::timing::parse(|| parse_input(input))?;
 
     let states: HashMap<_, _> = states
         .into_iter()
@@ -40,7 +39,9 @@ fn solve_part(input: &str) -> Result<usize, SimpleError> {
     let mut current_state_name = starting_state;
     let mut current_pos = 0;
     for _ in 0..checksum_steps {
-        let current_state = states.get(&current_state_name).unwrap();
+        let current_state = states.get(&current_state_name).ok_or_else(|| {
+            SimpleError::new(format!("no instructions for state {current_state_name}"))
+        })?;
         let (value, direction, next_state) = if on_bits.contains(&current_pos) {
             (
                 current_state.one_value,
@@ -143,8 +144,8 @@ fn last_word_without_punctuation(s: &str) -> Result<&str, SimpleError> {
     }
 }
 
-pub fn solve(input: &str) -> Result<(usize, String), Box<dyn Error>> {
-    let solution1 = solve_part(input)?;
+pub fn solve(input: &str) -> Result<(usize, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input))?;
 
     Ok((solution1, String::new()))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     let valid_count = input
@@ -42,9 +41,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     Ok(valid_count)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,11 +3,10 @@
 //! <https://adventofcode.com/2017/day/5>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::num::ParseIntError;
 
 fn solve_part(input: &str, part_two: bool) -> Result<usize, SimpleError> {
-    let mut jumps = parse_input(input)?;
+    let mut jumps = crate::timing::parse(|| parse_input(input))?;
 
     let mut i = 0;
     let mut steps = 0;
@@ -31,9 +30,9 @@ fn parse_input(input: &str) -> Result<Vec<i32>, ParseIntError> {
     input.lines().map(|line| line.parse()).collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
@@ -4,11 +4,10 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 use std::iter;
 
 fn solve_part(input: &str, return_cycle_len: bool) -> Result<usize, SimpleError> {
-    let mut banks = parse_input(input)?;
+    let mut banks = crate::timing::parse(|| parse_input(input))?;
 
     let mut cycles = 0;
     let mut seen_configurations: HashMap<_, _> = iter::once((banks.clone(), 0)).collect();
@@ -53,9 +52,9 @@ fn parse_input(input: &str) -> Result<Vec<u32>, SimpleError> {
     Ok(banks)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
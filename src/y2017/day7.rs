@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, Clone)]
 struct Program {
@@ -20,7 +19,10 @@ struct ProgramTreeNode {
 }
 
 impl ProgramTreeNode {
-    fn find_fixed_weight(&self) -> Result<u32, u32> {
+    // Returns the total weight of the tower if it's balanced, or else the weight the unbalanced
+    // program should have. That's None if the tower can't say which program is wrong, e.g. when a
+    // program holds two towers with different weights
+    fn find_fixed_weight(&self) -> Result<u32, Option<u32>> {
         if self.holding.is_empty() {
             return Ok(self.weight);
         }
@@ -61,7 +63,7 @@ impl ProgramTreeNode {
                     }
                 },
             )
-            .unwrap();
+            .ok_or(None)?;
 
         let incorrect_index = holding_weights
             .iter()
@@ -70,26 +72,33 @@ impl ProgramTreeNode {
 
         let difference = (correct_weight as i32) - (holding_weights[incorrect_index] as i32);
 
-        Err((self.holding[incorrect_index].weight as i32 + difference) as u32)
+        Err(Some(
+            (self.holding[incorrect_index].weight as i32 + difference) as u32,
+        ))
     }
 }
 
 fn solve_part_1(input: &str) -> Result<String, SimpleError> {
-    let programs = parse_input(input)?;
+    let programs = crate::timing::parse(|| parse_input(input))?;
     let sorted_programs = topological_sort(&programs);
 
     Ok(sorted_programs[0].name.clone())
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let programs = parse_input(input)?;
+    let programs = crate::timing::parse(|| parse_input(input))?;
     let sorted_programs = topological_sort(&programs);
 
     let program_tree_root = build_tree(&sorted_programs);
 
     match program_tree_root.find_fixed_weight() {
-        Err(result) => Ok(result),
-        Ok(_) => Err(SimpleError::new(String::from("no solution found"))),
+        Err(Some(result)) => Ok(result),
+        Err(None) => Err(SimpleError::unsupported_input(String::from(
+            "can't tell which program has the wrong weight",
+        ))),
+        Ok(_) => Err(SimpleError::unsupported_input(String::from(
+            "no solution found",
+        ))),
     }
 }
 
@@ -151,7 +160,7 @@ fn sort_visit<'a>(
 }
 
 fn parse_input(input: &str) -> Result<Vec<Program>, SimpleError> {
-    input
+    let programs: Vec<_> = input
         .lines()
         .map(|line| match line.split_once(" -> ") {
             Some((l, r)) => {
@@ -172,7 +181,61 @@ fn parse_input(input: &str) -> Result<Vec<Program>, SimpleError> {
                 })
             }
         })
-        .collect()
+        .collect::<Result<_, SimpleError>>()?;
+
+    validate_tower(&programs)?;
+
+    Ok(programs)
+}
+
+// Checks that the programs form a single tower, with every program except the bottom one held by
+// exactly one other program
+fn validate_tower(programs: &[Program]) -> Result<(), SimpleError> {
+    let name_map: HashMap<_, _> = programs
+        .iter()
+        .map(|program| (program.name.as_str(), program))
+        .collect();
+
+    let mut held = HashSet::new();
+    for program in programs {
+        for holding_name in &program.holding {
+            if !name_map.contains_key(holding_name.as_str()) {
+                return Err(SimpleError::new(format!(
+                    "{} is holding unknown program {holding_name}",
+                    program.name
+                )));
+            }
+            if !held.insert(holding_name.as_str()) {
+                return Err(SimpleError::new(format!(
+                    "{holding_name} is held by more than one program"
+                )));
+            }
+        }
+    }
+
+    let mut bottom = programs
+        .iter()
+        .filter(|program| !held.contains(program.name.as_str()));
+    let (Some(bottom), None) = (bottom.next(), bottom.next()) else {
+        return Err(SimpleError::new(String::from(
+            "programs should form exactly one tower",
+        )));
+    };
+
+    // Every program is held at most once, so the tower only misses programs that are in a cycle
+    let mut stack = vec![bottom];
+    let mut tower_size = 0;
+    while let Some(program) = stack.pop() {
+        tower_size += 1;
+        stack.extend(program.holding.iter().map(|name| name_map[name.as_str()]));
+    }
+    if tower_size != programs.len() {
+        return Err(SimpleError::new(String::from(
+            "programs are holding each other in a cycle",
+        )));
+    }
+
+    Ok(())
 }
 
 fn parse_name_and_weight(s: &str) -> Result<(String, u32), SimpleError> {
@@ -180,14 +243,18 @@ fn parse_name_and_weight(s: &str) -> Result<(String, u32), SimpleError> {
         .split_once(' ')
         .ok_or_else(|| SimpleError::new(format!("invalid name/weight string: {s}")))?;
 
-    let weight = weight[1..weight.len() - 1].parse()?;
+    let weight = weight
+        .strip_prefix('(')
+        .and_then(|weight| weight.strip_suffix(')'))
+        .ok_or_else(|| SimpleError::new(format!("invalid name/weight string: {s}")))?
+        .parse()?;
 
     Ok((String::from(name), weight))
 }
 
-pub fn solve(input: &str) -> Result<(String, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -207,4 +274,14 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(60), solve_part_2(SAMPLE_INPUT));
     }
+
+    #[test]
+    fn test_invalid_towers() {
+        assert!(solve_part_1("").is_err());
+        assert!(solve_part_1("a (1) -> b").is_err());
+        assert!(solve_part_1("a (1) -> c\nb (1) -> c\nc (1)").is_err());
+        assert!(solve_part_1("a (1)\nb (1) -> c\nc (1) -> b").is_err());
+        assert!(solve_part_1("a 1").is_err());
+        assert!(solve_part_2("a (1) -> b, c\nb (1)\nc (2)").is_err());
+    }
 }
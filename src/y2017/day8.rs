@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug)]
 enum Condition {
@@ -51,7 +50,7 @@ struct Instruction {
 }
 
 fn solve_part(input: &str, find_max_ever: bool) -> Result<i64, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut registers: HashMap<String, i64> = HashMap::new();
     let mut max_ever = i64::MIN;
@@ -73,7 +72,7 @@ fn solve_part(input: &str, find_max_ever: bool) -> Result<i64, SimpleError> {
     let max = registers
         .into_values()
         .max()
-        .ok_or_else(|| SimpleError::new(String::from("no registers were set")))?;
+        .ok_or_else(|| SimpleError::unsupported_input(String::from("no registers were set")))?;
 
     Ok(max)
 }
@@ -102,9 +101,9 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
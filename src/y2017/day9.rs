@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2017/day/9>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::iter::Peekable;
 
 fn solve_both_parts(input: &str) -> Result<(usize, usize), SimpleError> {
@@ -67,7 +66,7 @@ where
     garbage_chars
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
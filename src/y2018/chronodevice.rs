@@ -1,4 +1,4 @@
-use crate::SimpleError;
+use crate::simpleerror::{self, SimpleError};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum ChronoOperation {
@@ -91,8 +91,48 @@ impl ChronoOperation {
         }
     }
 
+    // Whether a and b are register numbers rather than values
+    fn reads_registers(&self) -> (bool, bool) {
+        match self {
+            Self::AddRegister
+            | Self::MultiplyRegister
+            | Self::AndRegister
+            | Self::OrRegister
+            | Self::GreaterThanRegisterRegister
+            | Self::EqualRegisterRegister => (true, true),
+            Self::AddImmediate
+            | Self::MultiplyImmediate
+            | Self::AndImmediate
+            | Self::OrImmediate
+            | Self::SetRegister
+            | Self::GreaterThanRegisterImmediate
+            | Self::EqualRegisterImmediate => (true, false),
+            Self::GreaterThanImmediateRegister | Self::EqualImmediateRegister => (false, true),
+            Self::SetImmediate => (false, false),
+        }
+    }
+
+    // Checks that every register the operation reads or writes exists, so execute can't panic
+    pub fn check_operands(
+        &self,
+        register_count: usize,
+        a: u64,
+        b: u64,
+        c: usize,
+    ) -> Result<(), SimpleError> {
+        let (reads_a, reads_b) = self.reads_registers();
+        let in_range = |register: u64| register < register_count as u64;
+        if (reads_a && !in_range(a)) || (reads_b && !in_range(b)) || c >= register_count {
+            return Err(SimpleError::new(format!(
+                "instruction uses a register that doesn't exist, there are only {register_count}"
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn can_produce(&self, before: &[u64], after: &[u64], a: u64, b: u64, c: usize) -> bool {
-        after[c] == self.execute(before, a, b)
+        self.check_operands(before.len(), a, b, c).is_ok() && after[c] == self.execute(before, a, b)
     }
 }
 
@@ -105,18 +145,22 @@ pub struct ChronoInstruction {
 }
 
 impl ChronoInstruction {
-    pub fn from_line(line: &str) -> Result<Self, SimpleError> {
+    // Parses an instruction for a device with the given number of registers
+    pub fn from_line(line: &str, register_count: usize) -> Result<Self, SimpleError> {
         let split: Vec<_> = line.split(' ').collect();
         if split.len() != 4 {
             return Err(SimpleError::new(format!(
                 "invalid line format, expected 3 spaces: {line}"
-            )));
+            ))
+            .at_token(line, line));
         }
 
-        let op = ChronoOperation::from_str(split[0])?;
-        let a = split[1].parse()?;
-        let b = split[2].parse()?;
-        let c = split[3].parse()?;
+        let op = ChronoOperation::from_str(split[0]).map_err(|err| err.at_token(line, split[0]))?;
+        let a = simpleerror::parse_token(line, split[1])?;
+        let b = simpleerror::parse_token(line, split[2])?;
+        let c = simpleerror::parse_token(line, split[3])?;
+        op.check_operands(register_count, a, b, c)
+            .map_err(|err| err.at_token(line, line))?;
 
         Ok(ChronoInstruction { op, a, b, c })
     }
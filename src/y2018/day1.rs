@@ -4,15 +4,16 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 use std::iter;
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    Ok(parse_input(input)?.into_iter().sum())
+    Ok(crate::timing::parse(|| parse_input(input))?
+        .into_iter()
+        .sum())
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let frequencies = parse_input(input)?;
+    let frequencies = crate::timing::parse(|| parse_input(input))?;
 
     let mut current_freq = 0;
     let mut seen_freqs: HashSet<_> = iter::once(0).collect();
@@ -23,7 +24,9 @@ fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<Vec<i32>, SimpleError> {
@@ -37,9 +40,9 @@ fn parse_input(input: &str) -> Result<Vec<i32>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
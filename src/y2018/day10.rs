@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp;
 use std::collections::HashSet;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct Coords {
@@ -33,7 +32,7 @@ impl Point {
 }
 
 fn solve_both_parts(input: &str) -> Result<(String, usize), SimpleError> {
-    let mut points = parse_input(input)?;
+    let mut points = crate::timing::parse(|| parse_input(input))?;
 
     for i in 0.. {
         let (min_x, max_x, min_y, max_y) = points.iter().fold(
@@ -74,20 +73,19 @@ fn solve_both_parts(input: &str) -> Result<(String, usize), SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<Vec<Point>, SimpleError> {
     input
         .lines()
         .map(|line| {
-            let position_end_index = line
-                .chars()
-                .position(|c| c == '>')
-                .ok_or_else(|| SimpleError::new(format!("line has no '>': {line}")))?;
-
-            let position = &line["position=".len()..position_end_index + 1];
-            let velocity = &line[(position_end_index + 2 + "velocity=".len())..];
+            let (position, velocity) = line
+                .strip_prefix("position=")
+                .and_then(|rest| rest.split_once(" velocity="))
+                .ok_or_else(|| SimpleError::new(format!("invalid line format: {line}")))?;
 
             let position = parse_coords(position)?;
             let velocity = parse_coords(velocity)?;
@@ -98,7 +96,13 @@ fn parse_input(input: &str) -> Result<Vec<Point>, SimpleError> {
 }
 
 fn parse_coords(s: &str) -> Result<Coords, SimpleError> {
-    let stripped: String = s[1..s.len() - 1].chars().filter(|&c| c != ' ').collect();
+    let stripped: String = s
+        .strip_prefix('<')
+        .and_then(|s| s.strip_suffix('>'))
+        .ok_or_else(|| SimpleError::new(format!("invalid coords string: {s}")))?
+        .chars()
+        .filter(|&c| c != ' ')
+        .collect();
     let (x, y) = stripped
         .split_once(',')
         .ok_or_else(|| SimpleError::new(format!("invalid coords string: {s}")))?;
@@ -106,7 +110,7 @@ fn parse_coords(s: &str) -> Result<Coords, SimpleError> {
     Ok(Coords::new(x.parse()?, y.parse()?))
 }
 
-pub fn solve(input: &str) -> Result<(String, usize), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(String, usize), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
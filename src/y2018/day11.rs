@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2018/day/11>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -128,9 +127,9 @@ fn compute_power_levels(serial_number: i32) -> [[i32; 300]; 300] {
     power_levels
 }
 
-pub(crate) fn solve(input: &str) -> Result<(Point, Part2Solution), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub(crate) fn solve(input: &str) -> Result<(Point, Part2Solution), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
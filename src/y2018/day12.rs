@@ -4,10 +4,9 @@
 
 use crate::SimpleError;
 use std::collections::{HashSet, VecDeque};
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
-    let (initial_state, plant_generate_rules) = parse_input(input)?;
+    let (initial_state, plant_generate_rules) = crate::timing::parse(|| parse_input(input))?;
 
     let mut state = VecDeque::from(initial_state);
     let mut index_0_position = 0;
@@ -21,7 +20,7 @@ fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
-    let (initial_state, plant_generate_rules) = parse_input(input)?;
+    let (initial_state, plant_generate_rules) = crate::timing::parse(|| parse_input(input))?;
 
     let mut state = VecDeque::from(initial_state);
     let mut index_0_position = 0;
@@ -39,7 +38,9 @@ fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
         index_0_position += index_0_position_diff;
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn score(plants: &VecDeque<bool>, index_0_position: i64) -> i64 {
@@ -94,23 +95,31 @@ fn parse_input(input: &str) -> Result<(Vec<bool>, HashSet<Vec<bool>>), SimpleErr
         )));
     }
 
-    let initial_state: Vec<_> = lines[0]["initial state: ".len()..]
+    let initial_state: Vec<_> = lines[0]
+        .strip_prefix("initial state: ")
+        .ok_or_else(|| SimpleError::new(format!("invalid initial state line: {}", lines[0])))?
         .chars()
         .map(|c| c == '#')
         .collect();
 
-    let plant_generate_rules: HashSet<_> = lines[2..]
-        .iter()
-        .filter(|line| line.ends_with('#'))
-        .map(|line| line[..5].chars().map(|c| c == '#').collect::<Vec<_>>())
-        .collect();
+    let mut plant_generate_rules = HashSet::new();
+    for line in &lines[2..] {
+        let (pattern, result) = line
+            .split_once(" => ")
+            .filter(|(pattern, _)| pattern.chars().count() == 5)
+            .ok_or_else(|| SimpleError::new(format!("invalid rule line: {line}")))?;
+
+        if result == "#" {
+            plant_generate_rules.insert(pattern.chars().map(|c| c == '#').collect::<Vec<_>>());
+        }
+    }
 
     Ok((initial_state, plant_generate_rules))
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
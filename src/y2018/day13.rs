@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::mem;
 use std::ops::{Add, AddAssign};
@@ -97,8 +96,6 @@ struct MineCart {
 }
 
 impl MineCart {
-    const MINE_CART_CHARS: [char; 4] = ['^', '<', '>', 'v'];
-
     fn new(x: usize, y: usize, dx: i32, dy: i32) -> Self {
         Self {
             position: Point::new(x, y),
@@ -112,7 +109,12 @@ impl MineCart {
         self.position += (self.dx, self.dy);
     }
 
-    fn maybe_turn(&mut self, current_space: Space) {
+    fn maybe_turn(&mut self, grid: &[Vec<Space>]) -> Result<(), SimpleError> {
+        let current_space = grid
+            .get(self.position.y)
+            .and_then(|row| row.get(self.position.x))
+            .copied()
+            .unwrap_or(Space::Void);
         match current_space {
             Space::HorizontalTrack | Space::VerticalTrack => {}
             Space::Intersection => match self.last_turn_direction {
@@ -142,8 +144,15 @@ impl MineCart {
                     self.turn_left();
                 }
             }
-            Space::Void => panic!("mine cart fell into the void: {self:?}"),
+            Space::Void => {
+                return Err(SimpleError::unsupported_input(format!(
+                    "mine cart went off the track at {}",
+                    self.position
+                )))
+            }
         }
+
+        Ok(())
     }
 
     fn turn_left(&mut self) {
@@ -158,7 +167,12 @@ impl MineCart {
 }
 
 fn solve_part_1(input: &str) -> Result<Point, SimpleError> {
-    let (grid, mut mine_carts) = parse_input(input)?;
+    let (grid, mut mine_carts) = crate::timing::parse(|| parse_input(input))?;
+    if mine_carts.len() < 2 {
+        return Err(SimpleError::unsupported_input(String::from(
+            "there should be at least two mine carts to crash",
+        )));
+    }
 
     loop {
         mine_carts.sort_by_key(|mine_cart| mine_cart.position);
@@ -176,13 +190,13 @@ fn solve_part_1(input: &str) -> Result<Point, SimpleError> {
             }
 
             mine_cart_positions.insert(mine_cart.position);
-            mine_cart.maybe_turn(grid[mine_cart.position.y][mine_cart.position.x]);
+            mine_cart.maybe_turn(&grid)?;
         }
     }
 }
 
 fn solve_part_2(input: &str) -> Result<Point, SimpleError> {
-    let (grid, mut mine_carts) = parse_input(input)?;
+    let (grid, mut mine_carts) = crate::timing::parse(|| parse_input(input))?;
 
     loop {
         mine_carts.sort_by_key(|mine_cart| mine_cart.position);
@@ -209,7 +223,7 @@ fn solve_part_2(input: &str) -> Result<Point, SimpleError> {
             }
 
             mine_cart_positions.insert(mine_cart.position, i);
-            mine_cart.maybe_turn(grid[mine_cart.position.y][mine_cart.position.x]);
+            mine_cart.maybe_turn(&grid)?;
         }
 
         mine_carts = mine_carts
@@ -219,8 +233,14 @@ fn solve_part_2(input: &str) -> Result<Point, SimpleError> {
             .map(|(_, mine_cart)| mine_cart)
             .collect();
 
-        if mine_carts.len() == 1 {
-            return Ok(mine_carts[0].position);
+        match mine_carts.as_slice() {
+            [] => {
+                return Err(SimpleError::unsupported_input(String::from(
+                    "every mine cart crashed",
+                )))
+            }
+            [mine_cart] => return Ok(mine_cart.position),
+            _ => {}
         }
     }
 }
@@ -232,7 +252,11 @@ fn parse_input(input: &str) -> Result<(Vec<Vec<Space>>, Vec<MineCart>), SimpleEr
     }
 
     let rows = lines.len();
-    let cols = lines[0].len();
+    let cols = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default();
 
     let mut grid = vec![vec![Space::Void; cols]; rows];
     let mut mine_carts = Vec::new();
@@ -240,15 +264,15 @@ fn parse_input(input: &str) -> Result<(Vec<Vec<Space>>, Vec<MineCart>), SimpleEr
     for (i, line) in lines.iter().enumerate() {
         for (j, c) in line.chars().enumerate() {
             grid[i][j] = Space::from_char(c)?;
-            if MineCart::MINE_CART_CHARS.contains(&c) {
-                let (dx, dy) = match c {
-                    '^' => (0, -1),
-                    '<' => (-1, 0),
-                    '>' => (1, 0),
-                    'v' => (0, 1),
-                    _ => panic!("should not be possible due to surrounding if check"),
-                };
-
+            let direction = match c {
+                '^' => Some((0, -1)),
+                '<' => Some((-1, 0)),
+                '>' => Some((1, 0)),
+                'v' => Some((0, 1)),
+                _ => None,
+            };
+
+            if let Some((dx, dy)) = direction {
                 let mine_cart = MineCart::new(j, i, dx, dy);
                 mine_carts.push(mine_cart);
             }
@@ -258,9 +282,9 @@ fn parse_input(input: &str) -> Result<(Vec<Vec<Space>>, Vec<MineCart>), SimpleEr
     Ok((grid, mine_carts))
 }
 
-pub(crate) fn solve(input: &str) -> Result<(Point, Point), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub(crate) fn solve(input: &str) -> Result<(Point, Point), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -281,4 +305,11 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(Point::new(6, 4)), solve_part_2(SAMPLE_INPUT_2));
     }
+
+    #[test]
+    fn test_broken_track() {
+        assert!(solve_part_1("->-- <-").is_err());
+        assert!(solve_part_1("-").is_err());
+        assert!(solve_part_2("->-<-").is_err());
+    }
 }
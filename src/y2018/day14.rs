@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2018/day/14>
 
 use crate::SimpleError;
-use std::error::Error;
 
 const INITIAL_STATE: [u32; 2] = [3, 7];
 
@@ -43,6 +42,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
                 .ok_or_else(|| SimpleError::new(format!("not a digit: {c}")))
         })
         .collect::<Result<_, _>>()?;
+    if target_sequence.is_empty() {
+        return Err(SimpleError::new(String::from("input should not be empty")));
+    }
 
     let mut list = Vec::from(INITIAL_STATE);
 
@@ -78,9 +80,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
     }
 }
 
-pub fn solve(input: &str) -> Result<(String, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -7,7 +7,6 @@ use std::cell::RefCell;
 use std::cmp;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
-use std::error::Error;
 use std::rc::Rc;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -129,13 +128,13 @@ impl Clone for SimulationInput {
 }
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let simulation_input = parse_input(input)?;
+    let simulation_input = crate::timing::parse(|| parse_input(input))?;
 
     let SimulationOutput {
         final_full_round,
         goblins,
         elves,
-    } = run_combat_simulation(simulation_input);
+    } = run_combat_simulation(simulation_input)?;
 
     if goblins.is_empty() {
         return Ok(compute_score(final_full_round, &elves));
@@ -149,7 +148,7 @@ fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let simulation_input = parse_input(input)?;
+    let simulation_input = crate::timing::parse(|| parse_input(input))?;
 
     for elf_attack_power in 4.. {
         for elf in &simulation_input.elves {
@@ -160,17 +159,19 @@ fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
             final_full_round,
             elves,
             ..
-        } = run_combat_simulation(simulation_input.clone());
+        } = run_combat_simulation(simulation_input.clone())?;
 
         if elves.len() == simulation_input.elves.len() {
             return Ok(compute_score(final_full_round, &elves));
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
-fn run_combat_simulation(input: SimulationInput) -> SimulationOutput {
+fn run_combat_simulation(input: SimulationInput) -> Result<SimulationOutput, SimpleError> {
     let SimulationInput {
         mut map,
         mut goblins,
@@ -185,6 +186,7 @@ fn run_combat_simulation(input: SimulationInput) -> SimulationOutput {
             .collect();
         all_warriors.sort_by_key(|warrior| warrior.borrow().position);
 
+        let mut any_acted = false;
         for warrior in &all_warriors {
             // Check if killed earlier in the round
             if warrior.borrow().hit_points <= 0 {
@@ -196,17 +198,18 @@ fn run_combat_simulation(input: SimulationInput) -> SimulationOutput {
             if (warrior_race == WarriorRace::Goblin && elves.is_empty())
                 || (warrior_race == WarriorRace::Elf && goblins.is_empty())
             {
-                return SimulationOutput {
+                return Ok(SimulationOutput {
                     final_full_round: round - 1,
                     goblins,
                     elves,
-                };
+                });
             }
 
             // Move if not adjacent to an enemy and a target is reachable
             if let Some(move_target) = find_move_target(&map, warrior) {
                 let warrior_position = warrior.borrow().position;
                 if move_target != warrior_position {
+                    any_acted = true;
                     map[warrior_position.y][warrior_position.x] = Space::Empty;
                     map[move_target.y][move_target.x] = Space::Warrior(Rc::clone(warrior));
 
@@ -225,6 +228,7 @@ fn run_combat_simulation(input: SimulationInput) -> SimulationOutput {
                     _ => panic!("attack target does not contain a warrior"),
                 };
 
+                any_acted = true;
                 other_warrior.borrow_mut().hit_points -= warrior.attack_power;
 
                 // If enemy is dead, remove them from the simulation
@@ -243,9 +247,18 @@ fn run_combat_simulation(input: SimulationInput) -> SimulationOutput {
                 }
             }
         }
+
+        // Nothing will ever change if no one could reach an enemy this round
+        if !any_acted {
+            return Err(SimpleError::unsupported_input(String::from(
+                "combat never ends because no warrior can reach an enemy",
+            )));
+        }
     }
 
-    panic!("simulation did not terminate organically");
+    Err(SimpleError::unsupported_input(String::from(
+        "combat did not end",
+    )))
 }
 
 fn find_move_target(map: &Vec<Vec<Space>>, warrior: &Rc<RefCell<Warrior>>) -> Option<Point> {
@@ -390,7 +403,29 @@ fn parse_input(input: &str) -> Result<SimulationInput, SimpleError> {
         return Err(SimpleError::new(String::from("input has no lines")));
     }
 
-    let mut map = vec![vec![Space::Empty; lines[0].len()]; lines.len()];
+    // Searches look at all four neighbors of open squares without bounds checks
+    let cols = lines[0].len();
+    let last_row = lines.len() - 1;
+    for (i, line) in lines.iter().enumerate() {
+        if line.len() != cols {
+            return Err(SimpleError::new(String::from(
+                "lines are not all the same length",
+            )));
+        }
+
+        let enclosed = if i == 0 || i == last_row {
+            line.chars().all(|c| c == '#')
+        } else {
+            line.starts_with('#') && line.ends_with('#')
+        };
+        if !enclosed {
+            return Err(SimpleError::new(String::from(
+                "cave should be surrounded by walls",
+            )));
+        }
+    }
+
+    let mut map = vec![vec![Space::Empty; cols]; lines.len()];
     let mut goblins = Vec::new();
     let mut elves = Vec::new();
     for (i, line) in lines.iter().enumerate() {
@@ -424,9 +459,9 @@ fn parse_input(input: &str) -> Result<SimulationInput, SimpleError> {
     })
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -460,4 +495,11 @@ mod tests {
         assert_eq!(Ok(6474), solve_part_2(SAMPLE_INPUT_5));
         assert_eq!(Ok(1140), solve_part_2(SAMPLE_INPUT_6));
     }
+
+    #[test]
+    fn test_invalid_caves() {
+        assert!(solve_part_1("#####\n#G#E#\n#####").is_err());
+        assert!(solve_part_1("#####\n#G.E.\n#####").is_err());
+        assert!(solve_part_1("#####\n#G.E#\n####").is_err());
+    }
 }
@@ -5,7 +5,8 @@
 use crate::y2018::chronodevice::ChronoOperation;
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
+
+const REGISTER_COUNT: usize = 4;
 
 #[derive(Debug, Clone)]
 struct OpTest {
@@ -34,8 +35,14 @@ impl OpTest {
             )));
         }
 
-        let before = parse_registers(&lines[0]["Before: ".len()..])?;
-        let after = parse_registers(&lines[2]["After:  ".len()..])?;
+        let before = lines[0]
+            .strip_prefix("Before: ")
+            .ok_or_else(|| SimpleError::new(format!("expected Before: line: {}", lines[0])))?;
+        let after = lines[2]
+            .strip_prefix("After:  ")
+            .ok_or_else(|| SimpleError::new(format!("expected After: line: {}", lines[2])))?;
+        let before = parse_registers(before)?;
+        let after = parse_registers(after)?;
 
         let split: Vec<_> = lines[1].split(' ').collect();
         if split.len() != 4 {
@@ -49,6 +56,7 @@ impl OpTest {
         let a = split[1].parse()?;
         let b = split[2].parse()?;
         let c = split[3].parse()?;
+        check_opcode(lines[1], opcode)?;
 
         Ok(Self {
             before,
@@ -62,7 +70,7 @@ impl OpTest {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let (op_tests, _) = parse_input(input)?;
+    let (op_tests, _) = crate::timing::parse(|| parse_input(input))?;
 
     let result = op_tests
         .into_iter()
@@ -87,25 +95,35 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let (op_tests, test_program) = parse_input(input)?;
+    let (op_tests, test_program) = crate::timing::parse(|| parse_input(input))?;
 
-    let opcode_mapping = solve_for_opcodes(&op_tests);
+    let opcode_mapping = solve_for_opcodes(&op_tests)?;
 
     let mut registers = [0, 0, 0, 0];
     for instruction in &test_program {
         let op = opcode_mapping[instruction.opcode];
+        op.check_operands(REGISTER_COUNT, instruction.a, instruction.b, instruction.c)?;
         registers[instruction.c] = op.execute(&registers, instruction.a, instruction.b);
     }
 
     Ok(registers[0])
 }
 
-fn solve_for_opcodes(op_tests: &[OpTest]) -> Vec<ChronoOperation> {
+fn check_opcode(line: &str, opcode: usize) -> Result<(), SimpleError> {
+    if opcode >= ChronoOperation::ALL.len() {
+        return Err(SimpleError::new(format!("invalid opcode in line: {line}")));
+    }
+
+    Ok(())
+}
+
+fn solve_for_opcodes(op_tests: &[OpTest]) -> Result<Vec<ChronoOperation>, SimpleError> {
     let mut opcode_to_operation: Vec<Option<ChronoOperation>> =
         vec![None; ChronoOperation::ALL.len()];
 
     let mut found_operations = HashSet::new();
     while found_operations.len() < ChronoOperation::ALL.len() {
+        let found_before = found_operations.len();
         for op_test in op_tests {
             let can_produce_ops: Vec<_> = ChronoOperation::ALL
                 .iter()
@@ -126,11 +144,23 @@ fn solve_for_opcodes(op_tests: &[OpTest]) -> Vec<ChronoOperation> {
                 found_operations.insert(can_produce_ops[0]);
             }
         }
+
+        if found_operations.len() == found_before {
+            return Err(SimpleError::unsupported_input(String::from(
+                "samples do not narrow down every opcode",
+            )));
+        }
     }
 
     opcode_to_operation
         .into_iter()
-        .map(Option::unwrap)
+        .map(|op| {
+            op.ok_or_else(|| {
+                SimpleError::unsupported_input(String::from(
+                    "samples match one operation to more than one opcode",
+                ))
+            })
+        })
         .collect()
 }
 
@@ -154,12 +184,15 @@ fn parse_input(input: &str) -> Result<(Vec<OpTest>, Vec<TestInstruction>), Simpl
                 )));
             }
 
-            Ok(TestInstruction {
+            let instruction = TestInstruction {
                 opcode: split[0].parse()?,
                 a: split[1].parse()?,
                 b: split[2].parse()?,
                 c: split[3].parse()?,
-            })
+            };
+            check_opcode(line, instruction.opcode)?;
+
+            Ok(instruction)
         })
         .collect::<Result<_, _>>()?;
 
@@ -179,7 +212,12 @@ fn find_triple_blank_line_index(lines: &[&str]) -> Result<usize, SimpleError> {
 }
 
 fn parse_registers(s: &str) -> Result<[u64; 4], SimpleError> {
-    let numbers: Vec<_> = s[1..s.len() - 1].split(", ").collect();
+    let numbers: Vec<_> = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| SimpleError::new(format!("registers should be in brackets: {s}")))?
+        .split(", ")
+        .collect();
     if numbers.len() != 4 {
         return Err(SimpleError::new(format!(
             "string does not split into 4 numbers: {s}"
@@ -194,9 +232,9 @@ fn parse_registers(s: &str) -> Result<[u64; 4], SimpleError> {
     Ok([numbers[0], numbers[1], numbers[2], numbers[3]])
 }
 
-pub fn solve(input: &str) -> Result<(usize, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -211,4 +249,14 @@ mod tests {
     fn test_sample_input_part_1() {
         assert_eq!(Ok(1), solve_part_1(SAMPLE_INPUT));
     }
+
+    #[test]
+    fn test_operands_out_of_range() {
+        // Operations that would read register 7 can't match the sample
+        assert_eq!(
+            Ok(0),
+            solve_part_1(&SAMPLE_INPUT.replace("9 2 1 2", "9 7 1 2"))
+        );
+        assert!(solve_part_1(&SAMPLE_INPUT.replace("9 2 1 2", "16 2 1 2")).is_err());
+    }
 }
@@ -3,7 +3,9 @@
 //! <https://adventofcode.com/2018/day/17>
 
 use crate::SimpleError;
-use std::error::Error;
+use std::iter;
+
+const SPRING_X: usize = 500;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Space {
@@ -40,10 +42,10 @@ impl ClayVein {
 }
 
 fn solve_both_parts(input: &str) -> Result<(usize, usize), SimpleError> {
-    let clay_veins = parse_input(input)?;
+    let clay_veins = crate::timing::parse(|| parse_input(input))?;
 
     let mut map = build_map(&clay_veins);
-    flood(&mut map, 500, 0);
+    flood(&mut map, SPRING_X, 0);
 
     let min_y = clay_veins
         .iter()
@@ -52,7 +54,7 @@ fn solve_both_parts(input: &str) -> Result<(usize, usize), SimpleError> {
             ClayVein::Horizontal { y, .. } => y as usize,
         })
         .min()
-        .unwrap();
+        .unwrap_or(0);
 
     let mut water_count = 0;
     let mut resting_water_count = 0;
@@ -149,8 +151,9 @@ fn build_map(clay_veins: &[ClayVein]) -> Vec<Vec<Space>> {
             ClayVein::Vertical { x, .. } => x,
             ClayVein::Horizontal { x_max, .. } => x_max,
         })
+        .chain(iter::once(SPRING_X as u32))
         .max()
-        .unwrap();
+        .unwrap_or_default();
 
     let max_y = clay_veins
         .iter()
@@ -159,7 +162,7 @@ fn build_map(clay_veins: &[ClayVein]) -> Vec<Vec<Space>> {
             ClayVein::Horizontal { y, .. } => y,
         })
         .max()
-        .unwrap();
+        .unwrap_or_default();
 
     // Leave extra space at the x end to allow water to run off to the right of the rightmost clay
     let mut map = vec![vec![Space::Empty; max_x as usize + 2]; max_y as usize + 1];
@@ -182,49 +185,67 @@ fn build_map(clay_veins: &[ClayVein]) -> Vec<Vec<Space>> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<ClayVein>, SimpleError> {
-    input
+    let clay_veins: Vec<_> = input
         .lines()
         .map(|line| {
             let (l, r) = line
                 .split_once(", ")
                 .ok_or_else(|| SimpleError::new(format!("invalid line format: {line}")))?;
 
-            match &line[..2] {
-                "x=" => {
-                    let x = &l[2..];
-
-                    let (y_min, y_max) = r[2..].split_once("..").ok_or_else(|| {
+            if let Some(x) = l.strip_prefix("x=") {
+                let (y_min, y_max) = r
+                    .strip_prefix("y=")
+                    .and_then(|r| r.split_once(".."))
+                    .ok_or_else(|| {
                         SimpleError::new(format!("invalid y range format in line: {line}"))
                     })?;
 
-                    Ok(ClayVein::new_vertical(
-                        x.parse()?,
-                        y_min.parse()?,
-                        y_max.parse()?,
-                    ))
-                }
-                "y=" => {
-                    let y = &l[2..];
-
-                    let (x_min, x_max) = r[2..].split_once("..").ok_or_else(|| {
+                Ok(ClayVein::new_vertical(
+                    x.parse()?,
+                    y_min.parse()?,
+                    y_max.parse()?,
+                ))
+            } else if let Some(y) = l.strip_prefix("y=") {
+                let (x_min, x_max) = r
+                    .strip_prefix("x=")
+                    .and_then(|r| r.split_once(".."))
+                    .ok_or_else(|| {
                         SimpleError::new(format!("invalid x range format in line: {line}"))
                     })?;
 
-                    Ok(ClayVein::new_horizontal(
-                        x_min.parse()?,
-                        x_max.parse()?,
-                        y.parse()?,
-                    ))
-                }
-                _ => Err(SimpleError::new(format!(
+                Ok(ClayVein::new_horizontal(
+                    x_min.parse()?,
+                    x_max.parse()?,
+                    y.parse()?,
+                ))
+            } else {
+                Err(SimpleError::new(format!(
                     "expected line to start with 'x=' or 'y=': {line}"
-                ))),
+                )))
             }
         })
-        .collect()
+        .collect::<Result<_, SimpleError>>()?;
+
+    if clay_veins.is_empty() {
+        return Err(SimpleError::new(String::from("input should not be empty")));
+    }
+
+    // Water spreading sideways assumes there's always a column to the left of the clay, and the
+    // spring sits in the top row
+    let touches_edge = clay_veins.iter().any(|&clay_vein| match clay_vein {
+        ClayVein::Vertical { x, y_min, .. } => x == 0 || y_min == 0,
+        ClayVein::Horizontal { x_min, y, .. } => x_min == 0 || y == 0,
+    });
+    if touches_edge {
+        return Err(SimpleError::new(String::from(
+            "clay should not be in the top row or the leftmost column",
+        )));
+    }
+
+    Ok(clay_veins)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
@@ -240,4 +261,12 @@ mod tests {
     fn test_sample_input() {
         assert_eq!(Ok((57, 29)), solve_both_parts(SAMPLE_INPUT));
     }
+
+    #[test]
+    fn test_clay_away_from_spring() {
+        assert_eq!(Ok((3, 0)), solve_both_parts("x=10, y=1..3"));
+        assert!(solve_both_parts("x=0, y=1..3").is_err());
+        assert!(solve_both_parts("y=0, x=1..3").is_err());
+        assert!(solve_both_parts("").is_err());
+    }
 }
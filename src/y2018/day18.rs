@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 use std::iter;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -25,7 +24,7 @@ impl Space {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let mut collection_area = parse_input(input)?;
+    let mut collection_area = crate::timing::parse(|| parse_input(input))?;
 
     for _ in 0..10 {
         collection_area = simulate_iteration(&collection_area);
@@ -35,7 +34,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let mut collection_area = parse_input(input)?;
+    let mut collection_area = crate::timing::parse(|| parse_input(input))?;
 
     let mut past_collection_areas: HashMap<_, _> =
         iter::once((collection_area.clone(), 0)).collect();
@@ -54,7 +53,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         past_collection_areas.insert(collection_area.clone(), i);
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn compute_score(collection_area: &Vec<Vec<Space>>) -> usize {
@@ -147,6 +148,12 @@ fn parse_input(input: &str) -> Result<Vec<Vec<Space>>, SimpleError> {
 
     let mut collection_area = vec![vec![Space::Open; lines[0].len()]; lines.len()];
     for (i, line) in lines.iter().enumerate() {
+        if line.len() != lines[0].len() {
+            return Err(SimpleError::new(String::from(
+                "lines are not all the same length",
+            )));
+        }
+
         for (j, c) in line.chars().enumerate() {
             collection_area[i][j] = match c {
                 '.' => Space::Open,
@@ -160,9 +167,9 @@ fn parse_input(input: &str) -> Result<Vec<Vec<Space>>, SimpleError> {
     Ok(collection_area)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
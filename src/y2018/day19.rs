@@ -4,12 +4,13 @@
 
 use crate::y2018::chronodevice::ChronoInstruction;
 use crate::SimpleError;
-use std::error::Error;
+
+const REGISTER_COUNT: usize = 6;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let (ip, instructions) = parse_input(input)?;
+    let (ip, instructions) = crate::timing::parse(|| parse_input(input))?;
 
-    let mut registers = [0; 6];
+    let mut registers = [0; REGISTER_COUNT];
 
     let mut pc = 0;
     while pc < instructions.len() {
@@ -29,9 +30,9 @@ fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
 
 // I have no idea how well this solution generalizes to other people's inputs
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let (ip, instructions) = parse_input(input)?;
+    let (ip, instructions) = crate::timing::parse(|| parse_input(input))?;
 
-    let mut registers = [0; 6];
+    let mut registers = [0; REGISTER_COUNT];
     registers[0] = 1;
 
     let mut pc = 0;
@@ -39,7 +40,11 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
     while pc != 1 {
         registers[ip] = pc as u64;
 
-        let instruction = &instructions[pc];
+        let instruction = instructions.get(pc).ok_or_else(|| {
+            SimpleError::unsupported_input(String::from(
+                "program halted before jumping back to the start of its loop",
+            ))
+        })?;
         registers[instruction.c] = instruction
             .op
             .execute(&registers, instruction.a, instruction.b);
@@ -67,27 +72,36 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
 fn parse_input(input: &str) -> Result<(usize, Vec<ChronoInstruction>), SimpleError> {
     let first_line = crate::read_single_line(input)?;
 
-    if !first_line.starts_with("#ip ") {
+    let ip: usize = first_line
+        .strip_prefix("#ip ")
+        .ok_or_else(|| {
+            SimpleError::new(format!(
+                "expected '#ip ' prefix in first line: {first_line}"
+            ))
+        })?
+        .parse()?;
+    if ip >= REGISTER_COUNT {
         return Err(SimpleError::new(format!(
-            "expected '#ip ' prefix in first line: {first_line}"
+            "instruction pointer register {ip} doesn't exist"
         )));
     }
 
-    let ip = first_line[4..].parse()?;
-
-    let instructions: Result<_, _> = input
+    let instructions = input
         .lines()
+        .enumerate()
         .skip(1)
-        .map(ChronoInstruction::from_line)
-        .collect();
-    let instructions = instructions?;
+        .map(|(i, line)| {
+            ChronoInstruction::from_line(line, REGISTER_COUNT)
+                .map_err(|err| err.at_line(i + 1, line))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok((ip, instructions))
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::error::Error;
 use std::hash::Hash;
 use std::iter;
 use std::ops::Add;
@@ -222,7 +221,13 @@ impl Add<Direction> for Point {
 
 fn solve_both_parts(input: &str) -> Result<(usize, usize), SimpleError> {
     let regex = crate::read_single_line(input)?;
-    let regex: Regex = regex[1..regex.len() - 1].parse()?;
+    let regex: Regex = regex
+        .strip_prefix('^')
+        .and_then(|regex| regex.strip_suffix('$'))
+        .ok_or_else(|| {
+            SimpleError::new(format!("regex should start with ^ and end with $: {regex}"))
+        })?
+        .parse()?;
 
     let mut map: HashMap<_, _> = iter::once((Point::new(0, 0), DirectionSet::new())).collect();
 
@@ -313,7 +318,7 @@ fn search_rooms(map: &HashMap<Point, DirectionSet>, distance_threshold: usize) -
     (last_distance, num_distant_rooms)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
@@ -4,15 +4,16 @@
 
 use crate::y2018::chronodevice::{ChronoInstruction, ChronoOperation};
 use crate::SimpleError;
+
+const REGISTER_COUNT: usize = 6;
 use std::collections::HashSet;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let (ip, program) = parse_input(input)?;
+    let (ip, program) = crate::timing::parse(|| parse_input(input))?;
 
     let target_register = determine_target_register(&program)?;
 
-    let mut registers = [0; 6];
+    let mut registers = [0; REGISTER_COUNT];
     let mut pc = 0;
     while pc < program.len() {
         if pc == program.len() - 3 {
@@ -30,15 +31,17 @@ fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
         pc += 1;
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let (ip, program) = parse_input(input)?;
+    let (ip, program) = crate::timing::parse(|| parse_input(input))?;
 
     let target_register = determine_target_register(&program)?;
 
-    let mut registers = [0; 6];
+    let mut registers = [0; REGISTER_COUNT];
     let mut pc = 0;
     let mut seen_values = HashSet::new();
     let mut last_seen_value = 0;
@@ -61,26 +64,51 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
         pc += 1;
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn parse_input(input: &str) -> Result<(usize, Vec<ChronoInstruction>), SimpleError> {
     let first_line = crate::read_single_line(input)?;
-    let ip: usize = first_line[4..].parse()?;
+    let ip: usize = first_line
+        .strip_prefix("#ip ")
+        .ok_or_else(|| {
+            SimpleError::new(format!(
+                "expected '#ip ' prefix in first line: {first_line}"
+            ))
+        })?
+        .parse()?;
+    if ip >= REGISTER_COUNT {
+        return Err(SimpleError::new(format!(
+            "instruction pointer register {ip} doesn't exist"
+        )));
+    }
 
-    let program: Vec<_> = input
+    let program = input
         .lines()
+        .enumerate()
         .skip(1)
-        .map(ChronoInstruction::from_line)
-        .collect::<Result<_, _>>()?;
+        .map(|(i, line)| {
+            ChronoInstruction::from_line(line, REGISTER_COUNT)
+                .map_err(|err| err.at_line(i + 1, line))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok((ip, program))
 }
 
 fn determine_target_register(program: &[ChronoInstruction]) -> Result<usize, SimpleError> {
+    if program.len() < 3 {
+        return Err(SimpleError::unsupported_input(format!(
+            "expected at least 3 instructions, got {}",
+            program.len()
+        )));
+    }
+
     let check_instruction = program[program.len() - 3];
     if check_instruction.op != ChronoOperation::EqualRegisterRegister {
-        return Err(SimpleError::new(format!(
+        return Err(SimpleError::unsupported_input(format!(
             "expected third-to-last instruction to be eqrr: {check_instruction:?}"
         )));
     }
@@ -89,7 +117,7 @@ fn determine_target_register(program: &[ChronoInstruction]) -> Result<usize, Sim
         (0, b) => b as usize,
         (a, 0) => a as usize,
         _ => {
-            return Err(SimpleError::new(format!(
+            return Err(SimpleError::unsupported_input(format!(
                 "expected either a or b to be 0 in instruction: {check_instruction:?}"
             )))
         }
@@ -98,9 +126,9 @@ fn determine_target_register(program: &[ChronoInstruction]) -> Result<usize, Sim
     Ok(target_register)
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
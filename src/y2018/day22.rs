@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct Point {
@@ -101,7 +100,7 @@ struct VisitedKey {
 const EROSION_LEVEL_MODULO: u64 = 20183;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let (depth, target) = parse_input(input)?;
+    let (depth, target) = crate::timing::parse(|| parse_input(input))?;
 
     let geologic_indices = build_geologic_index_map(depth, target);
 
@@ -128,13 +127,14 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let (depth, target) = parse_input(input)?;
+    let (depth, target) = crate::timing::parse(|| parse_input(input))?;
 
     let geologic_indices = build_geologic_index_map(depth, target);
 
     let shortest_path = find_shortest_path_to_target(geologic_indices, depth, target);
 
-    shortest_path.ok_or_else(|| SimpleError::new(String::from("no path found to target")))
+    shortest_path
+        .ok_or_else(|| SimpleError::unsupported_input(String::from("no path found to target")))
 }
 
 fn build_geologic_index_map(depth: u64, target: Point) -> Vec<Vec<u64>> {
@@ -268,16 +268,22 @@ fn parse_input(input: &str) -> Result<(u64, Point), SimpleError> {
         .nth(1)
         .ok_or_else(|| SimpleError::new(String::from("input should have two lines")))?;
 
-    let depth = first_line["depth: ".len()..].parse()?;
-
-    let (target_x, target_y) =
-        second_line["target: ".len()..]
-            .split_once(',')
-            .ok_or_else(|| {
-                SimpleError::new(format!(
-                    "target string should contain one comma: {second_line}"
-                ))
-            })?;
+    let depth: u64 = first_line
+        .strip_prefix("depth: ")
+        .ok_or_else(|| SimpleError::new(format!("expected depth line: {first_line}")))?
+        .parse()?;
+    // Depth is only ever added before taking the modulo, and reducing it keeps products in range
+    let depth = depth % EROSION_LEVEL_MODULO;
+
+    let (target_x, target_y) = second_line
+        .strip_prefix("target: ")
+        .ok_or_else(|| SimpleError::new(format!("expected target line: {second_line}")))?
+        .split_once(',')
+        .ok_or_else(|| {
+            SimpleError::new(format!(
+                "target string should contain one comma: {second_line}"
+            ))
+        })?;
 
     let target_x = target_x.parse()?;
     let target_y = target_y.parse()?;
@@ -286,9 +292,9 @@ fn parse_input(input: &str) -> Result<(u64, Point), SimpleError> {
     Ok((depth, target))
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
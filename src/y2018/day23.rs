@@ -6,7 +6,6 @@ use crate::SimpleError;
 use std::cmp;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct Point {
@@ -39,7 +38,10 @@ impl Nanobot {
             .split_once(", ")
             .ok_or_else(|| SimpleError::new(format!("line has no ', ': {line}")))?;
 
-        let position = &position[5..position.len() - 1];
+        let position = position
+            .strip_prefix("pos=<")
+            .and_then(|position| position.strip_suffix('>'))
+            .ok_or_else(|| SimpleError::new(format!("invalid position in line: {line}")))?;
         let split: Vec<_> = position.split(',').collect();
         if split.len() != 3 {
             return Err(SimpleError::new(format!(
@@ -51,7 +53,10 @@ impl Nanobot {
         let y = split[1].parse()?;
         let z = split[2].parse()?;
 
-        let radius = radius[2..].parse()?;
+        let radius = radius
+            .strip_prefix("r=")
+            .ok_or_else(|| SimpleError::new(format!("invalid radius in line: {line}")))?
+            .parse()?;
 
         Ok(Self {
             position: Point::new(x, y, z),
@@ -162,7 +167,7 @@ impl Ord for HeapEntry {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let nanobots = parse_input(input)?;
+    let nanobots = crate::timing::parse(|| parse_input(input))?;
 
     let strongest_nanobot = nanobots
         .iter()
@@ -181,7 +186,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
-    let nanobots = parse_input(input)?;
+    let nanobots = crate::timing::parse(|| parse_input(input))?;
 
     let (min_x, max_x, min_y, max_y, min_z, max_z) = nanobots.iter().fold(
         (i64::MAX, i64::MIN, i64::MAX, i64::MIN, i64::MAX, i64::MIN),
@@ -259,9 +264,9 @@ fn parse_input(input: &str) -> Result<Vec<Nanobot>, SimpleError> {
     input.lines().map(Nanobot::from_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2018/day/24>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -70,7 +69,7 @@ impl UnitGroup {
 }
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let (immune_system, infection) = parse_input(input)?;
+    let (immune_system, infection) = crate::timing::parse(|| parse_input(input))?;
 
     let (immune_system, infection) = run_simulation(immune_system, infection);
 
@@ -89,7 +88,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let (immune_system, infection) = parse_input(input)?;
+    let (immune_system, infection) = crate::timing::parse(|| parse_input(input))?;
 
     for boost in 1.. {
         let mut boosted_immune_system = immune_system.clone();
@@ -109,7 +108,9 @@ fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn run_simulation(
@@ -297,24 +298,32 @@ fn parse_unit_group(lines: &[&str], unit_type: UnitType) -> Result<Vec<UnitGroup
         .enumerate()
         .map(|(i, &line)| {
             let split: Vec<_> = line.splitn(8, ' ').collect();
+            if split.len() != 8 {
+                return Err(SimpleError::new(format!("malformed line: {line}")));
+            }
 
             let units = split[0].parse()?;
             let hit_points = split[4].parse()?;
+            if hit_points == 0 {
+                return Err(SimpleError::new(format!(
+                    "units should have at least 1 hit point: {line}"
+                )));
+            }
 
             let mut weaknesses = Vec::new();
             let mut immunities = Vec::new();
             let mut rest = split[7];
-            if rest.starts_with('(') {
-                let close_paren_index = rest.chars().position(|c| c == ')').ok_or_else(|| {
-                    SimpleError::new(format!("line has open paren but no close paren: {line}"))
-                })?;
+            if let Some(after_open_paren) = rest.strip_prefix('(') {
+                let (resistances, after_close_paren) =
+                    after_open_paren.split_once(") ").ok_or_else(|| {
+                        SimpleError::new(format!("line has open paren but no close paren: {line}"))
+                    })?;
 
-                let (parsed_weaknesses, parsed_immunities) =
-                    parse_resistances(&rest[1..close_paren_index])?;
+                let (parsed_weaknesses, parsed_immunities) = parse_resistances(resistances)?;
 
                 weaknesses = parsed_weaknesses;
                 immunities = parsed_immunities;
-                rest = &rest[close_paren_index + 2..];
+                rest = after_close_paren;
             }
 
             let rest_split: Vec<_> = rest.split(' ').collect();
@@ -352,12 +361,16 @@ fn parse_resistances(s: &str) -> Result<(Vec<AttackType>, Vec<AttackType>), Simp
     let mut immunities = Vec::new();
     for &s in &resistance_strings {
         let split: Vec<_> = s.splitn(3, ' ').collect();
-        let attack_types: Vec<_> = split[2]
+        let [resistance, "to", attack_types] = split.as_slice() else {
+            return Err(SimpleError::new(format!("invalid resistance string: {s}")));
+        };
+
+        let attack_types: Vec<_> = attack_types
             .split(", ")
             .map(|s| s.parse::<AttackType>())
             .collect::<Result<_, _>>()?;
 
-        match split[0] {
+        match *resistance {
             "weak" => weaknesses = attack_types,
             "immune" => immunities = attack_types,
             _ => return Err(SimpleError::new(format!("expected weak/immune: {s}"))),
@@ -367,9 +380,9 @@ fn parse_resistances(s: &str) -> Result<(Vec<AttackType>, Vec<AttackType>), Simp
     Ok((weaknesses, immunities))
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Point4D {
@@ -66,7 +65,7 @@ impl DisjointSet {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let points = parse_input(input)?;
+    let points = crate::timing::parse(|| parse_input(input))?;
 
     let mut disjoint_set = DisjointSet::new(points.len());
 
@@ -103,8 +102,8 @@ fn parse_input(input: &str) -> Result<Vec<Point4D>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
+pub fn solve(input: &str) -> Result<(usize, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
 
     Ok((solution1, String::new()))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2018/day/3>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
 struct Rectangle {
@@ -14,7 +13,7 @@ struct Rectangle {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let rectangles = parse_input(input)?;
+    let rectangles = crate::timing::parse(|| parse_input(input))?;
 
     let square = compute_tile_counts(&rectangles);
 
@@ -27,7 +26,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let rectangles = parse_input(input)?;
+    let rectangles = crate::timing::parse(|| parse_input(input))?;
 
     let square = compute_tile_counts(&rectangles);
 
@@ -47,7 +46,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn compute_tile_counts(rectangles: &Vec<Rectangle>) -> Vec<Vec<i32>> {
@@ -93,9 +94,9 @@ fn parse_input(input: &str) -> Result<Vec<Rectangle>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
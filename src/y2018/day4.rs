@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
 enum GuardLogEvent {
@@ -20,14 +19,14 @@ struct LogLine {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let logs = parse_input(input)?;
+    let logs = crate::timing::parse(|| parse_input(input))?;
 
-    let minutes_asleep = compute_guard_id_to_minutes_asleep(&logs);
+    let minutes_asleep = compute_guard_id_to_minutes_asleep(&logs)?;
 
     let (laziest_guard_id, guard_minutes_asleep) = minutes_asleep
         .into_iter()
         .max_by_key(|(_, guard_minutes_asleep)| guard_minutes_asleep.iter().copied().sum::<usize>())
-        .unwrap();
+        .ok_or_else(|| SimpleError::new(String::from("no guards in input")))?;
 
     let laziest_minute = guard_minutes_asleep
         .into_iter()
@@ -40,9 +39,9 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let logs = parse_input(input)?;
+    let logs = crate::timing::parse(|| parse_input(input))?;
 
-    let minutes_asleep = compute_guard_id_to_minutes_asleep(&logs);
+    let minutes_asleep = compute_guard_id_to_minutes_asleep(&logs)?;
 
     let (laziest_guard_id, laziest_minute) = minutes_asleep
         .into_iter()
@@ -55,37 +54,54 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         })
         .max_by_key(|&(_, _, count)| count)
         .map(|(guard_id, minute, _)| (guard_id, minute))
-        .unwrap();
+        .ok_or_else(|| SimpleError::new(String::from("no guards in input")))?;
 
     Ok(laziest_guard_id * laziest_minute)
 }
 
-fn compute_guard_id_to_minutes_asleep(logs: &Vec<LogLine>) -> HashMap<usize, Vec<usize>> {
+fn compute_guard_id_to_minutes_asleep(
+    logs: &Vec<LogLine>,
+) -> Result<HashMap<usize, Vec<usize>>, SimpleError> {
     let mut minutes_asleep = HashMap::new();
 
-    let mut current_guard_id = 0;
-    let mut fell_asleep_minute = 0;
+    let mut current_guard_id = None;
+    let mut fell_asleep_minute = None;
     for &log in logs {
         match log.event {
             GuardLogEvent::BeginsShift(guard_id) => {
-                current_guard_id = guard_id;
+                current_guard_id = Some(guard_id);
+                fell_asleep_minute = None;
                 minutes_asleep
                     .entry(guard_id)
                     .or_insert_with(|| vec![0; 60]);
             }
             GuardLogEvent::FallsAsleep => {
-                fell_asleep_minute = log.minute;
+                fell_asleep_minute = Some(log.minute);
             }
             GuardLogEvent::WakesUp => {
-                let guard_minutes_asleep = minutes_asleep.get_mut(&current_guard_id).unwrap();
-                for asleep_count in &mut guard_minutes_asleep[fell_asleep_minute..log.minute] {
+                let guard_minutes_asleep = current_guard_id
+                    .and_then(|guard_id| minutes_asleep.get_mut(&guard_id))
+                    .ok_or_else(|| {
+                        SimpleError::new(String::from("guard wakes up before any shift begins"))
+                    })?;
+                let asleep_minutes = fell_asleep_minute
+                    .take()
+                    .filter(|&fell_asleep_minute| fell_asleep_minute <= log.minute)
+                    .map(|fell_asleep_minute| fell_asleep_minute..log.minute)
+                    .ok_or_else(|| {
+                        SimpleError::new(format!(
+                            "guard wakes up at minute {} without falling asleep first",
+                            log.minute
+                        ))
+                    })?;
+                for asleep_count in &mut guard_minutes_asleep[asleep_minutes] {
                     *asleep_count += 1;
                 }
             }
         }
     }
 
-    minutes_asleep
+    Ok(minutes_asleep)
 }
 
 fn parse_input(input: &str) -> Result<Vec<LogLine>, SimpleError> {
@@ -100,12 +116,25 @@ fn parse_input(input: &str) -> Result<Vec<LogLine>, SimpleError> {
                 )));
             }
 
-            let minute: usize = line[15..17].parse()?;
+            let minute: usize = line
+                .get(15..17)
+                .ok_or_else(|| SimpleError::new(format!("invalid timestamp in line: {line}")))?
+                .parse()?;
+            if minute >= 60 {
+                return Err(SimpleError::new(format!("invalid minute in line: {line}")));
+            }
 
-            let rest_of_line = &line["[1518-00-00 00:00] ".len()..];
+            let rest_of_line = line
+                .get("[1518-00-00 00:00] ".len()..)
+                .ok_or_else(|| SimpleError::new(format!("invalid timestamp in line: {line}")))?;
             let split: Vec<_> = rest_of_line.split(' ').collect();
             let event = match split.as_slice() {
-                ["Guard", id, "begins", "shift"] => GuardLogEvent::BeginsShift(id[1..].parse()?),
+                ["Guard", id, "begins", "shift"] => {
+                    let id = id.strip_prefix('#').ok_or_else(|| {
+                        SimpleError::new(format!("invalid guard ID in line: {line}"))
+                    })?;
+                    GuardLogEvent::BeginsShift(id.parse()?)
+                }
                 ["falls", "asleep"] => GuardLogEvent::FallsAsleep,
                 ["wakes", "up"] => GuardLogEvent::WakesUp,
                 _ => return Err(SimpleError::new(format!("unknown event in line: {line}"))),
@@ -116,9 +145,9 @@ fn parse_input(input: &str) -> Result<Vec<LogLine>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -138,4 +167,16 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(4455), solve_part_2(SAMPLE_INPUT));
     }
+
+    #[test]
+    fn test_invalid_logs() {
+        assert!(solve_part_1("").is_err());
+        assert!(solve_part_1("[1518-11-01 00:05] wakes up").is_err());
+        assert!(solve_part_1(
+            "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] wakes up"
+        )
+        .is_err());
+        assert!(solve_part_1("[1518-11-01 00:00] Guard 10 begins shift").is_err());
+        assert!(solve_part_1("[1518-11-01 00:60] Guard #10 begins shift").is_err());
+    }
 }
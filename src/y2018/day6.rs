@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, Clone, Copy)]
 struct Point {
@@ -24,7 +23,7 @@ impl Point {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let points = parse_input(input)?;
+    let points = crate::timing::parse(|| parse_input(input))?;
 
     let (max_x, max_y) = get_maximums(&points);
 
@@ -83,13 +82,16 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    let max_region_count = count_by_region.into_values().max().unwrap();
+    let max_region_count = count_by_region
+        .into_values()
+        .max()
+        .ok_or_else(|| SimpleError::unsupported_input(String::from("every region is infinite")))?;
 
     Ok(max_region_count)
 }
 
 fn solve_part_2(input: &str, distance_limit: i32) -> Result<usize, SimpleError> {
-    let points = parse_input(input)?;
+    let points = crate::timing::parse(|| parse_input(input))?;
 
     let (max_x, max_y) = get_maximums(&points);
 
@@ -120,21 +122,34 @@ fn get_maximums(points: &[Point]) -> (i32, i32) {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Point>, SimpleError> {
-    input
+    let points: Vec<_> = input
         .lines()
         .map(|line| {
             let (x, y) = line
                 .split_once(", ")
                 .ok_or_else(|| SimpleError::new(format!("line has no ', ': {line}")))?;
 
-            Ok(Point::new(x.parse()?, y.parse()?))
+            let point = Point::new(x.parse()?, y.parse()?);
+            if point.x < 0 || point.y < 0 {
+                return Err(SimpleError::new(format!(
+                    "coordinates should not be negative: {line}"
+                )));
+            }
+
+            Ok(point)
         })
-        .collect()
+        .collect::<Result<_, SimpleError>>()?;
+
+    if points.is_empty() {
+        return Err(SimpleError::new(String::from("input should not be empty")));
+    }
+
+    Ok(points)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input, 10000)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, 10000))?;
 
     Ok((solution1, solution2))
 }
@@ -154,4 +169,11 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(16), solve_part_2(SAMPLE_INPUT, 32));
     }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(solve_part_1("").is_err());
+        assert!(solve_part_1("1, -1").is_err());
+        assert!(solve_part_1("1, 1\n2, 2").is_err());
+    }
 }
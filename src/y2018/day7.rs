@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 use std::iter;
 
 #[derive(Debug, Clone, Copy)]
@@ -23,7 +22,7 @@ impl Worker {
 }
 
 fn solve_part_1(input: &str) -> Result<String, SimpleError> {
-    let requirements = parse_input(input)?;
+    let requirements = crate::timing::parse(|| parse_input(input))?;
 
     let all_letters = get_all_letters(&requirements);
 
@@ -35,7 +34,7 @@ fn solve_part_1(input: &str) -> Result<String, SimpleError> {
 }
 
 fn solve_part_2(input: &str, workers: usize, add_sixty: bool) -> Result<u32, SimpleError> {
-    let requirements = parse_input(input)?;
+    let requirements = crate::timing::parse(|| parse_input(input))?;
 
     let all_letters = get_all_letters(&requirements);
 
@@ -187,7 +186,7 @@ fn topological_sort_iteration(
 }
 
 fn parse_input(input: &str) -> Result<Vec<(char, char)>, SimpleError> {
-    input
+    let requirements: Vec<_> = input
         .lines()
         .map(|line| {
             let split: Vec<_> = line.split(' ').collect();
@@ -197,14 +196,52 @@ fn parse_input(input: &str) -> Result<Vec<(char, char)>, SimpleError> {
                 )));
             }
 
-            Ok((split[1].parse()?, split[7].parse()?))
+            let (before, after) = (split[1].parse()?, split[7].parse()?);
+            if ![before, after].iter().all(char::is_ascii_uppercase) {
+                return Err(SimpleError::new(format!(
+                    "steps should be uppercase letters: {line}"
+                )));
+            }
+
+            Ok((before, after))
         })
-        .collect()
+        .collect::<Result<_, SimpleError>>()?;
+
+    check_for_cycles(&requirements)?;
+
+    Ok(requirements)
 }
 
-pub fn solve(input: &str) -> Result<(String, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input, 5, true)?;
+// Sorting recurses through the requirements, so a cycle would never finish
+fn check_for_cycles(requirements: &[(char, char)]) -> Result<(), SimpleError> {
+    let all_letters = get_all_letters(requirements);
+
+    let mut remaining: Vec<_> = requirements.to_vec();
+    let mut done = HashSet::new();
+    while done.len() < all_letters.len() {
+        let ready: Vec<_> = all_letters
+            .iter()
+            .copied()
+            .filter(|letter| {
+                !done.contains(letter) && remaining.iter().all(|&(_, after)| after != *letter)
+            })
+            .collect();
+        if ready.is_empty() {
+            return Err(SimpleError::new(String::from(
+                "steps have circular requirements",
+            )));
+        }
+
+        remaining.retain(|(before, _)| !ready.contains(before));
+        done.extend(ready);
+    }
+
+    Ok(())
+}
+
+pub fn solve(input: &str) -> Result<(String, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, 5, true))?;
 
     Ok((solution1, solution2))
 }
@@ -224,4 +261,14 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(15), solve_part_2(SAMPLE_INPUT, 2, false));
     }
+
+    #[test]
+    fn test_invalid_requirements() {
+        assert!(solve_part_1(
+            "Step A must be finished before step B can begin.\n\
+             Step B must be finished before step A can begin."
+        )
+        .is_err());
+        assert!(solve_part_1("Step a must be finished before step B can begin.").is_err());
+    }
 }
@@ -3,56 +3,73 @@
 //! <https://adventofcode.com/2018/day/8>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let numbers = parse_input(input)?;
+    let numbers = crate::timing::parse(|| parse_input(input))?;
 
-    let metadata_sum = compute_metadata_sum(&mut numbers.into_iter());
+    let mut iter = numbers.into_iter();
+    let metadata_sum = compute_metadata_sum(&mut iter)?;
+    check_exhausted(iter)?;
 
     Ok(metadata_sum)
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let numbers = parse_input(input)?;
+    let numbers = crate::timing::parse(|| parse_input(input))?;
 
-    let root_node_value = compute_node_value(&mut numbers.into_iter());
+    let mut iter = numbers.into_iter();
+    let root_node_value = compute_node_value(&mut iter)?;
+    check_exhausted(iter)?;
 
     Ok(root_node_value)
 }
 
-fn compute_metadata_sum(iter: &mut impl Iterator<Item = u32>) -> u32 {
-    let child_nodes = iter.next().unwrap();
-    let metadata_entries = iter.next().unwrap();
+fn next_number(iter: &mut impl Iterator<Item = u32>) -> Result<u32, SimpleError> {
+    iter.next()
+        .ok_or_else(|| SimpleError::new(String::from("input ends in the middle of a node")))
+}
+
+fn check_exhausted(mut iter: impl Iterator<Item = u32>) -> Result<(), SimpleError> {
+    match iter.next() {
+        Some(_) => Err(SimpleError::new(String::from(
+            "input has numbers after the end of the root node",
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn compute_metadata_sum(iter: &mut impl Iterator<Item = u32>) -> Result<u32, SimpleError> {
+    let child_nodes = next_number(iter)?;
+    let metadata_entries = next_number(iter)?;
 
     let mut metadata_sum = 0;
     for _ in 0..child_nodes {
-        metadata_sum += compute_metadata_sum(iter);
+        metadata_sum += compute_metadata_sum(iter)?;
     }
 
     for _ in 0..metadata_entries {
-        metadata_sum += iter.next().unwrap();
+        metadata_sum += next_number(iter)?;
     }
 
-    metadata_sum
+    Ok(metadata_sum)
 }
 
-fn compute_node_value(iter: &mut impl Iterator<Item = u32>) -> u32 {
-    let num_child_nodes = iter.next().unwrap();
-    let num_metadata_entries = iter.next().unwrap();
+fn compute_node_value(iter: &mut impl Iterator<Item = u32>) -> Result<u32, SimpleError> {
+    let num_child_nodes = next_number(iter)?;
+    let num_metadata_entries = next_number(iter)?;
 
     let mut child_nodes = Vec::new();
     for _ in 0..num_child_nodes {
-        child_nodes.push(compute_node_value(iter));
+        child_nodes.push(compute_node_value(iter)?);
     }
 
     let mut metadata_entries = Vec::new();
     for _ in 0..num_metadata_entries {
-        metadata_entries.push(iter.next().unwrap());
+        metadata_entries.push(next_number(iter)?);
     }
 
     if num_child_nodes == 0 {
-        return metadata_entries.into_iter().sum();
+        return Ok(metadata_entries.into_iter().sum());
     }
 
     let mut node_value = 0;
@@ -61,7 +78,7 @@ fn compute_node_value(iter: &mut impl Iterator<Item = u32>) -> u32 {
             node_value += child_nodes[(node_index - 1) as usize];
         }
     }
-    node_value
+    Ok(node_value)
 }
 
 fn parse_input(input: &str) -> Result<Vec<u32>, SimpleError> {
@@ -71,9 +88,9 @@ fn parse_input(input: &str) -> Result<Vec<u32>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -91,4 +108,10 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(66), solve_part_2("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2"));
     }
+
+    #[test]
+    fn test_invalid_tree() {
+        assert!(solve_part_1("2 3 0 3 10 11 12").is_err());
+        assert!(solve_part_2("0 1 5 7").is_err());
+    }
 }
@@ -4,18 +4,21 @@
 
 use crate::SimpleError;
 use std::collections::VecDeque;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let (players, last_marble) = parse_input(input)?;
+    let (players, last_marble) = crate::timing::parse(|| parse_input(input))?;
 
     Ok(compute_max_player_score(players, last_marble))
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let (players, last_marble) = parse_input(input)?;
+    let (players, last_marble) = crate::timing::parse(|| parse_input(input))?;
 
-    Ok(compute_max_player_score(players, last_marble * 100))
+    let last_marble = last_marble.checked_mul(100).ok_or_else(|| {
+        SimpleError::unsupported_input(format!("last marble is too large: {last_marble}"))
+    })?;
+
+    Ok(compute_max_player_score(players, last_marble))
 }
 
 fn compute_max_player_score(players: u64, last_marble: u64) -> u64 {
@@ -51,12 +54,18 @@ fn parse_input(input: &str) -> Result<(u64, u64), SimpleError> {
 
     let players: u64 = split[0].parse()?;
     let last_marble: u64 = split[6].parse()?;
+    if players == 0 {
+        return Err(SimpleError::new(String::from(
+            "there should be at least one player",
+        )));
+    }
+
     Ok((players, last_marble))
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -78,4 +87,9 @@ mod tests {
         assert_eq!(Ok(54718), solve_part_1(test_string(21, 6111).as_str()));
         assert_eq!(Ok(37305), solve_part_1(test_string(30, 5807).as_str()));
     }
+
+    #[test]
+    fn test_no_players() {
+        assert!(solve_part_1(test_string(0, 25).as_str()).is_err());
+    }
 }
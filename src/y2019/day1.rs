@@ -4,16 +4,15 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let masses = parse_input(input)?;
+    let masses = crate::timing::parse(|| parse_input(input))?;
 
     Ok(masses.into_iter().map(|mass| mass / 3 - 2).sum())
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let masses = parse_input(input)?;
+    let masses = crate::timing::parse(|| parse_input(input))?;
 
     let fuel_required = masses
         .into_iter()
@@ -38,9 +37,9 @@ fn parse_input(input: &str) -> Result<Vec<i32>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
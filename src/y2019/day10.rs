@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp;
 use std::cmp::Ordering;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Point {
@@ -31,7 +30,7 @@ impl Point {
 }
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let grid = parse_input(input)?;
+    let grid = crate::timing::parse(|| parse_input(input))?;
 
     let mut max_seen = 0;
     for (i, row) in grid.iter().enumerate() {
@@ -48,7 +47,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str, n: usize) -> Result<usize, SimpleError> {
-    let grid = parse_input(input)?;
+    let grid = crate::timing::parse(|| parse_input(input))?;
 
     let mut max_seen = 0;
     let mut max_seen_i = 0;
@@ -133,7 +132,7 @@ fn find_nth_destroyed(
         .map(|row| row.iter().filter(|&&b| b).count())
         .sum();
     if total_asteroids < n + 1 {
-        return Err(SimpleError::new(format!(
+        return Err(SimpleError::unsupported_input(format!(
             "input has {total_asteroids} asteroids, expected at least {}",
             n + 1
         )));
@@ -220,9 +219,9 @@ fn parse_input(input: &str) -> Result<Vec<Vec<bool>>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input, 200)?;
+pub fn solve(input: &str) -> Result<(u32, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, 200))?;
 
     Ok((solution1, solution2))
 }
@@ -3,75 +3,62 @@
 //! <https://adventofcode.com/2019/day/11>
 
 use crate::y2019::intcode;
-use crate::y2019::intcode::InteractiveIntcodeProgram;
+use crate::y2019::intcode::screen::TileScreen;
+use crate::y2019::intcode::{InteractiveIntcodeProgram, RunState};
 use crate::SimpleError;
-use std::collections::HashSet;
-use std::error::Error;
-use std::{cmp, mem};
+use std::io::Write;
+use std::mem;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct Point {
-    x: i32,
-    y: i32,
+    x: i64,
+    y: i64,
 }
 
 impl Point {
-    fn new(x: i32, y: i32) -> Self {
+    fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
 }
 
-fn solve_part_1(input: &str) -> Result<usize, Box<dyn Error>> {
+const BLACK: i64 = 0;
+const WHITE: i64 = 1;
+
+fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     let program = intcode::parse_program(input)?;
 
     let program = InteractiveIntcodeProgram::new(program);
-    let (painted_points, _) = run_robot_program(program, false)?;
+    let hull = run_robot_program(program, false, None)?;
 
-    Ok(painted_points.len())
+    Ok(hull.drawn_count())
 }
 
-fn solve_part_2(input: &str) -> Result<String, Box<dyn Error>> {
+fn solve_part_2(input: &str) -> Result<String, SimpleError> {
     let program = intcode::parse_program(input)?;
 
     let program = InteractiveIntcodeProgram::new(program);
-    let (_, white_points) = run_robot_program(program, true)?;
-
-    let (min_x, max_x, min_y, max_y) = white_points.iter().fold(
-        (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
-        |(min_x, max_x, min_y, max_y), &point| {
-            (
-                cmp::min(min_x, point.x),
-                cmp::max(max_x, point.x),
-                cmp::min(min_y, point.y),
-                cmp::max(max_y, point.y),
-            )
-        },
-    );
-
-    let mut s = String::new();
-    for y in (min_y..=max_y).rev() {
-        if y < max_y {
-            s.push('\n');
-        }
+    let hull = run_robot_program(program, true, None)?;
 
-        for x in min_x..=max_x {
-            if white_points.contains(&Point::new(x, y)) {
-                s.push('█');
-            } else {
-                s.push(' ');
-            }
-        }
-    }
+    Ok(hull.render())
+}
+
+// Writes a frame to out for every panel that's painted while painting the registration identifier
+pub fn write_frames(input: &str, out: &mut dyn Write) -> Result<(), SimpleError> {
+    let program = intcode::parse_program(input)?;
+
+    let program = InteractiveIntcodeProgram::new(program);
+    run_robot_program(program, true, Some(out))?;
 
-    Ok(s)
+    Ok(())
 }
 
+// Paints the hull, with y flipped so that up on the hull is up on the screen
 fn run_robot_program(
     mut program: InteractiveIntcodeProgram,
     start_on_white: bool,
-) -> Result<(HashSet<Point>, HashSet<Point>), Box<dyn Error>> {
-    let mut painted_points = HashSet::new();
-    let mut white_points = HashSet::new();
+    mut frames: Option<&mut dyn Write>,
+) -> Result<TileScreen, SimpleError> {
+    let mut hull = TileScreen::new(" █");
 
     let mut position = Point::new(0, 0);
     let mut dx = 0;
@@ -79,31 +66,26 @@ fn run_robot_program(
     let mut halted = false;
 
     if start_on_white {
-        white_points.insert(position);
+        hull.draw(position.x, -position.y, WHITE);
     }
 
     while !halted {
-        if white_points.contains(&position) {
-            program.push_input(1);
-        } else {
-            program.push_input(0);
-        }
+        program.push_input(hull.tile(position.x, -position.y));
 
-        halted = program.execute();
+        halted = program.execute()?.check_step_limit()? == RunState::Halted;
 
         let outputs = program.fetch_outputs();
         if outputs.len() != 2 {
-            return Err(Box::new(SimpleError::new(format!(
+            return Err(SimpleError::runtime(format!(
                 "expected 2 outputs, got: {outputs:?}"
-            ))));
+            )));
         }
 
-        if outputs[0] == 1 {
-            white_points.insert(position);
-        } else {
-            white_points.remove(&position);
+        let color = if outputs[0] == 1 { WHITE } else { BLACK };
+        hull.draw(position.x, -position.y, color);
+        if let Some(frames) = frames.as_deref_mut() {
+            hull.write_frame(frames)?;
         }
-        painted_points.insert(position);
 
         if outputs[1] == 1 {
             // Turn right
@@ -118,12 +100,12 @@ fn run_robot_program(
         position = Point::new(position.x + dx, position.y + dy);
     }
 
-    Ok((painted_points, white_points))
+    Ok(hull)
 }
 
-pub fn solve(input: &str) -> Result<(usize, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
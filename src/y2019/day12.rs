@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashMap;
-use std::error::Error;
 use std::iter;
 use std::ops::{Add, AddAssign, Sub};
 
@@ -67,7 +66,7 @@ impl Moon {
 }
 
 fn solve_part_1(input: &str, steps: usize) -> Result<i64, SimpleError> {
-    let mut moons = parse_input(input)?;
+    let mut moons = crate::timing::parse(|| parse_input(input))?;
 
     for _ in 0..steps {
         // Apply gravity
@@ -92,7 +91,7 @@ fn solve_part_1(input: &str, steps: usize) -> Result<i64, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let mut moons = parse_input(input)?;
+    let mut moons = crate::timing::parse(|| parse_input(input))?;
 
     let mut previous_x_states: HashMap<_, _> = iter::once((x_state(&moons), 0)).collect();
     let mut previous_y_states: HashMap<_, _> = iter::once((y_state(&moons), 0)).collect();
@@ -124,7 +123,7 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
             let current_x_state = x_state(&moons);
             if let Some(&prev) = previous_x_states.get(&current_x_state) {
                 if prev != 0 {
-                    return Err(SimpleError::new(format!(
+                    return Err(SimpleError::unsupported_input(format!(
                         "expected x cycle to start at 0, starts at {prev}"
                     )));
                 }
@@ -138,7 +137,7 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
             let current_y_state = y_state(&moons);
             if let Some(&prev) = previous_y_states.get(&current_y_state) {
                 if prev != 0 {
-                    return Err(SimpleError::new(format!(
+                    return Err(SimpleError::unsupported_input(format!(
                         "expected y cycle to start at 0, starts at {prev}"
                     )));
                 }
@@ -152,7 +151,7 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
             let current_z_state = z_state(&moons);
             if let Some(&prev) = previous_z_states.get(&current_z_state) {
                 if prev != 0 {
-                    return Err(SimpleError::new(format!(
+                    return Err(SimpleError::unsupported_input(format!(
                         "expected z cycle to start at 0, starts at {prev}"
                     )));
                 }
@@ -167,7 +166,9 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn x_state(moons: &[Moon]) -> Vec<(i64, i64)> {
@@ -222,23 +223,30 @@ fn parse_input(input: &str) -> Result<Vec<Moon>, SimpleError> {
     input
         .lines()
         .map(|line| {
-            let split: Vec<_> = line[1..line.len() - 1].split(", ").collect();
-            if split.len() != 3 {
-                return Err(SimpleError::new(format!("invalid line format: {line}")));
-            }
-
-            let x = split[0][2..].parse()?;
-            let y = split[1][2..].parse()?;
-            let z = split[2][2..].parse()?;
+            let invalid_line = || SimpleError::new(format!("invalid line format: {line}"));
+
+            let split: Vec<_> = line
+                .strip_prefix('<')
+                .and_then(|line| line.strip_suffix('>'))
+                .ok_or_else(invalid_line)?
+                .split(", ")
+                .collect();
+            let [x, y, z] = split.as_slice() else {
+                return Err(invalid_line());
+            };
+
+            let x = x.strip_prefix("x=").ok_or_else(invalid_line)?.parse()?;
+            let y = y.strip_prefix("y=").ok_or_else(invalid_line)?.parse()?;
+            let z = z.strip_prefix("z=").ok_or_else(invalid_line)?.parse()?;
 
             Ok(Moon::new(Coords::new(x, y, z)))
         })
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(i64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 1000)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 1000))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,101 +3,83 @@
 //! <https://adventofcode.com/2019/day/13>
 
 use crate::y2019::intcode;
-use crate::y2019::intcode::InteractiveIntcodeProgram;
+use crate::y2019::intcode::screen::TileScreen;
+use crate::y2019::intcode::{InteractiveIntcodeProgram, RunState};
 use crate::SimpleError;
-use std::error::Error;
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Tile {
-    Empty,
-    Wall,
-    Block,
-    Paddle,
-    Ball,
-}
+use std::io::Write;
+use std::iter;
 
-impl Tile {
-    fn from_int(n: i64) -> Result<Self, SimpleError> {
-        let tile = match n {
-            0 => Self::Empty,
-            1 => Self::Wall,
-            2 => Self::Block,
-            3 => Self::Paddle,
-            4 => Self::Ball,
-            _ => return Err(SimpleError::new(format!("invalid tile code: {n}"))),
-        };
-        Ok(tile)
-    }
-}
+const BLOCK: i64 = 2;
+const PADDLE: i64 = 3;
+const BALL: i64 = 4;
 
-fn solve_part_1(input: &str) -> Result<usize, Box<dyn Error>> {
+// Empty, wall, block, paddle and ball
+const PALETTE: &str = " █#▬●";
+
+fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     let mut program = intcode::parse_program(input)?;
 
     let mut outputs = Vec::new();
     intcode::execute(
         &mut program,
-        || panic!("input fn should not be called"),
+        intcode::iterator_input_fn(iter::empty()),
         |output| outputs.push(output),
-    );
+    )?;
+
+    let mut screen = TileScreen::new(PALETTE);
+    screen.extend(&outputs)?;
 
-    if outputs.is_empty() || outputs.len() % 3 != 0 {
-        return Err(Box::new(SimpleError::new(format!(
+    if outputs.is_empty() || !screen.pending_outputs().is_empty() {
+        return Err(SimpleError::runtime(format!(
             "expected outputs to be a positive multiple of 3, got {}",
             outputs.len()
-        ))));
+        )));
     }
 
-    let block_tile_count = outputs
-        .iter()
-        .skip(2)
-        .step_by(3)
-        .filter(|&&n| n == 2)
-        .count();
+    Ok(screen.count(BLOCK))
+}
+
+fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
+    play_game(input, None)
+}
 
-    Ok(block_tile_count)
+// Writes a frame to out for every time the game waits for input, and one at the end
+pub fn write_frames(input: &str, out: &mut dyn Write) -> Result<(), SimpleError> {
+    play_game(input, Some(out))?;
+    Ok(())
 }
 
-fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
+fn play_game(input: &str, mut frames: Option<&mut dyn Write>) -> Result<i64, SimpleError> {
     let mut program = intcode::parse_program(input)?;
     program[0] = 2;
 
     let mut program = InteractiveIntcodeProgram::new(program);
+    let mut screen = TileScreen::new(PALETTE);
 
-    let mut paddle_x = 0;
-    let mut ball_x = 0;
-    let mut score = 0;
     loop {
-        let halted = program.execute();
-
-        for chunk in program.fetch_outputs().chunks(3) {
-            let x = chunk[0];
-            let y = chunk[1];
-            if x == -1 && y == 0 {
-                score = chunk[2];
-                continue;
-            }
-
-            let tile = Tile::from_int(chunk[2])?;
-
-            match tile {
-                Tile::Ball => ball_x = x,
-                Tile::Paddle => paddle_x = x,
-                _ => {}
-            }
+        let halted = program.execute()?.check_step_limit()? == RunState::Halted;
+
+        screen.extend(&program.fetch_outputs())?;
+        if let Some(frames) = frames.as_deref_mut() {
+            screen.write_frame(frames)?;
         }
 
         if halted {
-            return Ok(score);
+            return screen
+                .score()
+                .ok_or_else(|| SimpleError::runtime(String::from("game ended without a score")));
         }
 
-        let next_input = (ball_x - paddle_x).signum();
-        program.push_input(next_input);
+        // Keep the paddle under the ball
+        let ball_x = screen.last_drawn(BALL).map_or(0, |(x, _)| x);
+        let paddle_x = screen.last_drawn(PADDLE).map_or(0, |(x, _)| x);
+        program.push_input((ball_x - paddle_x).signum());
     }
 }
 
-pub fn solve(input: &str) -> Result<(usize, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 use std::str::FromStr;
 
 #[derive(Debug, Clone)]
@@ -35,7 +34,7 @@ struct Reaction {
 }
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let reactions = parse_input(input)?;
+    let reactions = crate::timing::parse(|| parse_input(input))?;
 
     let reactions_dag = build_dag(&reactions);
 
@@ -72,11 +71,13 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let reactions = parse_input(input)?;
+    let reactions = crate::timing::parse(|| parse_input(input))?;
 
     let reactions_dag = build_dag(&reactions);
 
@@ -116,7 +117,9 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn build_dag(reactions: &[Reaction]) -> HashMap<String, Vec<String>> {
@@ -172,7 +175,7 @@ fn topological_sort_visit(
 }
 
 fn parse_input(input: &str) -> Result<Vec<Reaction>, SimpleError> {
-    input
+    let reactions: Vec<_> = input
         .lines()
         .map(|line| {
             let (inputs, output) = line.split_once(" => ").ok_or_else(|| {
@@ -188,12 +191,46 @@ fn parse_input(input: &str) -> Result<Vec<Reaction>, SimpleError> {
 
             Ok(Reaction { inputs, output })
         })
-        .collect()
+        .collect::<Result<_, SimpleError>>()?;
+
+    // Every chemical other than ORE has to come from exactly one reaction
+    let mut produced = HashSet::new();
+    for reaction in &reactions {
+        let name = reaction.output.name.as_str();
+        if name == "ORE" {
+            return Err(SimpleError::new(String::from(
+                "ORE should not be produced by a reaction",
+            )));
+        }
+        if !produced.insert(name) {
+            return Err(SimpleError::new(format!(
+                "{name} should not be produced by more than one reaction"
+            )));
+        }
+        if reaction.output.amount == 0 {
+            return Err(SimpleError::new(format!(
+                "reaction for {name} should produce at least 1"
+            )));
+        }
+    }
+    for chemical in reactions.iter().flat_map(|reaction| &reaction.inputs) {
+        if chemical.name != "ORE" && !produced.contains(chemical.name.as_str()) {
+            return Err(SimpleError::new(format!(
+                "no reaction produces {}",
+                chemical.name
+            )));
+        }
+    }
+    if !produced.contains("FUEL") {
+        return Err(SimpleError::new(String::from("no reaction produces FUEL")));
+    }
+
+    Ok(reactions)
 }
 
-pub fn solve(input: &str) -> Result<(u32, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -223,4 +260,10 @@ mod tests {
         assert_eq!(Ok(5586022), solve_part_2(SAMPLE_INPUT_4));
         assert_eq!(Ok(460664), solve_part_2(SAMPLE_INPUT_5));
     }
+
+    #[test]
+    fn test_missing_reaction() {
+        assert!(solve_part_1("10 ORE => 1 A\n1 A, 1 B => 1 FUEL").is_err());
+        assert!(solve_part_1("10 ORE => 1 A").is_err());
+    }
 }
@@ -6,7 +6,6 @@ use crate::y2019::intcode;
 use crate::y2019::intcode::InteractiveIntcodeProgram;
 use crate::SimpleError;
 use std::collections::{HashSet, VecDeque};
-use std::error::Error;
 use std::ops::Add;
 use std::{cmp, iter};
 
@@ -56,7 +55,7 @@ impl Direction {
     }
 }
 
-fn solve_both_parts(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+fn solve_both_parts(input: &str) -> Result<(usize, usize), SimpleError> {
     let program = intcode::parse_program(input)?;
 
     let (program_at_oxygen_system, steps_to_oxygen_system) = find_oxygen_system(program)?;
@@ -75,10 +74,10 @@ fn solve_both_parts(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
             if !visited.contains(&new_position) {
                 visited.insert(new_position);
 
-                let mut program = program.clone();
+                let mut program = program.fork();
                 program.push_input(direction.to_intcode_command());
 
-                program.execute();
+                program.execute()?.check_step_limit()?;
 
                 let outputs = program.fetch_outputs();
 
@@ -88,10 +87,10 @@ fn solve_both_parts(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
                         queue.push_back((program, new_position, steps + 1));
                     }
                     _ => {
-                        return Err(Box::new(SimpleError::new(format!(
+                        return Err(SimpleError::runtime(format!(
                             "unexpected program output: {}",
                             outputs[0]
-                        ))))
+                        )))
                     }
                 }
             }
@@ -103,7 +102,7 @@ fn solve_both_parts(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
 
 fn find_oxygen_system(
     program: Vec<i64>,
-) -> Result<(InteractiveIntcodeProgram, usize), Box<dyn Error>> {
+) -> Result<(InteractiveIntcodeProgram, usize), SimpleError> {
     let mut visited: HashSet<_> = iter::once(Point::new(0, 0)).collect();
 
     let mut queue = VecDeque::new();
@@ -115,17 +114,17 @@ fn find_oxygen_system(
             if !visited.contains(&new_position) {
                 visited.insert(new_position);
 
-                let mut program = program.clone();
+                let mut program = program.fork();
                 program.push_input(direction.to_intcode_command());
 
-                program.execute();
+                program.execute()?.check_step_limit()?;
 
                 let outputs = program.fetch_outputs();
                 if outputs.len() != 1 {
-                    return Err(Box::new(SimpleError::new(format!(
+                    return Err(SimpleError::runtime(format!(
                         "expected 1 output from program, got {}",
                         outputs.len()
-                    ))));
+                    )));
                 }
 
                 match outputs[0] {
@@ -137,22 +136,22 @@ fn find_oxygen_system(
                         return Ok((program, steps + 1));
                     }
                     _ => {
-                        return Err(Box::new(SimpleError::new(format!(
+                        return Err(SimpleError::runtime(format!(
                             "unexpected program output: {}",
                             outputs[0]
-                        ))))
+                        )))
                     }
                 }
             }
         }
     }
 
-    Err(Box::new(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "no solution found",
-    ))))
+    )))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2019/day/16>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::iter;
 
 fn solve_part_1(input: &str, phases: usize) -> Result<String, SimpleError> {
@@ -28,7 +27,7 @@ fn solve_part_2(input: &str) -> Result<String, SimpleError> {
     let offset: usize = numbers[..7].parse()?;
 
     if offset <= 10000 * digits.len() / 2 {
-        return Err(SimpleError::new(format!(
+        return Err(SimpleError::unsupported_input(format!(
             "expected offset to be in second half of expanded list; offset={offset}, len={}",
             10000 * digits.len()
         )));
@@ -107,9 +106,9 @@ fn digits_to_string(digits: &[i32]) -> String {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(String, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 100)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 100))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
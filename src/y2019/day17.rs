@@ -3,10 +3,10 @@
 //! <https://adventofcode.com/2019/day/17>
 
 use crate::y2019::intcode;
-use crate::y2019::intcode::InteractiveIntcodeProgram;
+use crate::y2019::intcode::ascii::{self, AsciiProgram};
 use crate::SimpleError;
-use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::iter;
 use std::ops::{Add, AddAssign};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -119,7 +119,7 @@ struct RobotProgram {
     function_c: RobotFunction,
 }
 
-fn solve_part_1(input: &str) -> Result<usize, Box<dyn Error>> {
+fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     let program = intcode::parse_program(input)?;
 
     let (map, _) = build_map_from_program(program)?;
@@ -136,7 +136,7 @@ fn solve_part_1(input: &str) -> Result<usize, Box<dyn Error>> {
     Ok(alignment_sum)
 }
 
-fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
+fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
     let mut program = intcode::parse_program(input)?;
 
     let (map, robot) = build_map_from_program(program.clone())?;
@@ -145,18 +145,22 @@ fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
 
     program[0] = 2;
 
-    let mut intcode_program = InteractiveIntcodeProgram::new(program);
+    let mut intcode_program = AsciiProgram::new(program);
 
-    intcode_program.push_line_as_ascii(&join_chars(&movement_program.main_routine));
-    intcode_program.push_line_as_ascii(&movement_program.function_a.to_string());
-    intcode_program.push_line_as_ascii(&movement_program.function_b.to_string());
-    intcode_program.push_line_as_ascii(&movement_program.function_c.to_string());
+    intcode_program.send_line(&join_chars(&movement_program.main_routine))?;
+    intcode_program.send_line(&movement_program.function_a.to_string())?;
+    intcode_program.send_line(&movement_program.function_b.to_string())?;
+    intcode_program.send_line(&movement_program.function_c.to_string())?;
 
-    intcode_program.push_line_as_ascii("n");
+    intcode_program.send_line("n")?;
 
-    intcode_program.execute();
-
-    Ok(intcode_program.fetch_outputs().last().copied().unwrap())
+    let output = intcode_program.run()?;
+    output.value.ok_or_else(|| {
+        SimpleError::runtime(format!(
+            "intcode program did not output the amount of dust collected: {}",
+            output.text
+        ))
+    })
 }
 
 fn join_chars(chars: &[char]) -> String {
@@ -168,11 +172,11 @@ fn create_movement_program(
     map: &Vec<Vec<bool>>,
     robot: Robot,
 ) -> Result<RobotProgram, SimpleError> {
-    let full_path = find_full_path(map, robot);
+    let full_path = find_full_path(map, robot)?;
 
     let program = search_for_movement_program(&full_path, Vec::new(), Vec::new());
 
-    program.ok_or_else(|| SimpleError::new(String::from("no solution found")))
+    program.ok_or_else(|| SimpleError::unsupported_input(String::from("no solution found")))
 }
 
 fn search_for_movement_program(
@@ -316,9 +320,16 @@ fn remove_prefix(path: &[RobotCommand], commands: &[RobotCommand]) -> Vec<RobotC
     new_path
 }
 
-fn find_full_path(map: &Vec<Vec<bool>>, mut robot: Robot) -> Vec<RobotCommand> {
+fn find_full_path(
+    map: &Vec<Vec<bool>>,
+    mut robot: Robot,
+) -> Result<Vec<RobotCommand>, SimpleError> {
     let mut commands = Vec::new();
 
+    // A path that doesn't dead end within this many steps has to be going around in a loop
+    let max_steps = 4 * map.iter().map(Vec::len).sum::<usize>();
+    let mut total_steps = 0;
+
     let mut move_steps = 0;
     loop {
         if !is_scaffold(map, robot.position + robot.direction) {
@@ -349,9 +360,16 @@ fn find_full_path(map: &Vec<Vec<bool>>, mut robot: Robot) -> Vec<RobotCommand> {
 
         robot.position += robot.direction;
         move_steps += 1;
+
+        total_steps += 1;
+        if total_steps > max_steps {
+            return Err(SimpleError::unsupported_input(String::from(
+                "scaffold path loops forever",
+            )));
+        }
     }
 
-    commands
+    Ok(commands)
 }
 
 fn find_turn_command(map: &Vec<Vec<bool>>, robot: Robot) -> Option<RobotCommand> {
@@ -367,39 +385,31 @@ fn find_turn_command(map: &Vec<Vec<bool>>, robot: Robot) -> Option<RobotCommand>
 fn is_scaffold(map: &Vec<Vec<bool>>, p: Point) -> bool {
     let i = p.i;
     let j = p.j;
-    i >= 0
-        && j >= 0
-        && i < map.len() as i32
-        && j < map[0].len() as i32
-        && map[i as usize][j as usize]
+    let (Ok(i), Ok(j)) = (usize::try_from(i), usize::try_from(j)) else {
+        return false;
+    };
+    map.get(i)
+        .and_then(|row| row.get(j))
+        .copied()
+        .unwrap_or(false)
 }
 
 fn build_map_from_program(mut program: Vec<i64>) -> Result<(Vec<Vec<bool>>, Robot), SimpleError> {
     let mut outputs = Vec::new();
     intcode::execute(
         &mut program,
-        || panic!("input fn should not be called"),
+        intcode::iterator_input_fn(iter::empty()),
         |output| outputs.push(output),
-    );
-
-    let mut outputs_as_string = String::new();
-    for &ascii_code in &outputs {
-        if ascii_code < 0 {
-            return Err(SimpleError::new(format!(
-                "invalid negative ASCII code output by program: {ascii_code}"
-            )));
-        }
-
-        let c = char::from_u32(ascii_code as u32).ok_or_else(|| {
-            SimpleError::new(format!(
-                "invalid ASCII code output by program: {ascii_code}"
-            ))
-        })?;
+    )?;
 
-        outputs_as_string.push(c);
+    let output = ascii::decode(&outputs)?;
+    if let Some(value) = output.value {
+        return Err(SimpleError::runtime(format!(
+            "invalid ASCII code output by program: {value}"
+        )));
     }
 
-    build_map(&outputs_as_string)
+    build_map(&output.text)
 }
 
 fn build_map(outputs_as_string: &str) -> Result<(Vec<Vec<bool>>, Robot), SimpleError> {
@@ -434,25 +444,33 @@ fn build_map(outputs_as_string: &str) -> Result<(Vec<Vec<bool>>, Robot), SimpleE
                 }
             }
             _ => {
-                return Err(SimpleError::new(format!(
+                return Err(SimpleError::runtime(format!(
                     "unexpected char in program output: {c}"
                 )))
             }
         }
     }
 
-    if robot.is_none() {
-        return Err(SimpleError::new(String::from(
-            "program did not output a robot location",
+    if !current_row.is_empty() {
+        map.push(current_row);
+    }
+
+    if map.iter().any(|row| row.len() != map[0].len()) {
+        return Err(SimpleError::runtime(String::from(
+            "program output rows of different lengths",
         )));
     }
 
-    Ok((map, robot.unwrap()))
+    let robot = robot.ok_or_else(|| {
+        SimpleError::runtime(String::from("program did not output a robot location"))
+    })?;
+
+    Ok((map, robot))
 }
 
-pub fn solve(input: &str) -> Result<(usize, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -469,4 +487,13 @@ mod tests {
 
         assert!(create_movement_program(&map, robot).is_ok());
     }
+
+    #[test]
+    fn test_invalid_camera_output() {
+        assert!(build_map("#.#\n#\n").is_err());
+        assert!(build_map("###\n").is_err());
+
+        let (map, robot) = build_map("###\n#^#\n###").unwrap();
+        assert!(create_movement_program(&map, robot).is_err());
+    }
 }
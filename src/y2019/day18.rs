@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
-use std::error::Error;
 use std::hash::Hash;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -102,7 +101,7 @@ struct PathToKey {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let map = parse_input(input)?;
+    let map = crate::timing::parse(|| parse_input(input))?;
 
     let num_keys = count_keys(&map);
 
@@ -177,11 +176,13 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let mut map = parse_input(input)?;
+    let mut map = crate::timing::parse(|| parse_input(input))?;
 
     let num_keys = count_keys(&map);
 
@@ -265,7 +266,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn build_reachable_keys_map(
@@ -395,15 +398,42 @@ fn find_entrance(map: &[Vec<Space>]) -> Option<(usize, usize)> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Vec<Space>>, SimpleError> {
-    input
+    let map: Vec<Vec<_>> = input
         .lines()
         .map(|line| line.chars().map(Space::from_char).collect())
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    if map.is_empty() {
+        return Err(SimpleError::new(String::from("input should not be empty")));
+    }
+
+    if map.iter().any(|row| row.len() != map[0].len()) {
+        return Err(SimpleError::new(String::from(
+            "lines are not all the same length",
+        )));
+    }
+
+    // Searches look at all four neighbors of open spaces without bounds checks
+    let last_row = map.len() - 1;
+    let enclosed = map.iter().enumerate().all(|(i, row)| {
+        if i == 0 || i == last_row {
+            row.iter().all(|&space| space == Space::Wall)
+        } else {
+            row.first() == Some(&Space::Wall) && row.last() == Some(&Space::Wall)
+        }
+    });
+    if !enclosed {
+        return Err(SimpleError::new(String::from(
+            "map should be surrounded by walls",
+        )));
+    }
+
+    Ok(map)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -437,4 +467,11 @@ mod tests {
         assert_eq!(Ok(32), solve_part_2(SAMPLE_INPUT_8));
         assert_eq!(Ok(72), solve_part_2(SAMPLE_INPUT_9));
     }
+
+    #[test]
+    fn test_invalid_maps() {
+        assert!(solve_part_1("#####\n#@.a.\n#####").is_err());
+        assert!(solve_part_1("#####\n#@a#\n#####").is_err());
+        assert!(solve_part_1("").is_err());
+    }
 }
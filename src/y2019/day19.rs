@@ -3,10 +3,10 @@
 //! <https://adventofcode.com/2019/day/19>
 
 use crate::y2019::intcode;
+use crate::y2019::intcode::{InteractiveIntcodeProgram, RunState};
 use crate::SimpleError;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 struct HeapEntry {
@@ -26,13 +26,13 @@ impl Ord for HeapEntry {
     }
 }
 
-fn solve_part_1(input: &str) -> Result<usize, Box<dyn Error>> {
-    let program = intcode::parse_program(input)?;
+fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
+    let program = InteractiveIntcodeProgram::new(intcode::parse_program(input)?);
 
     let mut points_affected = 0;
     for x in 0..50 {
         for y in 0..50 {
-            if is_in_beam(program.clone(), x, y) {
+            if is_in_beam(&program, x, y)? {
                 points_affected += 1;
             }
         }
@@ -41,13 +41,13 @@ fn solve_part_1(input: &str) -> Result<usize, Box<dyn Error>> {
     Ok(points_affected)
 }
 
-fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
-    let program = intcode::parse_program(input)?;
+fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
+    let program = InteractiveIntcodeProgram::new(intcode::parse_program(input)?);
 
     let mut heap = BinaryHeap::new();
 
     let mut y = 0;
-    while !is_in_beam(program.clone(), 100, y) {
+    while !is_in_beam(&program, 100, y)? {
         y += 1;
     }
 
@@ -56,9 +56,9 @@ fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
     let mut visited = HashSet::new();
 
     while let Some(HeapEntry { x, y }) = heap.pop() {
-        if is_in_beam(program.clone(), x + 100, y)
-            && is_in_beam(program.clone(), x, y + 100)
-            && is_in_beam(program.clone(), x + 100, y + 100)
+        if is_in_beam(&program, x + 100, y)?
+            && is_in_beam(&program, x, y + 100)?
+            && is_in_beam(&program, x + 100, y + 100)?
         {
             return Ok(10000 * x + y);
         }
@@ -68,40 +68,47 @@ fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
         }
 
         let horizontal_entry = HeapEntry { x: x + 1, y };
-        if is_in_beam(program.clone(), x + 1, y) && !visited.contains(&horizontal_entry) {
+        if is_in_beam(&program, x + 1, y)? && !visited.contains(&horizontal_entry) {
             heap.push(horizontal_entry);
         }
 
         let vertical_entry = HeapEntry { x, y: y + 1 };
-        if is_in_beam(program.clone(), x, y + 1) && !visited.contains(&vertical_entry) {
+        if is_in_beam(&program, x, y + 1)? && !visited.contains(&vertical_entry) {
             heap.push(vertical_entry);
         }
     }
 
-    Err(Box::new(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "no solution found",
-    ))))
+    )))
 }
 
-fn is_in_beam(mut program: Vec<i64>, x: i64, y: i64) -> bool {
-    let mut outputs = Vec::new();
+// Runs a fork of the unstarted program, which shares its memory and decoded instructions rather
+// than loading the program from scratch for every point
+fn is_in_beam(program: &InteractiveIntcodeProgram, x: i64, y: i64) -> Result<bool, SimpleError> {
+    let mut program = program.fork();
+    program.push_input(x);
+    program.push_input(y);
+    if program.execute()?.check_step_limit()? != RunState::Halted {
+        return Err(SimpleError::runtime(String::from(
+            "intcode program needed more than two inputs",
+        )));
+    }
 
-    intcode::execute(
-        &mut program,
-        intcode::iterator_input_fn(vec![x, y].into_iter()),
-        |output| outputs.push(output),
-    );
+    let outputs = program.fetch_outputs();
 
     if outputs.is_empty() {
-        panic!("intcode program returned no outputs");
+        return Err(SimpleError::runtime(String::from(
+            "intcode program returned no outputs",
+        )));
     }
 
-    outputs[0] == 1
+    Ok(outputs[0] == 1)
 }
 
-pub fn solve(input: &str) -> Result<(usize, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
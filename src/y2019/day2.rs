@@ -4,20 +4,19 @@
 
 use crate::y2019::intcode;
 use crate::SimpleError;
-use std::error::Error;
 
-fn solve_part_1(input: &str) -> Result<i64, Box<dyn Error>> {
+fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
     let mut program = intcode::parse_program(input)?;
 
     program[1] = 12;
     program[2] = 2;
 
-    intcode::execute_no_io(&mut program);
+    intcode::execute_no_io(&mut program)?;
 
     Ok(program[0])
 }
 
-fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
+fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
     let program = intcode::parse_program(input)?;
 
     for noun in 0..=99 {
@@ -26,7 +25,7 @@ fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
             program[1] = noun;
             program[2] = verb;
 
-            intcode::execute_no_io(&mut program);
+            intcode::execute_no_io(&mut program)?;
 
             if program[0] == 19690720 {
                 return Ok(100 * noun + verb);
@@ -34,14 +33,14 @@ fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
         }
     }
 
-    Err(Box::new(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "no solution found",
-    ))))
+    )))
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
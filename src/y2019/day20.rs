@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::error::Error;
 use std::iter;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -43,7 +42,7 @@ impl From<RawSpace> for Space {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let raw_maze = parse_input(input)?;
+    let raw_maze = crate::timing::parse(|| parse_input(input))?;
     let maze = locate_portals(&raw_maze);
 
     let portal_connections = build_portal_connection_map(&maze)?;
@@ -85,11 +84,13 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let raw_maze = parse_input(input)?;
+    let raw_maze = crate::timing::parse(|| parse_input(input))?;
     let maze = locate_portals(&raw_maze);
 
     let portal_connections = build_portal_connection_map(&maze)?;
@@ -138,7 +139,9 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn find_start_and_end(grid: &[Vec<Space>]) -> Result<(Point, Point), SimpleError> {
@@ -249,7 +252,7 @@ fn locate_portals(raw_maze: &Vec<Vec<RawSpace>>) -> Vec<Vec<Space>> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Vec<RawSpace>>, SimpleError> {
-    input
+    let mut raw_maze: Vec<Vec<_>> = input
         .lines()
         .map(|line| {
             line.chars()
@@ -261,12 +264,33 @@ fn parse_input(input: &str) -> Result<Vec<Vec<RawSpace>>, SimpleError> {
                 })
                 .collect()
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    // Trailing spaces are often trimmed from the end of lines
+    let cols = raw_maze.iter().map(Vec::len).max().unwrap_or_default();
+    for row in &mut raw_maze {
+        row.resize(cols, RawSpace::Wall);
+    }
+
+    // The outer two rows and columns are only for portal labels, which keeps searches in bounds
+    let rows = raw_maze.len();
+    for (i, row) in raw_maze.iter().enumerate() {
+        for (j, &space) in row.iter().enumerate() {
+            let in_border = i < 2 || j < 2 || i + 2 >= rows || j + 2 >= cols;
+            if in_border && space == RawSpace::Empty {
+                return Err(SimpleError::new(format!(
+                    "open tile at ({i}, {j}) is outside of the maze"
+                )));
+            }
+        }
+    }
+
+    Ok(raw_maze)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -290,4 +314,9 @@ mod tests {
         assert_eq!(Ok(26), solve_part_2(SAMPLE_INPUT_1));
         assert_eq!(Ok(396), solve_part_2(SAMPLE_INPUT_3));
     }
+
+    #[test]
+    fn test_open_tile_on_edge() {
+        assert!(solve_part_1(".#\n##").is_err());
+    }
 }
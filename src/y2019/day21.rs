@@ -3,9 +3,8 @@
 //! <https://adventofcode.com/2019/day/21>
 
 use crate::y2019::intcode;
-use crate::y2019::intcode::InteractiveIntcodeProgram;
+use crate::y2019::intcode::ascii::AsciiProgram;
 use crate::SimpleError;
-use std::error::Error;
 
 // J = !A || (!B && D) || (!C && D)
 // When a hole is within 3 spaces, jump as early as possible as long as the robot will land on
@@ -45,38 +44,29 @@ AND T J
 RUN
 ";
 
-fn solve_part(input: &str, instructions: &str) -> Result<i64, Box<dyn Error>> {
+fn solve_part(input: &str, instructions: &str) -> Result<i64, SimpleError> {
     let program = intcode::parse_program(input)?;
-    let mut program = InteractiveIntcodeProgram::new(program);
+    let mut program = AsciiProgram::new(program);
 
     let instructions: Vec<_> = instructions.lines().filter(|s| !s.is_empty()).collect();
 
     for line in instructions {
-        program.push_line_as_ascii(line);
+        program.send_line(line)?;
     }
 
-    program.execute();
-
-    let outputs = program.fetch_outputs();
-    let solution = outputs.last().copied().unwrap();
-    if <i64 as TryInto<u8>>::try_into(solution).is_ok() {
-        // If the number fits in a u8 then the intcode program did not actually output a solution
-
-        for &output in &outputs {
-            print!("{}", (output as u8) as char);
-        }
-
-        return Err(Box::new(SimpleError::new(String::from(
-            "intcode program did not return a solution, see above output",
-        ))));
-    }
-
-    Ok(solution)
+    // If the droid falls into space then the program prints its last moments instead of a solution
+    let output = program.run()?;
+    output.value.ok_or_else(|| {
+        SimpleError::runtime(format!(
+            "intcode program did not return a solution, output was:\n{}",
+            output.text
+        ))
+    })
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part(input, PART_1_INSTRUCTIONS)?;
-    let solution2 = solve_part(input, PART_2_INSTRUCTIONS)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, PART_1_INSTRUCTIONS))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, PART_2_INSTRUCTIONS))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2019/day/22>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Shuffle {
@@ -67,7 +66,7 @@ impl Shuffle {
 const PART_1_DECK_SIZE: i64 = 10007;
 
 fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
-    let shuffles = parse_input(input)?;
+    let shuffles = crate::timing::parse(|| parse_input(input))?;
 
     let shuffle_function = reduce_to_function(&shuffles, PART_1_DECK_SIZE);
 
@@ -81,7 +80,7 @@ const PART_2_DECK_SIZE: i64 = 119315717514047;
 const PART_2_SHUFFLES: u64 = 101741582076661;
 
 fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
-    let shuffles = parse_input(input)?;
+    let shuffles = crate::timing::parse(|| parse_input(input))?;
 
     let shuffle_function = reduce_to_function(&shuffles, PART_2_DECK_SIZE);
 
@@ -189,9 +188,9 @@ fn parse_input(input: &str) -> Result<Vec<Shuffle>, SimpleError> {
     input.lines().map(Shuffle::from_line).collect()
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,11 +4,10 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 use std::iter;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let mut grid = parse_input(input)?;
+    let mut grid = crate::timing::parse(|| parse_input(input))?;
 
     let mut previous_states: HashSet<_> = iter::once(grid.clone()).collect();
 
@@ -22,7 +21,7 @@ fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
 }
 
 fn solve_part_2(input: &str, minutes: usize) -> Result<usize, SimpleError> {
-    let initial_grid = parse_input(input)?;
+    let initial_grid = crate::timing::parse(|| parse_input(input))?;
 
     let mut grids = vec![
         vec![vec![false; initial_grid[0].len()]; initial_grid.len()],
@@ -174,7 +173,7 @@ fn should_append(last_grid: &[Vec<bool>]) -> bool {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Vec<bool>>, SimpleError> {
-    input
+    let grid: Vec<Vec<_>> = input
         .lines()
         .map(|line| {
             line.chars()
@@ -185,12 +184,18 @@ fn parse_input(input: &str) -> Result<Vec<Vec<bool>>, SimpleError> {
                 })
                 .collect()
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    if grid.len() != 5 || grid.iter().any(|row| row.len() != 5) {
+        return Err(SimpleError::new(String::from("grid should be 5x5")));
+    }
+
+    Ok(grid)
 }
 
-pub fn solve(input: &str) -> Result<(u64, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input, 200)?;
+pub fn solve(input: &str) -> Result<(u64, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, 200))?;
 
     Ok((solution1, solution2))
 }
@@ -210,4 +215,9 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(99), solve_part_2(SAMPLE_INPUT, 10));
     }
+
+    #[test]
+    fn test_wrong_grid_size() {
+        assert!(solve_part_1("#...\n....\n....\n....").is_err());
+    }
 }
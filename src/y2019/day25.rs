@@ -3,12 +3,10 @@
 //! <https://adventofcode.com/2019/day/25>
 
 use crate::y2019::intcode;
-use crate::y2019::intcode::InteractiveIntcodeProgram;
+use crate::y2019::intcode::ascii;
+use crate::y2019::intcode::{InteractiveIntcodeProgram, RunState};
 use crate::SimpleError;
-use std::error::Error;
-use std::path::Path;
 use std::str::FromStr;
-use std::{env, fs};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Direction {
@@ -66,7 +64,7 @@ struct PlayerState {
     from_direction: Option<Direction>,
 }
 
-fn solve(input: &str) -> Result<(), Box<dyn Error>> {
+fn solve_part_1(input: &str) -> Result<String, SimpleError> {
     let program = intcode::parse_program(input)?;
 
     let program = InteractiveIntcodeProgram::new(program);
@@ -80,20 +78,22 @@ fn solve(input: &str) -> Result<(), Box<dyn Error>> {
         inventory: Vec::new(),
         from_direction: None,
     })?;
+    let from_direction = from_direction
+        .ok_or_else(|| SimpleError::runtime(String::from("started at the security checkpoint")))?;
 
     for item in &inventory {
-        program.push_line_as_ascii(&format!("drop {item}"));
+        program.push_line_as_ascii(&format!("drop {item}"))?;
     }
-    program.execute();
+    program.execute()?.check_step_limit()?;
     program.fetch_outputs();
 
     // Go back out and in to figure out which direction the exit is in
-    program.push_line_as_ascii(from_direction.unwrap().invert().to_str());
-    program.execute();
+    program.push_line_as_ascii(from_direction.invert().to_str())?;
+    program.execute()?.check_step_limit()?;
     program.fetch_outputs();
 
-    program.push_line_as_ascii(from_direction.unwrap().to_str());
-    program.execute();
+    program.push_line_as_ascii(from_direction.to_str())?;
+    program.execute()?.check_step_limit()?;
 
     let final_room_state = parse_room_state(&program.fetch_outputs())?;
 
@@ -101,53 +101,57 @@ fn solve(input: &str) -> Result<(), Box<dyn Error>> {
         .exits
         .iter()
         .copied()
-        .find(|&direction| direction != from_direction.unwrap().invert())
-        .unwrap();
+        .find(|&direction| direction != from_direction.invert())
+        .ok_or_else(|| {
+            SimpleError::runtime(String::from("security checkpoint has no other exit"))
+        })?;
 
+    // Try every combination of items from the same state outside the checkpoint
+    let checkpoint = program.snapshot();
     for inventory_bits in 1..2_u32.pow(inventory.len() as u32) {
-        let mut program = program.clone();
+        program.restore(&checkpoint);
 
         for (i, item) in inventory.iter().enumerate() {
             let i = i as u32;
             if inventory_bits & (1 << i) != 0 {
-                program.push_line_as_ascii(&format!("take {item}"));
+                program.push_line_as_ascii(&format!("take {item}"))?;
             }
         }
 
-        program.execute();
+        program.execute()?.check_step_limit()?;
         program.fetch_outputs();
 
-        program.push_line_as_ascii(checkpoint_direction.to_str());
+        program.push_line_as_ascii(checkpoint_direction.to_str())?;
 
-        if program.execute() {
-            print!("{}", ascii_to_string(&program.fetch_outputs()));
-            return Ok(());
+        if program.execute()?.check_step_limit()? == RunState::Halted {
+            let password = parse_password(&ascii::decode(&program.fetch_outputs())?.text)?;
+            return Ok(password);
         }
     }
 
-    Err(Box::new(SimpleError::new(String::from(
+    Err(SimpleError::unsupported_input(String::from(
         "no solution found",
-    ))))
+    )))
 }
 
 const BLACKLISTED_ITEMS: [&str; 2] = ["infinite loop", "giant electromagnet"];
 
 fn traverse_map(mut state: PlayerState) -> Result<PlayerState, SimpleError> {
-    state.program.execute();
+    state.program.execute()?.check_step_limit()?;
 
     let room_state = parse_room_state(&state.program.fetch_outputs())?;
 
     for item in &room_state.items {
         if !BLACKLISTED_ITEMS.contains(&item.as_str()) {
-            let mut test_program = state.program.clone();
-            test_program.push_line_as_ascii(&format!("take {item}"));
-            if test_program.execute() {
+            let mut test_program = state.program.fork();
+            test_program.push_line_as_ascii(&format!("take {item}"))?;
+            if test_program.execute()?.check_step_limit()? == RunState::Halted {
                 // Halted, don't take this item
                 continue;
             }
 
-            state.program.push_line_as_ascii(&format!("take {item}"));
-            state.program.execute();
+            state.program.push_line_as_ascii(&format!("take {item}"))?;
+            state.program.execute()?.check_step_limit()?;
             state.program.fetch_outputs();
 
             state.inventory.push(item.clone());
@@ -162,10 +166,10 @@ fn traverse_map(mut state: PlayerState) -> Result<PlayerState, SimpleError> {
             continue;
         }
 
-        let mut program = state.program.clone();
-        program.push_line_as_ascii(exit.to_str());
+        let mut program = state.program.fork();
+        program.push_line_as_ascii(exit.to_str())?;
 
-        if contains_exit(program.clone(), exit)? {
+        if contains_exit(program.fork(), exit)? {
             exit_direction = Some(exit);
         } else {
             state = traverse_map(PlayerState {
@@ -181,26 +185,28 @@ fn traverse_map(mut state: PlayerState) -> Result<PlayerState, SimpleError> {
         return Ok(state);
     }
 
-    if exit_direction.is_none() {
+    let Some(exit_direction) = exit_direction else {
         match from_direction {
             Some(from_direction) => {
                 state
                     .program
-                    .push_line_as_ascii(from_direction.invert().to_str());
-                state.program.execute();
+                    .push_line_as_ascii(from_direction.invert().to_str())?;
+                state.program.execute()?.check_step_limit()?;
                 state.program.fetch_outputs();
                 return Ok(state);
             }
-            None => panic!("not on exit path and from direction is not set"),
+            None => {
+                return Err(SimpleError::runtime(String::from(
+                    "not on exit path and from direction is not set",
+                )))
+            }
         }
-    }
+    };
 
-    state
-        .program
-        .push_line_as_ascii(exit_direction.unwrap().to_str());
+    state.program.push_line_as_ascii(exit_direction.to_str())?;
 
     traverse_map(PlayerState {
-        from_direction: exit_direction,
+        from_direction: Some(exit_direction),
         ..state
     })
 }
@@ -209,7 +215,7 @@ fn contains_exit(
     mut program: InteractiveIntcodeProgram,
     from_direction: Direction,
 ) -> Result<bool, SimpleError> {
-    program.execute();
+    program.execute()?.check_step_limit()?;
 
     let room_state = parse_room_state(&program.fetch_outputs())?;
 
@@ -222,8 +228,8 @@ fn contains_exit(
             continue;
         }
 
-        let mut program = program.clone();
-        program.push_line_as_ascii(exit.to_str());
+        let mut program = program.fork();
+        program.push_line_as_ascii(exit.to_str())?;
 
         if contains_exit(program, exit)? {
             return Ok(true);
@@ -234,7 +240,7 @@ fn contains_exit(
 }
 
 fn parse_room_state(output: &[i64]) -> Result<RoomState, SimpleError> {
-    let room_string = ascii_to_string(output);
+    let room_string = ascii::decode(output)?.text;
     let lines: Vec<_> = room_string.lines().collect();
 
     let mut name = String::new();
@@ -247,23 +253,26 @@ fn parse_room_state(output: &[i64]) -> Result<RoomState, SimpleError> {
         }
 
         let first_line = line_group[0];
-        if first_line.starts_with("== ") {
-            name = String::from(&first_line[3..first_line.len() - 3]);
+        if let Some(room_name) = first_line
+            .strip_prefix("== ")
+            .and_then(|s| s.strip_suffix(" =="))
+        {
+            name = String::from(room_name);
         } else if first_line == "Doors here lead:" {
             exits = line_group[1..]
                 .iter()
-                .map(|line| Direction::from_str(&line[2..]))
+                .map(|line| Direction::from_str(strip_list_item(line)?))
                 .collect::<Result<_, _>>()?;
         } else if first_line == "Items here:" {
             items = line_group[1..]
                 .iter()
-                .map(|line| String::from(&line[2..]))
-                .collect();
+                .map(|line| strip_list_item(line).map(String::from))
+                .collect::<Result<_, _>>()?;
         }
     }
 
     if name.is_empty() || exits.is_empty() {
-        return Err(SimpleError::new(format!(
+        return Err(SimpleError::runtime(format!(
             "output has no name and/or doors: {room_string}"
         )));
     }
@@ -271,28 +280,43 @@ fn parse_room_state(output: &[i64]) -> Result<RoomState, SimpleError> {
     Ok(RoomState { name, exits, items })
 }
 
-fn ascii_to_string(output: &[i64]) -> String {
-    let mut s = String::new();
-    for &c in output {
-        s.push((c as u8) as char);
-    }
-    s
+fn strip_list_item(line: &str) -> Result<&str, SimpleError> {
+    line.strip_prefix("- ")
+        .ok_or_else(|| SimpleError::runtime(format!("expected a list item in output: {line}")))
 }
 
-pub fn run() -> Result<(), Box<dyn Error>> {
-    let mut args = env::args();
-
-    let program_name = args.next().unwrap();
-    let year = args.next().unwrap();
-    let day = args.next().unwrap();
+fn parse_password(output: &str) -> Result<String, SimpleError> {
+    output
+        .split_whitespace()
+        .skip_while(|&word| word != "typing")
+        .nth(1)
+        .map(String::from)
+        .ok_or_else(|| SimpleError::runtime(format!("no password found in output: {output}")))
+}
 
-    let input_filename = args.next().ok_or_else(|| {
-        SimpleError::new(format!(
-            "USAGE: {program_name} {year} {day} <input_filename>"
-        ))
-    })?;
+pub fn solve(input: &str) -> Result<(String, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
 
-    let input = fs::read_to_string(Path::new(&input_filename))?;
+    Ok((solution1, String::new()))
+}
 
-    solve(&input)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_password() {
+        let output = "== Pressure-Sensitive Floor ==\n\
+            Analyzing...\n\
+            \n\
+            Doors here lead:\n\
+            - west\n\
+            \n\
+            A loud, robotic voice says \"Analysis complete! You may proceed.\" and you enter the cockpit.\n\
+            Santa notices your small droid, looks puzzled for a moment, realizes what has happened, and radios your ship directly.\n\
+            \"Oh, hello! You should be able to get in by typing 2424308736 on the keypad at the main airlock.\"\n";
+
+        assert_eq!(Ok(String::from("2424308736")), parse_password(output));
+        assert!(parse_password("== Security Checkpoint ==\n").is_err());
+    }
 }
@@ -3,9 +3,7 @@
 //! <https://adventofcode.com/2019/day/3>
 
 use crate::SimpleError;
-use std::cmp;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Direction {
@@ -46,7 +44,7 @@ struct PathPart {
 }
 
 fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
-    let (first_path, second_path) = parse_input(input)?;
+    let (first_path, second_path) = crate::timing::parse(|| parse_input(input))?;
 
     let first_touched_points: HashSet<_> =
         build_touched_points(&first_path).keys().copied().collect();
@@ -57,31 +55,30 @@ fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
         .intersection(&second_touched_points)
         .copied()
         .min_by_key(|&point| point.x.abs() + point.y.abs())
-        .unwrap();
+        .ok_or_else(no_intersection)?;
 
     Ok(min_by_distance.x.abs() + min_by_distance.y.abs())
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let (first_path, second_path) = parse_input(input)?;
+    let (first_path, second_path) = crate::timing::parse(|| parse_input(input))?;
 
     let first_touched_points = build_touched_points(&first_path);
     let second_touched_points = build_touched_points(&second_path);
 
-    let intersection: Vec<_> = first_touched_points
-        .keys()
-        .copied()
-        .filter(|&point| second_touched_points.contains_key(&point))
-        .collect();
-
-    let mut min_steps = usize::MAX;
-    for &point in &intersection {
-        let steps = first_touched_points.get(&point).copied().unwrap()
-            + second_touched_points.get(&point).copied().unwrap();
-        min_steps = cmp::min(min_steps, steps);
-    }
+    first_touched_points
+        .iter()
+        .filter_map(|(point, &first_steps)| {
+            second_touched_points
+                .get(point)
+                .map(|&second_steps| first_steps + second_steps)
+        })
+        .min()
+        .ok_or_else(no_intersection)
+}
 
-    Ok(min_steps)
+fn no_intersection() -> SimpleError {
+    SimpleError::unsupported_input(String::from("wires do not cross"))
 }
 
 fn build_touched_points(path: &[PathPart]) -> HashMap<Point, usize> {
@@ -106,9 +103,9 @@ fn build_touched_points(path: &[PathPart]) -> HashMap<Point, usize> {
     touched_points
 }
 
-pub fn solve(input: &str) -> Result<(i64, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -129,12 +126,12 @@ fn parse_input(input: &str) -> Result<(Vec<PathPart>, Vec<PathPart>), SimpleErro
 fn parse_path(line: &str) -> Result<Vec<PathPart>, SimpleError> {
     line.split(',')
         .map(|part| {
-            if part.len() < 2 {
-                return Err(SimpleError::new(format!("path part is too short: {part}")));
-            }
-
-            let direction = Direction::from_char(part.chars().next().unwrap())?;
-            let distance = part[1..].parse()?;
+            let mut chars = part.chars();
+            let direction = chars
+                .next()
+                .ok_or_else(|| SimpleError::new(format!("path part is too short: {part}")))?;
+            let direction = Direction::from_char(direction)?;
+            let distance = chars.as_str().parse()?;
 
             Ok(PathPart {
                 direction,
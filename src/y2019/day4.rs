@@ -3,10 +3,9 @@
 //! <https://adventofcode.com/2019/day/4>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let (start, end) = parse_input(input)?;
+    let (start, end) = crate::timing::parse(|| parse_input(input))?;
 
     let mut valid_count = 0;
     for i in start..=end {
@@ -27,7 +26,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let (start, end) = parse_input(input)?;
+    let (start, end) = crate::timing::parse(|| parse_input(input))?;
 
     let mut valid_count = 0;
     for i in start..=end {
@@ -85,9 +84,9 @@ fn parse_input(input: &str) -> Result<(u32, u32), SimpleError> {
     Ok((start, end))
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::error::Error;
 use std::iter;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -14,21 +13,21 @@ struct OrbitRelation<'a> {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let orbit_relations = parse_input(input)?;
+    let orbit_relations = crate::timing::parse(|| parse_input(input))?;
 
     let orbit_dag = build_orbit_dag(&orbit_relations);
 
     let mut orbit_counts = HashMap::new();
 
     for orbiting_object in orbit_dag.keys() {
-        compute_orbit_counts(orbiting_object, &orbit_dag, &mut orbit_counts);
+        compute_orbit_counts(orbiting_object, &orbit_dag, &mut orbit_counts)?;
     }
 
     Ok(orbit_counts.values().sum())
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let orbit_relations = parse_input(input)?;
+    let orbit_relations = crate::timing::parse(|| parse_input(input))?;
 
     let orbit_dag = build_orbit_dag(&orbit_relations);
     let bidirectional_map = make_bidirectional(&orbit_dag);
@@ -39,20 +38,18 @@ fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
 fn find_shortest_path_to_santa(
     bidirectional_map: &HashMap<String, Vec<String>>,
 ) -> Result<usize, SimpleError> {
-    if !bidirectional_map.contains_key("YOU") {
-        return Err(SimpleError::new(String::from("map does not contain 'YOU'")));
-    }
+    let you_objects = bidirectional_map.get("YOU").ok_or_else(|| {
+        SimpleError::unsupported_input(String::from("map does not contain 'YOU'"))
+    })?;
 
     let mut queue = VecDeque::new();
     let mut visited: HashSet<_> = iter::once(String::from("YOU")).collect();
-    for object in bidirectional_map.get("YOU").unwrap() {
+    for object in you_objects {
         queue.push_back((object.as_str(), 0));
         visited.insert(object.clone());
     }
 
-    while !queue.is_empty() {
-        let (object, steps) = queue.pop_front().unwrap();
-
+    while let Some((object, steps)) = queue.pop_front() {
         if let Some(other_objects) = bidirectional_map.get(object) {
             for other_object in other_objects {
                 if other_object == "SAN" {
@@ -67,7 +64,9 @@ fn find_shortest_path_to_santa(
         }
     }
 
-    Err(SimpleError::new(String::from("no path found to Santa")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no path found to Santa",
+    )))
 }
 
 fn make_bidirectional(orbit_dag: &HashMap<String, String>) -> HashMap<String, Vec<String>> {
@@ -94,21 +93,35 @@ fn compute_orbit_counts(
     object: &str,
     orbit_dag: &HashMap<String, String>,
     orbit_counts: &mut HashMap<String, usize>,
-) -> usize {
-    if let Some(&value) = orbit_counts.get(object) {
-        return value;
-    }
+) -> Result<usize, SimpleError> {
+    // Walk inwards until reaching an object that's already counted or doesn't orbit anything
+    let mut chain = Vec::new();
+    let mut current = object;
+    let mut orbit_count = loop {
+        if let Some(&value) = orbit_counts.get(current) {
+            break value;
+        }
 
-    if !orbit_dag.contains_key(object) {
-        orbit_counts.insert(String::from(object), 0);
-        return 0;
-    }
+        let Some(orbiting) = orbit_dag.get(current) else {
+            orbit_counts.insert(String::from(current), 0);
+            break 0;
+        };
 
-    let orbiting = orbit_dag.get(object).unwrap();
-    let orbit_count = 1 + compute_orbit_counts(orbiting, orbit_dag, orbit_counts);
+        chain.push(current);
+        if chain.len() > orbit_dag.len() {
+            return Err(SimpleError::new(format!(
+                "objects orbit each other in a cycle, including {object}"
+            )));
+        }
+        current = orbiting;
+    };
 
-    orbit_counts.insert(String::from(object), orbit_count);
-    orbit_count
+    for object in chain.into_iter().rev() {
+        orbit_count += 1;
+        orbit_counts.insert(String::from(object), orbit_count);
+    }
+
+    Ok(orbit_count)
 }
 
 fn build_orbit_dag(orbit_relations: &[OrbitRelation]) -> HashMap<String, String> {
@@ -127,22 +140,18 @@ fn parse_input(input: &str) -> Result<Vec<OrbitRelation>, SimpleError> {
     input
         .lines()
         .map(|line| {
-            let close_paren_index = line
-                .chars()
-                .position(|c| c == ')')
+            let (orbited, orbiting) = line
+                .split_once(')')
                 .ok_or_else(|| SimpleError::new(format!("line contains no ')': {line}")))?;
 
-            let orbited = &line[..close_paren_index];
-            let orbiting = &line[close_paren_index + 1..];
-
             Ok(OrbitRelation { orbiting, orbited })
         })
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -163,4 +172,9 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(4), solve_part_2(SAMPLE_INPUT_2));
     }
+
+    #[test]
+    fn test_orbit_cycle() {
+        assert!(solve_part_1("COM)A\nA)B\nB)A").is_err());
+    }
 }
@@ -3,117 +3,70 @@
 //! <https://adventofcode.com/2019/day/7>
 
 use crate::y2019::intcode;
-use crate::y2019::intcode::{InputFn, IntcodeProgram, OutputFn};
+use crate::y2019::intcode::network::{DirectRouter, Framing, Network};
 use crate::SimpleError;
-use std::cell::RefCell;
 use std::cmp;
-use std::collections::VecDeque;
-use std::error::Error;
-use std::rc::Rc;
 
-#[derive(Debug)]
-struct QueueInputFn {
-    input_queue: Rc<RefCell<VecDeque<i64>>>,
-}
-
-impl InputFn for QueueInputFn {
-    fn call(&mut self) -> Option<i64> {
-        self.input_queue.borrow_mut().pop_front()
-    }
-}
-
-#[derive(Debug)]
-struct QueueOutputFn {
-    output_queue: Rc<RefCell<VecDeque<i64>>>,
-}
-
-impl OutputFn for QueueOutputFn {
-    fn call(&mut self, output: i64) {
-        self.output_queue.borrow_mut().push_back(output);
-    }
-}
-
-fn solve_part_1(input: &str) -> Result<i64, Box<dyn Error>> {
+fn solve_part(input: &str, phases: Vec<usize>) -> Result<i64, SimpleError> {
     let program = intcode::parse_program(input)?;
 
-    let phase_permutations = permutations(&(0..5).collect());
-
     let mut max_thruster_signal = 0;
-    for phase_permutation in &phase_permutations {
-        let mut last_amplifier_output = 0;
-
-        for &phase in phase_permutation {
-            let mut program = program.clone();
-            let mut outputs = Vec::new();
-            intcode::execute(
-                &mut program,
-                intcode::iterator_input_fn(vec![phase as i64, last_amplifier_output].into_iter()),
-                |output| outputs.push(output),
-            );
-
-            if outputs.is_empty() {
-                return Err(Box::new(SimpleError::new(format!(
-                    "amplifier returned no output for phase {phase}"
-                ))));
-            }
-
-            last_amplifier_output = outputs[0];
-        }
-
-        max_thruster_signal = cmp::max(max_thruster_signal, last_amplifier_output);
+    for phase_permutation in &permutations(&phases) {
+        let thruster_signal = run_amplifiers(&program, phase_permutation)?;
+        max_thruster_signal = cmp::max(max_thruster_signal, thruster_signal);
     }
 
     Ok(max_thruster_signal)
 }
 
-fn solve_part_2(input: &str) -> Result<i64, Box<dyn Error>> {
-    let program = intcode::parse_program(input)?;
-
-    let phase_permutations = permutations(&(5..10).collect());
-
-    let mut max_thruster_signal = 0;
-    for phase_permutation in &phase_permutations {
-        let mut queues = Vec::new();
-        for &phase in phase_permutation {
-            let mut queue = VecDeque::new();
-            queue.push_back(phase as i64);
-            queues.push(Rc::new(RefCell::new(queue)));
-        }
-
-        queues[0].borrow_mut().push_back(0);
+// Connects the amplifiers in a loop and passes the signal around until they all halt, at which
+// point the last signal from the last amplifier is the one that went to the thrusters. Without
+// feedback each amplifier halts after its first output, so this covers both parts
+fn run_amplifiers(program: &[i64], phases: &[usize]) -> Result<i64, SimpleError> {
+    let last_address = phases.len() as i64 - 1;
 
-        let mut programs = Vec::new();
-        for i in 0..5 {
-            let input_queue = Rc::clone(&queues[i]);
-            let output_queue = Rc::clone(&queues[(i + 1) % queues.len()]);
+    let mut router = DirectRouter::new();
+    for address in 0..=last_address {
+        router.link(address, (address + 1) % phases.len() as i64);
+    }
 
-            let program = IntcodeProgram::new(
-                program.clone(),
-                QueueInputFn { input_queue },
-                QueueOutputFn { output_queue },
-            );
-            programs.push(program);
-        }
+    let mut network = Network::new(Framing::Unaddressed, router);
+    for (address, &phase) in phases.iter().enumerate() {
+        let inputs = if address == 0 {
+            vec![phase as i64, 0]
+        } else {
+            vec![phase as i64]
+        };
+        network.add_machine(address as i64, program.to_vec(), &inputs)?;
+    }
 
-        loop {
-            let mut all_halted = true;
+    network.run_until_idle()?;
 
-            for program in &mut programs {
-                all_halted &= program.execute();
-            }
+    if !network.all_halted() {
+        return Err(SimpleError::runtime(String::from(
+            "amplifier needed more than one input per output",
+        )));
+    }
 
-            if all_halted {
-                break;
-            }
-        }
+    network
+        .log()
+        .iter()
+        .rev()
+        .find(|packet| packet.source == last_address)
+        .map(|packet| packet.payload[0])
+        .ok_or_else(|| {
+            SimpleError::runtime(format!(
+                "last amplifier returned no output for phases {phases:?}"
+            ))
+        })
+}
 
-        let thruster_signal = queues[0].borrow_mut().pop_front().ok_or_else(|| {
-            SimpleError::new(String::from("programs did not produce a thruster signal"))
-        })?;
-        max_thruster_signal = cmp::max(max_thruster_signal, thruster_signal);
-    }
+fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
+    solve_part(input, (0..5).collect())
+}
 
-    Ok(max_thruster_signal)
+fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
+    solve_part(input, (5..10).collect())
 }
 
 fn permutations(numbers: &Vec<usize>) -> Vec<Vec<usize>> {
@@ -133,9 +86,9 @@ fn permutations(numbers: &Vec<usize>) -> Vec<Vec<usize>> {
     result
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
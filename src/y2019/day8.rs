@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2019/day/8>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
     let line = crate::read_single_line(input)?;
@@ -48,7 +47,9 @@ fn solve_part_2(input: &str) -> Result<String, SimpleError> {
                 .step_by(25 * 6)
                 .find(|&c| c != '2')
                 .ok_or_else(|| {
-                    SimpleError::new(format!("no visible pixel at row={row}, col={col}"))
+                    SimpleError::unsupported_input(format!(
+                        "no visible pixel at row={row}, col={col}"
+                    ))
                 })?;
             *value = c == '1';
         }
@@ -72,9 +73,9 @@ fn solve_part_2(input: &str) -> Result<String, SimpleError> {
     Ok(image_str)
 }
 
-pub fn solve(input: &str) -> Result<(usize, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -1,6 +1,22 @@
+pub mod ascii;
+pub mod assembler;
+pub mod debugger;
+pub mod disassembler;
+pub mod memory;
+pub mod network;
+pub mod screen;
+pub mod snapshot;
+pub mod tracer;
+
+use crate::simpleerror::ErrorKind;
+use crate::SimpleError;
+use memory::{Decoded, Memory};
+use snapshot::Snapshot;
+use std::cell::Cell;
 use std::collections::VecDeque;
 use std::error::Error;
-use std::iter;
+use std::fmt::{Display, Formatter};
+use tracer::{TraceEntry, Tracer};
 
 const ADD_OPCODE: i64 = 1;
 const MULTIPLY_OPCODE: i64 = 2;
@@ -17,6 +33,20 @@ const POSITION_MODE: i64 = 0;
 const IMMEDIATE_MODE: i64 = 1;
 const RELATIVE_MODE: i64 = 2;
 
+thread_local! {
+    static PREDECODE: Cell<bool> = const { Cell::new(true) };
+}
+
+// Runs f with every Intcode program created on the current thread splitting instructions into an
+// opcode and parameter modes every time they're executed, rather than using the decoded words that
+// memory keeps alongside each page. Only used to compare the two
+pub fn without_predecode<T>(f: impl FnOnce() -> T) -> T {
+    let previous = PREDECODE.replace(false);
+    let result = f();
+    PREDECODE.set(previous);
+    result
+}
+
 pub trait InputFn {
     fn call(&mut self) -> Option<i64>;
 }
@@ -37,137 +67,446 @@ impl<T: FnMut(i64)> OutputFn for T {
     }
 }
 
+// Programs stop with StepLimitReached after this many instructions unless given a different
+// limit, so that a program stuck in a loop can't hang the runner
+pub const DEFAULT_STEP_LIMIT: u64 = 1_000_000_000;
+
+// The result of executing a single instruction
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Step {
+    Executed,
+    // An output instruction was executed
+    Output(i64),
+    Halted,
+    NeedsInput,
+}
+
+impl Step {
+    pub fn executed(self) -> bool {
+        matches!(self, Self::Executed | Self::Output(_))
+    }
+}
+
+// Why a program stopped running
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RunState {
+    Halted,
+    NeedsInput,
+    // Only returned by run_until_output
+    Output(i64),
+    StepLimitReached,
+}
+
+impl RunState {
+    // For callers that can't do anything useful with a program that ran out of steps
+    pub fn check_step_limit(self) -> Result<Self, SimpleError> {
+        match self {
+            Self::StepLimitReached => Err(SimpleError::runtime(String::from(
+                "Intcode program reached its step limit",
+            ))),
+            _ => Ok(self),
+        }
+    }
+}
+
+// Why an Intcode instruction couldn't be executed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IntcodeErrorReason {
+    UnknownOpcode,
+    UnknownParameterMode(i64),
+    ImmediateModeWrite,
+    NegativeAddress(i64),
+    // The write would allocate more memory than the limit, in words
+    MemoryLimitExceeded(usize),
+    // An addition, multiplication or relative address doesn't fit in an i64
+    Overflow,
+}
+
+impl Display for IntcodeErrorReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownOpcode => write!(f, "unknown opcode"),
+            Self::UnknownParameterMode(mode) => write!(f, "unknown parameter mode {mode}"),
+            Self::ImmediateModeWrite => write!(f, "write parameter in immediate mode"),
+            Self::NegativeAddress(address) => write!(f, "negative address {address}"),
+            Self::MemoryLimitExceeded(limit) => {
+                write!(f, "memory limit of {limit} words exceeded")
+            }
+            Self::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct IntcodeError {
+    pub ip: usize,
+    // The opcode part of the instruction, i.e. without the parameter modes
+    pub opcode: i64,
+    pub reason: IntcodeErrorReason,
+}
+
+impl Display for IntcodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid Intcode instruction at ip {} (opcode {}): {}",
+            self.ip, self.opcode, self.reason
+        )
+    }
+}
+
+impl Error for IntcodeError {}
+
+impl From<IntcodeError> for SimpleError {
+    fn from(err: IntcodeError) -> Self {
+        SimpleError::with_source(
+            ErrorKind::Runtime,
+            String::from("Intcode program failed"),
+            err,
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IntcodeProgram<I: InputFn, O: OutputFn> {
-    program: Vec<i64>,
+    program: Memory,
     ip: usize,
     input_fn: I,
     output_fn: O,
     relative_base: i64,
+    steps: u64,
+    step_limit: Option<u64>,
+    tracer: Option<Box<Tracer>>,
+    // Whether instructions come from memory's decoded words, see without_predecode()
+    predecode: bool,
 }
 
 impl<I: InputFn, O: OutputFn> IntcodeProgram<I, O> {
     pub fn new(program: Vec<i64>, input_fn: I, output_fn: O) -> Self {
+        let program = Memory::new(&program);
+        let tracer = tracer::scoped_tracer().map(|mut tracer| {
+            tracer.attach(&program);
+            Box::new(tracer)
+        });
+
         Self {
             program,
             ip: 0,
             input_fn,
             output_fn,
             relative_base: 0,
+            steps: 0,
+            step_limit: Some(DEFAULT_STEP_LIMIT),
+            tracer,
+            predecode: PREDECODE.get(),
+        }
+    }
+
+    fn error(&self, reason: IntcodeErrorReason) -> IntcodeError {
+        IntcodeError {
+            ip: self.ip,
+            opcode: self.word(self.ip) % 100,
+            reason,
         }
     }
 
-    fn read_value(&self, index: usize, parameter_mode: i64) -> i64 {
-        let address = match parameter_mode % 10 {
-            POSITION_MODE => self.program[index] as usize,
-            IMMEDIATE_MODE => index,
-            RELATIVE_MODE => (self.relative_base + self.program[index]) as usize,
-            _ => panic!("unexpected parameter mode: {}", parameter_mode % 10),
+    // Memory past the end of the program reads as 0
+    fn word(&self, index: usize) -> i64 {
+        self.program.get(index)
+    }
+
+    fn to_address(&self, address: i64) -> Result<usize, IntcodeError> {
+        usize::try_from(address)
+            .map_err(|_| self.error(IntcodeErrorReason::NegativeAddress(address)))
+    }
+
+    fn relative_address(&self, parameter: i64) -> Result<usize, IntcodeError> {
+        let address = self.checked(self.relative_base.checked_add(parameter))?;
+        self.to_address(address)
+    }
+
+    // Fails with Overflow if an arithmetic result is None
+    fn checked(&self, result: Option<i64>) -> Result<i64, IntcodeError> {
+        result.ok_or_else(|| self.error(IntcodeErrorReason::Overflow))
+    }
+
+    // parameter is the parameter's word in the instruction, not its address
+    fn read_value(&self, parameter: i64, parameter_mode: u8) -> Result<i64, IntcodeError> {
+        let address = match i64::from(parameter_mode) {
+            POSITION_MODE => self.to_address(parameter)?,
+            IMMEDIATE_MODE => return Ok(parameter),
+            RELATIVE_MODE => self.relative_address(parameter)?,
+            mode => return Err(self.error(IntcodeErrorReason::UnknownParameterMode(mode))),
         };
 
-        self.program.get(address).copied().unwrap_or(0)
+        Ok(self.word(address))
     }
 
-    fn write_value(&mut self, index: usize, value: i64, parameter_mode: i64) {
-        let address = match parameter_mode % 10 {
-            POSITION_MODE => self.program[index] as usize,
-            IMMEDIATE_MODE => panic!("immediate parameter mode not supported for writes"),
-            RELATIVE_MODE => (self.relative_base + self.program[index]) as usize,
-            _ => panic!("unexpected parameter mode: {}", parameter_mode % 10),
+    fn write_value(
+        &mut self,
+        parameter: i64,
+        value: i64,
+        parameter_mode: u8,
+    ) -> Result<(), IntcodeError> {
+        let address = match i64::from(parameter_mode) {
+            POSITION_MODE => self.to_address(parameter)?,
+            IMMEDIATE_MODE => return Err(self.error(IntcodeErrorReason::ImmediateModeWrite)),
+            RELATIVE_MODE => self.relative_address(parameter)?,
+            mode => return Err(self.error(IntcodeErrorReason::UnknownParameterMode(mode))),
         };
 
-        if address >= self.program.len() {
-            let expansion_size = address - self.program.len() + 1;
-            self.program.extend(iter::repeat(0).take(expansion_size));
+        self.program
+            .set(address, value)
+            .map_err(|reason| self.error(reason))
+    }
+
+    // Runs until the program halts, needs input, or reaches its step limit
+    pub fn execute(&mut self) -> Result<RunState, IntcodeError> {
+        self.run(false)
+    }
+
+    // Like execute, but also stops after each output. The output is still passed to the output fn
+    pub fn run_until_output(&mut self) -> Result<RunState, IntcodeError> {
+        self.run(true)
+    }
+
+    fn run(&mut self, stop_on_output: bool) -> Result<RunState, IntcodeError> {
+        loop {
+            if self.step_limit.is_some_and(|limit| self.steps >= limit) {
+                return Ok(RunState::StepLimitReached);
+            }
+
+            match self.step()? {
+                Step::Executed => {}
+                Step::Output(output) if stop_on_output => return Ok(RunState::Output(output)),
+                Step::Output(_) => {}
+                Step::Halted => return Ok(RunState::Halted),
+                Step::NeedsInput => return Ok(RunState::NeedsInput),
+            }
         }
+    }
 
-        self.program[address] = value;
+    // Executes a single instruction
+    pub fn step(&mut self) -> Result<Step, IntcodeError> {
+        match self.tracer.take() {
+            Some(mut tracer) => {
+                let step = self.traced_step(&mut tracer);
+                self.tracer = Some(tracer);
+                step
+            }
+            None => self.execute_instruction(),
+        }
     }
 
-    pub fn execute(&mut self) -> bool {
-        while self.ip < self.program.len() {
-            let parameter_modes = self.program[self.ip] / 100;
-            let opcode = self.program[self.ip] % 100;
-            match opcode {
-                ADD_OPCODE => {
-                    let a = self.read_value(self.ip + 1, parameter_modes);
-                    let b = self.read_value(self.ip + 2, parameter_modes / 10);
+    fn traced_step(&mut self, tracer: &mut Tracer) -> Result<Step, IntcodeError> {
+        let ip = self.ip;
+        if !tracer.wants_entries() {
+            let step = self.execute_instruction()?;
+            if step.executed() {
+                tracer.record_ip(ip);
+            }
+            return Ok(step);
+        }
 
-                    self.write_value(self.ip + 3, a + b, parameter_modes / 100);
+        let Some(instruction) = self.program.decode(ip) else {
+            return self.execute_instruction();
+        };
 
-                    self.ip += 4;
-                }
-                MULTIPLY_OPCODE => {
-                    let a = self.read_value(self.ip + 1, parameter_modes);
-                    let b = self.read_value(self.ip + 2, parameter_modes / 10);
+        let relative_base = self.relative_base;
+        let write_parameter = instruction.opcode.write_parameter();
+        let operands = instruction
+            .parameters
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| Some(i) != write_parameter)
+            .map(|(_, parameter)| parameter.read(&self.program, relative_base))
+            .collect();
+        let write_address =
+            write_parameter.and_then(|i| instruction.parameters[i].address(relative_base));
+
+        let step = self.execute_instruction()?;
+        if step.executed() {
+            tracer.record(TraceEntry {
+                step: tracer.steps(),
+                ip,
+                relative_base,
+                instruction,
+                operands,
+                write: write_address.map(|address| (address, self.program.get(address))),
+            });
+        }
+        Ok(step)
+    }
 
-                    self.write_value(self.ip + 3, a * b, parameter_modes / 100);
+    fn execute_instruction(&mut self) -> Result<Step, IntcodeError> {
+        let step = self.decode_and_execute()?;
+        if step.executed() {
+            self.steps += 1;
+        }
+        Ok(step)
+    }
 
-                    self.ip += 4;
-                }
-                INPUT_OPCODE => {
-                    let input = self.input_fn.call();
-                    if input.is_none() {
-                        return false;
-                    }
+    fn decode_and_execute(&mut self) -> Result<Step, IntcodeError> {
+        if self.ip >= self.program.len() {
+            return Ok(Step::Halted);
+        }
+
+        let (decoded, parameters) = if self.predecode {
+            self.program.fetch(self.ip)
+        } else {
+            let [word, parameters @ ..] = self.program.fetch_words(self.ip);
+            (Decoded::from_word(word), parameters)
+        };
+        let Some(Decoded { opcode, modes }) = decoded else {
+            return Err(self.error(IntcodeErrorReason::UnknownOpcode));
+        };
 
-                    self.write_value(self.ip + 1, input.unwrap(), parameter_modes);
+        match i64::from(opcode) {
+            ADD_OPCODE => {
+                let a = self.read_value(parameters[0], modes[0])?;
+                let b = self.read_value(parameters[1], modes[1])?;
 
-                    self.ip += 2;
-                }
-                OUTPUT_OPCODE => {
-                    let a = self.read_value(self.ip + 1, parameter_modes);
-                    self.output_fn.call(a);
+                let c = self.checked(a.checked_add(b))?;
+                self.write_value(parameters[2], c, modes[2])?;
 
-                    self.ip += 2;
-                }
-                JUMP_IF_TRUE_OPCODE => {
-                    if self.read_value(self.ip + 1, parameter_modes) != 0 {
-                        self.ip = self.read_value(self.ip + 2, parameter_modes / 10) as usize;
-                    } else {
-                        self.ip += 3;
-                    }
-                }
-                JUMP_IF_FALSE_OPCODE => {
-                    if self.read_value(self.ip + 1, parameter_modes) == 0 {
-                        self.ip = self.read_value(self.ip + 2, parameter_modes / 10) as usize;
-                    } else {
-                        self.ip += 3;
-                    }
-                }
-                LESS_THAN_OPCODE => {
-                    let a = self.read_value(self.ip + 1, parameter_modes);
-                    let b = self.read_value(self.ip + 2, parameter_modes / 10);
+                self.ip += 4;
+            }
+            MULTIPLY_OPCODE => {
+                let a = self.read_value(parameters[0], modes[0])?;
+                let b = self.read_value(parameters[1], modes[1])?;
 
-                    let c = i64::from(a < b);
-                    self.write_value(self.ip + 3, c, parameter_modes / 100);
+                let c = self.checked(a.checked_mul(b))?;
+                self.write_value(parameters[2], c, modes[2])?;
 
-                    self.ip += 4;
+                self.ip += 4;
+            }
+            INPUT_OPCODE => {
+                let input = self.input_fn.call();
+                if input.is_none() {
+                    return Ok(Step::NeedsInput);
                 }
-                EQUAL_OPCODE => {
-                    let a = self.read_value(self.ip + 1, parameter_modes);
-                    let b = self.read_value(self.ip + 2, parameter_modes / 10);
 
-                    let c = i64::from(a == b);
-                    self.write_value(self.ip + 3, c, parameter_modes / 100);
+                self.write_value(parameters[0], input.unwrap(), modes[0])?;
 
-                    self.ip += 4;
-                }
-                ADJUST_RELATIVE_BASE_OPCODE => {
-                    let a = self.read_value(self.ip + 1, parameter_modes);
+                self.ip += 2;
+            }
+            OUTPUT_OPCODE => {
+                let a = self.read_value(parameters[0], modes[0])?;
+                self.output_fn.call(a);
 
-                    self.relative_base += a;
+                self.ip += 2;
 
-                    self.ip += 2;
+                return Ok(Step::Output(a));
+            }
+            JUMP_IF_TRUE_OPCODE => {
+                if self.read_value(parameters[0], modes[0])? != 0 {
+                    self.ip = self.to_address(self.read_value(parameters[1], modes[1])?)?;
+                } else {
+                    self.ip += 3;
                 }
-                HALT_OPCODE => {
-                    return true;
+            }
+            JUMP_IF_FALSE_OPCODE => {
+                if self.read_value(parameters[0], modes[0])? == 0 {
+                    self.ip = self.to_address(self.read_value(parameters[1], modes[1])?)?;
+                } else {
+                    self.ip += 3;
                 }
-                _ => panic!("invalid opcode: {opcode}"),
             }
+            LESS_THAN_OPCODE => {
+                let a = self.read_value(parameters[0], modes[0])?;
+                let b = self.read_value(parameters[1], modes[1])?;
+
+                let c = i64::from(a < b);
+                self.write_value(parameters[2], c, modes[2])?;
+
+                self.ip += 4;
+            }
+            EQUAL_OPCODE => {
+                let a = self.read_value(parameters[0], modes[0])?;
+                let b = self.read_value(parameters[1], modes[1])?;
+
+                let c = i64::from(a == b);
+                self.write_value(parameters[2], c, modes[2])?;
+
+                self.ip += 4;
+            }
+            ADJUST_RELATIVE_BASE_OPCODE => {
+                let a = self.read_value(parameters[0], modes[0])?;
+
+                self.relative_base = self.checked(self.relative_base.checked_add(a))?;
+
+                self.ip += 2;
+            }
+            HALT_OPCODE => {
+                return Ok(Step::Halted);
+            }
+            _ => return Err(self.error(IntcodeErrorReason::UnknownOpcode)),
         }
 
-        true
+        Ok(Step::Executed)
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn memory(&self) -> &Memory {
+        &self.program
+    }
+
+    // Instructions executed so far
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn step_limit(&self) -> Option<u64> {
+        self.step_limit
+    }
+
+    // Limits the total number of instructions executed by execute and run_until_output, or
+    // removes the limit if None
+    pub fn set_step_limit(&mut self, step_limit: Option<u64>) {
+        self.step_limit = step_limit;
+    }
+
+    // Limits how many words of memory the program can allocate, or removes the limit if None.
+    // Writes that would go over the limit fail with MemoryLimitExceeded
+    pub fn set_memory_limit(&mut self, memory_limit: Option<usize>) {
+        self.program.set_limit(memory_limit);
+    }
+
+    // Starts tracing every executed instruction, replacing any previous tracer
+    pub fn set_tracer(&mut self, mut tracer: Tracer) {
+        if let Some(mut previous) = self.tracer.take() {
+            tracer::detach(&mut previous);
+        }
+
+        tracer.attach(&self.program);
+        self.tracer = Some(Box::new(tracer));
+    }
+
+    pub fn tracer(&self) -> Option<&Tracer> {
+        self.tracer.as_deref()
+    }
+
+    // Stops tracing, returning the tracer so that its entries and profile can be inspected
+    pub fn take_tracer(&mut self) -> Option<Tracer> {
+        self.tracer.take().map(|mut tracer| {
+            tracer::detach(&mut tracer);
+            *tracer
+        })
+    }
+}
+
+impl<I: InputFn, O: OutputFn> Drop for IntcodeProgram<I, O> {
+    fn drop(&mut self) {
+        if let Some(tracer) = &mut self.tracer {
+            tracer::detach(tracer);
+        }
     }
 }
 
@@ -212,19 +551,56 @@ impl InteractiveIntcodeProgram {
         Self { program }
     }
 
+    pub fn from_snapshot(snapshot: &Snapshot) -> Self {
+        let mut program = Self::new(Vec::new());
+        program.restore(snapshot);
+        program
+    }
+
+    // A copy of this program that can run independently. Memory is shared until either copy
+    // writes to it, so this is cheap
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            memory: self.program.program.clone(),
+            ip: self.program.ip,
+            relative_base: self.program.relative_base,
+            steps: self.program.steps,
+            inputs: self.program.input_fn.inputs.clone(),
+            outputs: self.program.output_fn.outputs.clone(),
+        }
+    }
+
+    // Returns the program to the state it was in when the snapshot was taken. The step limit,
+    // memory limit and tracer are left as they are
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        let memory_limit = self.program.program.limit();
+        self.program.program = snapshot.memory.clone();
+        self.program.program.set_limit(memory_limit);
+        self.program.ip = snapshot.ip;
+        self.program.relative_base = snapshot.relative_base;
+        self.program.steps = snapshot.steps;
+        self.program.input_fn.inputs = snapshot.inputs.clone();
+        self.program.output_fn.outputs = snapshot.outputs.clone();
+    }
+
     pub fn push_input(&mut self, input: i64) {
         self.program.input_fn.inputs.push_back(input);
     }
 
-    pub fn push_line_as_ascii(&mut self, line: &str) {
-        if line.chars().any(|c| !c.is_ascii()) {
-            panic!("unexpected non-ASCII char in line: {line}");
+    pub fn push_line_as_ascii(&mut self, line: &str) -> Result<(), SimpleError> {
+        if !line.is_ascii() {
+            return Err(SimpleError::runtime(format!("line is not ASCII: {line}")));
         }
 
         for c in line.chars() {
             self.push_input(c as i64);
         }
         self.push_input('\n' as i64);
+        Ok(())
     }
 
     pub fn fetch_outputs(&mut self) -> Vec<i64> {
@@ -236,38 +612,126 @@ impl InteractiveIntcodeProgram {
         outputs
     }
 
-    pub fn execute(&mut self) -> bool {
+    pub fn execute(&mut self) -> Result<RunState, IntcodeError> {
         self.program.execute()
     }
+
+    // Like execute, but also stops after each output. The output is returned rather than queued
+    pub fn run_until_output(&mut self) -> Result<RunState, IntcodeError> {
+        let state = self.program.run_until_output()?;
+        if let RunState::Output(_) = state {
+            self.program.output_fn.outputs.pop_back();
+        }
+        Ok(state)
+    }
+
+    pub fn step(&mut self) -> Result<Step, IntcodeError> {
+        self.program.step()
+    }
+
+    pub fn ip(&self) -> usize {
+        self.program.ip()
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.program.relative_base()
+    }
+
+    pub fn memory(&self) -> &Memory {
+        self.program.memory()
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.program.steps()
+    }
+
+    pub fn step_limit(&self) -> Option<u64> {
+        self.program.step_limit()
+    }
+
+    pub fn set_step_limit(&mut self, step_limit: Option<u64>) {
+        self.program.set_step_limit(step_limit);
+    }
+
+    pub fn set_memory_limit(&mut self, memory_limit: Option<usize>) {
+        self.program.set_memory_limit(memory_limit);
+    }
+
+    pub fn set_tracer(&mut self, tracer: Tracer) {
+        self.program.set_tracer(tracer);
+    }
+
+    pub fn tracer(&self) -> Option<&Tracer> {
+        self.program.tracer()
+    }
+
+    pub fn take_tracer(&mut self) -> Option<Tracer> {
+        self.program.take_tracer()
+    }
 }
 
-pub fn execute(program: &mut Vec<i64>, input_fn: impl InputFn, output_fn: impl OutputFn) {
+// Runs the program until it halts, failing if it stops for any other reason
+pub fn execute(
+    program: &mut Vec<i64>,
+    input_fn: impl InputFn,
+    output_fn: impl OutputFn,
+) -> Result<(), SimpleError> {
     let mut intcode_program = IntcodeProgram::new(program.clone(), input_fn, output_fn);
-    intcode_program.execute();
-    *program = intcode_program.program;
+    if intcode_program.execute()?.check_step_limit()? != RunState::Halted {
+        return Err(SimpleError::runtime(String::from(
+            "Intcode program stopped before halting",
+        )));
+    }
+
+    // Memory is sparse, so a write to a high address could make it far too long to copy back
+    let memory = &intcode_program.program;
+    if memory.limit().is_some_and(|limit| memory.len() > limit) {
+        return Err(SimpleError::runtime(format!(
+            "Intcode program memory is too long to return ({} words)",
+            memory.len()
+        )));
+    }
+
+    *program = memory.to_vec();
+    Ok(())
 }
 
-pub fn execute_no_io(program: &mut Vec<i64>) {
-    execute(
-        program,
-        || panic!("did not expect input fn to get called"),
-        |_| panic!("did not expect output fn to get called"),
-    );
+pub fn execute_no_io(program: &mut Vec<i64>) -> Result<(), SimpleError> {
+    let mut output_count = 0;
+    execute(program, iterator_input_fn(std::iter::empty()), |_| {
+        output_count += 1;
+    })?;
+
+    if output_count > 0 {
+        return Err(SimpleError::runtime(format!(
+            "Intcode program output {output_count} values but should not output anything"
+        )));
+    }
+
+    Ok(())
 }
 
-pub fn iterator_input_fn(mut iter: impl Iterator<Item = i64>) -> impl InputFn {
-    move || {
-        iter.next()
-            .expect("input fn called after iterator was exhausted")
+struct IteratorInputFn<T>(T);
+
+impl<T: Iterator<Item = i64>> InputFn for IteratorInputFn<T> {
+    fn call(&mut self) -> Option<i64> {
+        self.0.next()
     }
 }
 
-pub fn parse_program(input: &str) -> Result<Vec<i64>, Box<dyn Error>> {
-    let result: Result<Vec<_>, _> = crate::read_single_line(input)?
-        .split(',')
-        .map(|n| n.parse::<i64>())
-        .collect();
-    Ok(result?)
+// Once the iterator runs out, the program stops with NeedsInput
+pub fn iterator_input_fn(iter: impl Iterator<Item = i64>) -> impl InputFn {
+    IteratorInputFn(iter)
+}
+
+pub fn parse_program(input: &str) -> Result<Vec<i64>, SimpleError> {
+    crate::timing::parse(|| {
+        let result: Result<Vec<_>, _> = crate::read_single_line(input)?
+            .split(',')
+            .map(|n| n.parse::<i64>())
+            .collect();
+        Ok(result?)
+    })
 }
 
 #[cfg(test)]
@@ -277,7 +741,7 @@ mod tests {
     fn execute_with_input(program: &Vec<i64>, input: impl FnMut() -> i64) -> Vec<i64> {
         let mut program = program.clone();
         let mut outputs = Vec::new();
-        execute(&mut program, input, |output| outputs.push(output));
+        execute(&mut program, input, |output| outputs.push(output)).unwrap();
         outputs
     }
 
@@ -288,24 +752,37 @@ mod tests {
     #[test]
     fn test_add() {
         let mut program = vec![1, 0, 0, 0, 99];
-        execute_no_io(&mut program);
+        execute_no_io(&mut program).unwrap();
         assert_eq!(vec![2, 0, 0, 0, 99], program);
     }
 
     #[test]
     fn test_multiply() {
         let mut program = vec![2, 3, 0, 3, 99];
-        execute_no_io(&mut program);
+        execute_no_io(&mut program).unwrap();
         assert_eq!(vec![2, 3, 0, 6, 99], program);
     }
 
     #[test]
     fn test_parameter_modes() {
         let mut program = vec![1002, 4, 3, 4, 33];
-        execute_no_io(&mut program);
+        execute_no_io(&mut program).unwrap();
         assert_eq!(vec![1002, 4, 3, 4, 99], program);
     }
 
+    #[test]
+    fn test_without_predecode() {
+        // Self-modifying: the first instruction turns the second from an add into a multiply
+        let program = vec![1101, 1, 1, 4, 1, 0, 0, 0, 99];
+        let mut interactive = without_predecode(|| InteractiveIntcodeProgram::new(program));
+        assert!(!interactive.program.predecode);
+        assert_eq!(Ok(RunState::Halted), interactive.execute());
+        assert_eq!(1101 * 1101, interactive.memory().get(0));
+
+        // Only programs created inside of without_predecode() are affected
+        assert!(InteractiveIntcodeProgram::new(Vec::new()).program.predecode);
+    }
+
     #[test]
     fn test_input() {
         let mut program = vec![3, 3, 99, 0];
@@ -313,7 +790,8 @@ mod tests {
             &mut program,
             || 5,
             |_| panic!("output should not be called"),
-        );
+        )
+        .unwrap();
         assert_eq!(vec![3, 3, 99, 5], program);
     }
 
@@ -325,7 +803,8 @@ mod tests {
             &mut program,
             || panic!("input should not be called"),
             |output| outputs.push(output),
-        );
+        )
+        .unwrap();
         assert_eq!(vec![200], outputs);
         assert_eq!(vec![4, 3, 99, 200], program);
     }
@@ -428,24 +907,192 @@ mod tests {
         assert_eq!(vec![1125899906842624], outputs);
     }
 
+    #[test]
+    fn test_run_until_output() {
+        // Echoes inputs until it reads a 0
+        let mut program = InteractiveIntcodeProgram::new(vec![3, 9, 4, 9, 1005, 9, 0, 99, 0, 0]);
+        assert_eq!(Ok(RunState::NeedsInput), program.run_until_output());
+
+        program.push_input(5);
+        program.push_input(6);
+        assert_eq!(Ok(RunState::Output(5)), program.run_until_output());
+        assert_eq!(Ok(RunState::Output(6)), program.run_until_output());
+        assert!(program.fetch_outputs().is_empty());
+
+        program.push_input(0);
+        assert_eq!(Ok(RunState::Output(0)), program.run_until_output());
+        assert_eq!(Ok(RunState::Halted), program.run_until_output());
+
+        let mut program = InteractiveIntcodeProgram::new(vec![3, 9, 4, 9, 1005, 9, 0, 99, 0, 0]);
+        program.push_input(7);
+        assert_eq!(Ok(RunState::NeedsInput), program.execute());
+        assert_eq!(vec![7], program.fetch_outputs());
+    }
+
+    #[test]
+    fn test_step_limit() {
+        // Loops forever
+        let looping = vec![1105, 1, 0];
+
+        let mut program = InteractiveIntcodeProgram::new(looping.clone());
+        program.set_step_limit(Some(100));
+        assert_eq!(Ok(RunState::StepLimitReached), program.execute());
+        assert_eq!(100, program.steps());
+
+        // Raising the limit lets it continue
+        program.set_step_limit(Some(150));
+        assert_eq!(Ok(RunState::StepLimitReached), program.run_until_output());
+        assert_eq!(150, program.steps());
+
+        assert!(RunState::StepLimitReached.check_step_limit().is_err());
+        assert_eq!(Ok(RunState::Halted), RunState::Halted.check_step_limit());
+
+        let mut program = vec![1101, 1, 1, 5, 99, 0];
+        execute_no_io(&mut program).unwrap();
+        assert_eq!(2, program[5]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |program: Vec<i64>| {
+            let mut program = InteractiveIntcodeProgram::new(program);
+            program.push_input(1);
+            program.execute().unwrap_err()
+        };
+
+        assert_eq!(
+            IntcodeError {
+                ip: 2,
+                opcode: 42,
+                reason: IntcodeErrorReason::UnknownOpcode,
+            },
+            error(vec![104, 5, 42])
+        );
+        assert_eq!(
+            IntcodeError {
+                ip: 0,
+                opcode: 4,
+                reason: IntcodeErrorReason::UnknownParameterMode(3),
+            },
+            error(vec![304, 0, 99])
+        );
+        assert_eq!(
+            IntcodeError {
+                ip: 0,
+                opcode: 3,
+                reason: IntcodeErrorReason::ImmediateModeWrite,
+            },
+            error(vec![103, 0, 99])
+        );
+        assert_eq!(
+            IntcodeError {
+                ip: 2,
+                opcode: 1,
+                reason: IntcodeErrorReason::NegativeAddress(-3),
+            },
+            error(vec![109, -5, 2201, 2, 0, 0, 99])
+        );
+        assert_eq!(
+            IntcodeError {
+                ip: 0,
+                opcode: 5,
+                reason: IntcodeErrorReason::NegativeAddress(-1),
+            },
+            error(vec![1105, 1, -1])
+        );
+
+        assert_eq!(
+            IntcodeError {
+                ip: 0,
+                opcode: 1,
+                reason: IntcodeErrorReason::Overflow,
+            },
+            error(vec![1101, i64::MAX, 1, 5, 99, 0])
+        );
+        assert_eq!(
+            IntcodeError {
+                ip: 0,
+                opcode: 2,
+                reason: IntcodeErrorReason::Overflow,
+            },
+            error(vec![1102, i64::MAX, 2, 5, 99, 0])
+        );
+        assert_eq!(
+            IntcodeError {
+                ip: 2,
+                opcode: 1,
+                reason: IntcodeErrorReason::Overflow,
+            },
+            error(vec![109, i64::MAX, 22201, 1, 1, 0, 99])
+        );
+        assert_eq!(
+            IntcodeError {
+                ip: 2,
+                opcode: 9,
+                reason: IntcodeErrorReason::Overflow,
+            },
+            error(vec![109, i64::MAX, 109, 1, 99])
+        );
+
+        // Programs run out of input rather than panicking
+        let mut program = vec![3, 0, 99];
+        assert!(execute(&mut program, iterator_input_fn([].into_iter()), |_| {}).is_err());
+        assert!(execute_no_io(&mut program).is_err());
+
+        let err = SimpleError::from(error(vec![-1]));
+        assert_eq!(ErrorKind::Runtime, err.kind());
+        assert_eq!(
+            "invalid Intcode instruction at ip 0 (opcode -1): unknown opcode",
+            err.source().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        // Writes to two distant addresses and then outputs the first
+        let program = vec![1101, 1, 2, 1 << 40, 1101, 3, 4, 1 << 50, 4, 1 << 40, 99];
+
+        let mut unlimited = InteractiveIntcodeProgram::new(program.clone());
+        unlimited.set_memory_limit(None);
+        assert_eq!(Ok(RunState::Halted), unlimited.execute());
+        assert_eq!(vec![3], unlimited.fetch_outputs());
+        assert_eq!(7, unlimited.memory().get(1 << 50));
+
+        let mut limited = InteractiveIntcodeProgram::new(program.clone());
+        limited.set_memory_limit(Some(2 * memory::PAGE_SIZE));
+        assert_eq!(
+            Err(IntcodeError {
+                ip: 4,
+                opcode: 1,
+                reason: IntcodeErrorReason::MemoryLimitExceeded(2 * memory::PAGE_SIZE),
+            }),
+            limited.execute()
+        );
+
+        // The memory can't be copied back into a Vec
+        let mut program = program;
+        program[8] = 99;
+        assert!(execute_no_io(&mut program).is_err());
+    }
+
     #[test]
     fn test_day2_sample_input_1() {
         let mut program = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
-        execute_no_io(&mut program);
+        execute_no_io(&mut program).unwrap();
         assert_eq!(vec![3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50], program);
     }
 
     #[test]
     fn test_day2_sample_input_2() {
         let mut program = vec![2, 4, 4, 5, 99, 0];
-        execute_no_io(&mut program);
+        execute_no_io(&mut program).unwrap();
         assert_eq!(vec![2, 4, 4, 5, 99, 9801], program);
     }
 
     #[test]
     fn test_day2_sample_input_3() {
         let mut program = vec![1, 1, 1, 4, 99, 5, 6, 0, 99];
-        execute_no_io(&mut program);
+        execute_no_io(&mut program).unwrap();
         assert_eq!(vec![30, 1, 1, 4, 2, 5, 6, 0, 99], program);
     }
 
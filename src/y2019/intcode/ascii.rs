@@ -0,0 +1,238 @@
+//! ASCII terminals for Intcode programs that read and write text, like the droids in 2019 days 17,
+//! 21 and 25.
+//!
+//! An [`AsciiProgram`] sends input a line at a time and reads output back as text. Programs that
+//! finish with a value that isn't ASCII, usually the puzzle answer, get that value split off from
+//! the text:
+//! ```text
+//! let mut program = AsciiProgram::new(program);
+//! program.read_until("Input instructions:\n")?;
+//! program.send_line("WALK")?;
+//! let output = program.run()?;
+//! // output.text is the droid's last moments, output.value is Some(19354437)
+//! ```
+//!
+//! [`AsciiProgram::play`] connects a program to a terminal, to play text adventures like day 25 by
+//! hand.
+
+use super::{InteractiveIntcodeProgram, RunState};
+use crate::SimpleError;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct AsciiOutput {
+    pub text: String,
+    // A value after the text that isn't ASCII
+    pub value: Option<i64>,
+}
+
+fn ascii_char(value: i64) -> Option<char> {
+    u8::try_from(value)
+        .ok()
+        .filter(u8::is_ascii)
+        .map(char::from)
+}
+
+// Splits a program's outputs into text and a trailing non-ASCII value. Non-ASCII values anywhere
+// else are an error
+pub fn decode(outputs: &[i64]) -> Result<AsciiOutput, SimpleError> {
+    let mut output = AsciiOutput::default();
+    for (i, &value) in outputs.iter().enumerate() {
+        match ascii_char(value) {
+            Some(c) => output.text.push(c),
+            None if i == outputs.len() - 1 => output.value = Some(value),
+            None => {
+                return Err(SimpleError::runtime(format!(
+                    "non-ASCII value {value} in the middle of program output: {}",
+                    output.text
+                )))
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+#[derive(Debug, Clone)]
+pub struct AsciiProgram {
+    program: InteractiveIntcodeProgram,
+    halted: bool,
+}
+
+impl AsciiProgram {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self::from(InteractiveIntcodeProgram::new(program))
+    }
+
+    pub fn program(&self) -> &InteractiveIntcodeProgram {
+        &self.program
+    }
+
+    pub fn program_mut(&mut self) -> &mut InteractiveIntcodeProgram {
+        &mut self.program
+    }
+
+    // Whether the program halted the last time it ran
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    // A copy of this program that can run independently, see InteractiveIntcodeProgram::fork
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+
+    // Queues a line of input, adding the newline. Fails if the line isn't ASCII
+    pub fn send_line(&mut self, line: &str) -> Result<(), SimpleError> {
+        self.program.push_line_as_ascii(line)
+    }
+
+    // Runs until the program halts or needs more input, returning everything it output
+    pub fn run(&mut self) -> Result<AsciiOutput, SimpleError> {
+        let state = self.program.execute()?.check_step_limit()?;
+        self.halted = state == RunState::Halted;
+
+        decode(&self.program.fetch_outputs())
+    }
+
+    // Runs until the program's text output ends with prompt, returning everything it output. Fails
+    // if the program stops first
+    pub fn read_until(&mut self, prompt: &str) -> Result<AsciiOutput, SimpleError> {
+        let mut outputs = self.program.fetch_outputs();
+        let mut text: String = outputs
+            .iter()
+            .filter_map(|&value| ascii_char(value))
+            .collect();
+
+        while !text.ends_with(prompt) {
+            match self.program.run_until_output()?.check_step_limit()? {
+                RunState::Output(value) => {
+                    outputs.push(value);
+                    text.extend(ascii_char(value));
+                }
+                state => {
+                    self.halted = state == RunState::Halted;
+                    return Err(SimpleError::runtime(format!(
+                        "program stopped before printing {prompt:?}, output was: {text}"
+                    )));
+                }
+            }
+        }
+
+        decode(&outputs)
+    }
+
+    // Plays the program from a terminal, printing its output and sending it each line of input
+    // until it halts or the input runs out
+    pub fn play(&mut self, input: impl BufRead, mut out: impl Write) -> Result<(), SimpleError> {
+        let mut lines = input.lines();
+        loop {
+            let output = self.run()?;
+            write!(out, "{}", output.text)?;
+            if let Some(value) = output.value {
+                writeln!(out, "{value}")?;
+            }
+            out.flush()?;
+
+            if self.halted {
+                return Ok(());
+            }
+
+            let Some(line) = lines.next() else {
+                return Ok(());
+            };
+            if let Err(err) = self.send_line(&line?) {
+                writeln!(out, "error: {err}")?;
+            }
+        }
+    }
+}
+
+impl From<InteractiveIntcodeProgram> for AsciiProgram {
+    fn from(program: InteractiveIntcodeProgram) -> Self {
+        Self {
+            program,
+            halted: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode::assembler;
+
+    // Prints a prompt, echoes a line back, then outputs 1000
+    const ECHO: &str = "\
+        out #62
+        out #32
+loop:   in [c]
+        out [c]
+        eq [c], #10, [t]
+        jf [t], #loop
+        out #1000
+        hlt
+c:      data 0
+t:      data 0
+";
+
+    fn echo_program() -> AsciiProgram {
+        AsciiProgram::new(assembler::assemble(ECHO).unwrap())
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            Ok(AsciiOutput {
+                text: String::from("ok\n"),
+                value: None,
+            }),
+            decode(&[111, 107, 10])
+        );
+        assert_eq!(
+            Ok(AsciiOutput {
+                text: String::from("ok\n"),
+                value: Some(19354437),
+            }),
+            decode(&[111, 107, 10, 19354437])
+        );
+        assert_eq!(Some(-1), decode(&[-1]).unwrap().value);
+        assert!(decode(&[111, 128, 107]).is_err());
+    }
+
+    #[test]
+    fn test_read_until() {
+        let mut program = echo_program();
+        assert_eq!("> ", program.read_until("> ").unwrap().text);
+
+        assert!(program.send_line("héllo").is_err());
+        program.send_line("hello").unwrap();
+        assert_eq!(
+            AsciiOutput {
+                text: String::from("hello\n"),
+                value: Some(1000),
+            },
+            program.run().unwrap()
+        );
+        assert!(program.halted());
+
+        let mut program = echo_program();
+        assert!(program.read_until("?").is_err());
+        assert!(!program.halted());
+    }
+
+    #[test]
+    fn test_play() {
+        let mut out = Vec::new();
+        echo_program()
+            .play("hi\nmore\n".as_bytes(), &mut out)
+            .unwrap();
+        assert_eq!("> hi\n1000\n", String::from_utf8(out).unwrap());
+
+        let mut out = Vec::new();
+        echo_program().play("ü\nhi\n".as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("> error: "));
+        assert!(out.ends_with("hi\n1000\n"));
+    }
+}
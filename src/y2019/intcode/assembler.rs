@@ -0,0 +1,470 @@
+//! Assembles Intcode source into a program, using the same syntax as the disassembler's listings.
+//!
+//! ```text
+//! ; Echoes input until it reads a 0
+//! loop:   in [value]
+//!         jf [value], #end
+//!         out [value]
+//!         jt #1, #loop
+//! end:    hlt
+//! value:  data 0
+//! ```
+//!
+//! - Parameters are `[x]` in position mode, `#x` in immediate mode, and `rb+x` or `rb-x` in
+//!   relative mode, where `x` is a number, a label, or a sum such as `buffer+2`
+//! - `data` takes a comma-separated list of values and string literals, e.g. `data "Hi\n", 0`
+//! - A line may start with `name:` to define a label, or with an address such as `12:` which has
+//!   to match the address that the line assembles to. That is what makes listings from the
+//!   disassembler valid source
+//! - `;` starts a comment
+
+use super::disassembler::{Instruction, Opcode, Parameter};
+use crate::SimpleError;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy)]
+enum Term<'a> {
+    Number(i64),
+    Label(&'a str),
+}
+
+// A sum of terms, e.g. `buffer+2`, along with where it appears in the source
+#[derive(Debug, Clone)]
+struct Expression<'a> {
+    terms: Vec<(i64, Term<'a>)>,
+    token: &'a str,
+}
+
+// Builds a parameter with a particular mode, e.g. Parameter::Immediate
+type ParameterMode = fn(i64) -> Parameter;
+
+#[derive(Debug, Clone)]
+enum Item<'a> {
+    Instruction {
+        opcode: Opcode,
+        parameters: Vec<(ParameterMode, Expression<'a>)>,
+    },
+    Data(Vec<Expression<'a>>),
+}
+
+impl Item<'_> {
+    fn word_count(&self) -> usize {
+        match self {
+            Self::Instruction { parameters, .. } => 1 + parameters.len(),
+            Self::Data(values) => values.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Statement<'a> {
+    line_number: usize,
+    line: &'a str,
+    item: Item<'a>,
+}
+
+pub fn assemble(source: &str) -> Result<Vec<i64>, SimpleError> {
+    let mut statements = Vec::new();
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut address = 0;
+
+    for (i, line) in source.lines().enumerate() {
+        let at_line = |err: SimpleError| err.at_line(i + 1, line);
+
+        let mut rest = strip_comment(line).trim();
+        while let Some((prefix, after)) = split_prefix(rest) {
+            if let Ok(expected) = prefix.parse::<usize>() {
+                if expected != address {
+                    return Err(at_line(
+                        SimpleError::new(format!("line is at address {address}, not {expected}"))
+                            .at_token(line, prefix),
+                    ));
+                }
+            } else if labels.insert(prefix, address).is_some() {
+                return Err(at_line(
+                    SimpleError::new(format!("duplicate label: {prefix}")).at_token(line, prefix),
+                ));
+            }
+            rest = after.trim_start();
+        }
+
+        if rest.is_empty() {
+            continue;
+        }
+
+        let item = parse_item(line, rest).map_err(at_line)?;
+        address += item.word_count();
+        statements.push(Statement {
+            line_number: i + 1,
+            line,
+            item,
+        });
+    }
+
+    let mut program = Vec::with_capacity(address);
+    for statement in &statements {
+        let resolve = |expression: &Expression| {
+            resolve(expression, &labels).map_err(|err| {
+                err.at_token(statement.line, expression.token)
+                    .at_line(statement.line_number, statement.line)
+            })
+        };
+
+        match &statement.item {
+            Item::Instruction { opcode, parameters } => {
+                let parameters = parameters
+                    .iter()
+                    .map(|(mode, expression)| Ok(mode(resolve(expression)?)))
+                    .collect::<Result<_, SimpleError>>()?;
+
+                let instruction = Instruction {
+                    opcode: *opcode,
+                    parameters,
+                };
+                program.extend(instruction.encode());
+            }
+            Item::Data(values) => {
+                for value in values {
+                    program.push(resolve(value)?);
+                }
+            }
+        }
+    }
+
+    Ok(program)
+}
+
+// Splits off a leading `label:` or `address:`
+fn split_prefix(s: &str) -> Option<(&str, &str)> {
+    let (prefix, rest) = s.split_once(':')?;
+    let is_label =
+        is_identifier(prefix) && prefix != "data" && Opcode::from_mnemonic(prefix).is_none();
+    let is_address = !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_digit());
+    (is_label || is_address).then_some((prefix, rest))
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ';' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_item<'a>(line: &'a str, s: &'a str) -> Result<Item<'a>, SimpleError> {
+    let (mnemonic, operands) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+    let operands = operands.trim();
+
+    if mnemonic == "data" {
+        let mut values = Vec::new();
+        for operand in split_operands(operands) {
+            match operand.strip_prefix('"') {
+                Some(string) => {
+                    let string = string.strip_suffix('"').ok_or_else(|| {
+                        SimpleError::new(format!("unterminated string: {operand}"))
+                            .at_token(line, operand)
+                    })?;
+                    for c in unescape(string).map_err(|err| err.at_token(line, operand))? {
+                        values.push(Expression {
+                            terms: vec![(1, Term::Number(c as i64))],
+                            token: operand,
+                        });
+                    }
+                }
+                None => values.push(parse_expression(line, operand)?),
+            }
+        }
+        return Ok(Item::Data(values));
+    }
+
+    let opcode = Opcode::from_mnemonic(mnemonic).ok_or_else(|| {
+        SimpleError::new(format!("unknown mnemonic: {mnemonic}")).at_token(line, mnemonic)
+    })?;
+
+    let operands = split_operands(operands);
+    if operands.len() != opcode.parameter_count() {
+        return Err(SimpleError::new(format!(
+            "{mnemonic} takes {} parameters, got {}",
+            opcode.parameter_count(),
+            operands.len()
+        ))
+        .at_token(line, s));
+    }
+
+    let mut parameters = Vec::with_capacity(operands.len());
+    for (i, operand) in operands.into_iter().enumerate() {
+        let (mode, value): (ParameterMode, _) = if let Some(value) = operand.strip_prefix('#') {
+            if opcode.write_parameter() == Some(i) {
+                return Err(SimpleError::new(format!(
+                    "{mnemonic} cannot write to an immediate parameter: {operand}"
+                ))
+                .at_token(line, operand));
+            }
+            (Parameter::Immediate, Some(value))
+        } else if let Some(value) = operand
+            .strip_prefix('[')
+            .and_then(|value| value.strip_suffix(']'))
+        {
+            (Parameter::Position, Some(value))
+        } else if let Some(offset) = operand.strip_prefix("rb") {
+            if offset.is_empty() {
+                (Parameter::Relative, None)
+            } else if offset.starts_with(['+', '-']) {
+                // Keep the sign as part of the value
+                (
+                    Parameter::Relative,
+                    Some(offset.strip_prefix('+').unwrap_or(offset)),
+                )
+            } else {
+                return Err(invalid_parameter(line, operand));
+            }
+        } else {
+            return Err(invalid_parameter(line, operand));
+        };
+
+        let expression = match value {
+            Some(value) => parse_expression(line, value)?,
+            None => Expression {
+                terms: vec![(1, Term::Number(0))],
+                token: operand,
+            },
+        };
+        parameters.push((mode, expression));
+    }
+
+    Ok(Item::Instruction { opcode, parameters })
+}
+
+fn invalid_parameter(line: &str, operand: &str) -> SimpleError {
+    SimpleError::new(format!(
+        "invalid parameter, expected [x], #x or rb+x: {operand}"
+    ))
+    .at_token(line, operand)
+}
+
+// Splits on commas outside of string literals
+fn split_operands(s: &str) -> Vec<&str> {
+    if s.is_empty() {
+        return Vec::new();
+    }
+
+    let mut operands = Vec::new();
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ',' if !in_string => {
+                operands.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    operands.push(s[start..].trim());
+
+    operands
+}
+
+fn parse_expression<'a>(line: &'a str, s: &'a str) -> Result<Expression<'a>, SimpleError> {
+    let mut terms = Vec::new();
+
+    let mut rest = s.trim();
+    let mut sign = 1;
+    if let Some(after) = rest.strip_prefix('-') {
+        sign = -1;
+        rest = after;
+    }
+
+    loop {
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let term = rest[..end].trim();
+        let term = match term.parse() {
+            Ok(n) => Term::Number(n),
+            Err(_) if is_identifier(term) => Term::Label(term),
+            Err(_) => {
+                return Err(SimpleError::new(format!("invalid value: {s}")).at_token(line, s));
+            }
+        };
+        terms.push((sign, term));
+
+        if end == rest.len() {
+            return Ok(Expression { terms, token: s });
+        }
+
+        sign = if rest[end..].starts_with('-') { -1 } else { 1 };
+        rest = &rest[end + 1..];
+    }
+}
+
+fn resolve(expression: &Expression, labels: &HashMap<&str, usize>) -> Result<i64, SimpleError> {
+    let mut value: i64 = 0;
+    for &(sign, term) in &expression.terms {
+        let term = match term {
+            Term::Number(n) => n,
+            Term::Label(label) => match labels.get(label) {
+                Some(&address) => address as i64,
+                None => return Err(SimpleError::new(format!("undefined label: {label}"))),
+            },
+        };
+        value = sign
+            .checked_mul(term)
+            .and_then(|term| value.checked_add(term))
+            .ok_or_else(|| {
+                SimpleError::new(format!("value overflows: {}", expression.token.trim()))
+            })?;
+    }
+    Ok(value)
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn unescape(s: &str) -> Result<Vec<char>, SimpleError> {
+    let mut chars = Vec::with_capacity(s.len());
+    let mut iter = s.chars();
+    while let Some(c) = iter.next() {
+        if c != '\\' {
+            chars.push(c);
+            continue;
+        }
+
+        match iter.next() {
+            Some('n') => chars.push('\n'),
+            Some('t') => chars.push('\t'),
+            Some(c @ ('"' | '\\')) => chars.push(c),
+            c => {
+                return Err(SimpleError::new(format!(
+                    "invalid escape in string: \\{}",
+                    c.map(String::from).unwrap_or_default()
+                )))
+            }
+        }
+    }
+    Ok(chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode::disassembler;
+    use crate::y2019::intcode::{self, InteractiveIntcodeProgram, RunState};
+
+    const ECHO: &str = "\
+; Echoes input until it reads a 0
+loop:   in [value]
+        jf [value], #end
+        out [value]
+        jt #1, #loop
+end:    hlt
+value:  data 0
+";
+
+    #[test]
+    fn test_assemble() {
+        assert_eq!(
+            vec![3, 13, 1006, 13, 12, 4, 13, 1105, 1, 0, 99, 0, 0],
+            assemble("in [13]\njf [13], #12\nout [13]\njt #1, #0\nhlt\ndata 0, 0").unwrap()
+        );
+
+        let program = assemble(ECHO).unwrap();
+        assert_eq!(vec![3, 11, 1006, 11, 10, 4, 11, 1105, 1, 0, 99, 0], program);
+
+        let mut echo = InteractiveIntcodeProgram::new(program);
+        for input in [5, -3, 0] {
+            echo.push_input(input);
+        }
+        assert_eq!(Ok(RunState::Halted), echo.execute());
+        assert_eq!(vec![5, -3], echo.fetch_outputs());
+    }
+
+    #[test]
+    fn test_assemble_expressions() {
+        let source = "\
+start:  add rb, rb-2, [buffer+1]
+        mul #-3, #buffer-start, rb+buffer
+buffer: data \"A;\\n\", 'x', -7
+";
+        assert!(assemble(source).is_err());
+
+        let source = source.replace("'x'", "120");
+        assert_eq!(
+            vec![2201, 0, -2, 9, 21102, -3, 8, 8, 65, 59, 10, 120, -7],
+            assemble(&source).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let programs = [
+            vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8],
+            vec![
+                109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+            ],
+            vec![1106, 0, 6, 72, 105, 10, 4, 3, 4, 4, 4, 5, 99, 7],
+            vec![
+                109, 20, 21101, 9, 0, 0, 1105, 1, 10, 99, 2106, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ],
+            assemble(ECHO).unwrap(),
+        ];
+
+        for program in programs {
+            let listing = disassembler::format_listing(&disassembler::disassemble(&program));
+            assert_eq!(program, assemble(&listing).unwrap(), "{listing}");
+        }
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let err = assemble("jt #1, #loop\nhlt").unwrap_err();
+        assert_eq!("undefined label: loop", err.to_string());
+        assert_eq!("line 1, column 9", err.location().unwrap().to_string());
+
+        let err = assemble("hlt\n    3: hlt").unwrap_err();
+        assert_eq!("line 2, column 5", err.location().unwrap().to_string());
+
+        assert!(assemble("add #1, #2, #3").is_err());
+        assert!(assemble("add #1, #2").is_err());
+        assert!(assemble("out 5").is_err());
+        assert!(assemble("nop").is_err());
+        assert!(assemble("a: hlt\na: hlt").is_err());
+        assert!(assemble("data \"abc").is_err());
+
+        let err = assemble("hlt\ndata 1, 9223372036854775807+1").unwrap_err();
+        assert_eq!("value overflows: 9223372036854775807+1", err.to_string());
+        assert_eq!("line 2, column 9", err.location().unwrap().to_string());
+        assert!(assemble("data -9223372036854775807-2").is_err());
+    }
+
+    #[test]
+    fn test_assembled_program_runs() {
+        // Outputs 1 if the input equals 8, the same as the day 5 sample
+        let program = assemble(
+            "\
+        in [input]
+        eq [input], #8, [input]
+        out [input]
+        hlt
+input:  data 0
+",
+        )
+        .unwrap();
+
+        let mut outputs = Vec::new();
+        intcode::execute(&mut program.clone(), || 8, |output| outputs.push(output)).unwrap();
+        assert_eq!(vec![1], outputs);
+    }
+}
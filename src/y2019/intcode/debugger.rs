@@ -0,0 +1,632 @@
+//! An interactive debugger for Intcode programs, with breakpoints, single-stepping, watchpoints on
+//! memory writes, and memory inspection.
+//!
+//! [`run_repl`] drives a [`Debugger`] from text commands; type `help` for the list.
+
+use super::disassembler::{Instruction, Opcode};
+use super::snapshot::Snapshot;
+use super::{IntcodeError, InteractiveIntcodeProgram, Step};
+use crate::simpleerror::ErrorKind;
+use crate::SimpleError;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+commands:
+  s, step [n]              execute n instructions, default 1
+  c, continue              run until a breakpoint, watchpoint, halt, missing input, or the step
+                           limit
+  b, break <addr | op>     break before executing an address or an opcode such as out
+  d, delete <addr | op>    remove a breakpoint
+  w, watch <addr>          stop after any write to an address
+  unwatch <addr>           remove a watchpoint
+  r, regs                  show ip, relative base and the next instruction
+  x <addr> [n]             show n words of memory, default 8
+  dis [addr] [n]           disassemble n instructions, default 8 from ip
+  in <n>...                queue input values
+  line <text>              queue a line of text as ASCII input
+  info                     list breakpoints and watchpoints
+  save <path>              write the program state to a file
+  load <path>              restore the program state from a file written by save
+  q, quit                  exit";
+
+const DEFAULT_COUNT: usize = 8;
+
+// The most words or instructions that x and dis will show at once
+const MAX_COUNT: usize = 10_000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Breakpoint {
+    Address(usize),
+    Opcode(Opcode),
+}
+
+impl Display for Breakpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "address {address}"),
+            Self::Opcode(opcode) => write!(f, "opcode {}", opcode.mnemonic()),
+        }
+    }
+}
+
+impl std::str::FromStr for Breakpoint {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(address) = s.parse() {
+            return Ok(Self::Address(address));
+        }
+
+        Opcode::from_mnemonic(s)
+            .map(Self::Opcode)
+            .ok_or_else(|| SimpleError::new(format!("expected an address or a mnemonic: {s}")))
+    }
+}
+
+// Why the debugger handed control back
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StopReason {
+    Stepped,
+    Breakpoint(Breakpoint),
+    Watchpoint { address: usize, old: i64, new: i64 },
+    Halted,
+    NeedsInput,
+    // Resuming ran this many instructions without stopping
+    StepLimitReached(u64),
+    Error(IntcodeError),
+}
+
+impl Display for StopReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stepped => write!(f, "stepped"),
+            Self::Breakpoint(breakpoint) => write!(f, "hit breakpoint on {breakpoint}"),
+            Self::Watchpoint { address, old, new } => {
+                write!(f, "watchpoint: [{address}] changed from {old} to {new}")
+            }
+            Self::Halted => write!(f, "halted"),
+            Self::NeedsInput => write!(f, "waiting for input"),
+            Self::StepLimitReached(limit) => {
+                write!(f, "step limit of {limit} instructions reached")
+            }
+            Self::Error(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    program: InteractiveIntcodeProgram,
+    breakpoints: Vec<Breakpoint>,
+    watchpoints: BTreeSet<usize>,
+}
+
+impl Debugger {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self {
+            program: InteractiveIntcodeProgram::new(program),
+            breakpoints: Vec::new(),
+            watchpoints: BTreeSet::new(),
+        }
+    }
+
+    pub fn program(&self) -> &InteractiveIntcodeProgram {
+        &self.program
+    }
+
+    pub fn program_mut(&mut self) -> &mut InteractiveIntcodeProgram {
+        &mut self.program
+    }
+
+    // Instructions executed so far
+    pub fn steps(&self) -> u64 {
+        self.program.steps()
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: Breakpoint) -> bool {
+        let len = self.breakpoints.len();
+        self.breakpoints.retain(|&b| b != breakpoint);
+        self.breakpoints.len() != len
+    }
+
+    pub fn add_watchpoint(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize) -> bool {
+        self.watchpoints.remove(&address)
+    }
+
+    pub fn current_instruction(&self) -> Option<Instruction> {
+        self.program.memory().decode(self.program.ip())
+    }
+
+    pub fn read(&self, address: usize) -> i64 {
+        self.program.memory().get(address)
+    }
+
+    // The address that the next instruction will write to, if it writes
+    fn write_address(&self, instruction: &Instruction) -> Option<usize> {
+        instruction.parameters[instruction.opcode.write_parameter()?]
+            .address(self.program.relative_base())
+    }
+
+    // Executes a single instruction, stopping early if it halts, needs input, or is invalid
+    pub fn step(&mut self) -> StopReason {
+        // Decoding the instruction is slow next to executing it, so only do it when it's needed
+        let watched = if self.watchpoints.is_empty() {
+            None
+        } else {
+            self.current_instruction()
+                .and_then(|instruction| self.write_address(&instruction))
+                .filter(|address| self.watchpoints.contains(address))
+                .map(|address| (address, self.read(address)))
+        };
+
+        match self.program.step() {
+            Ok(Step::Executed | Step::Output(_)) => {}
+            Ok(Step::Halted) => return StopReason::Halted,
+            Ok(Step::NeedsInput) => return StopReason::NeedsInput,
+            Err(err) => return StopReason::Error(err),
+        }
+
+        match watched {
+            Some((address, old)) => StopReason::Watchpoint {
+                address,
+                old,
+                new: self.read(address),
+            },
+            None => StopReason::Stepped,
+        }
+    }
+
+    fn breakpoint_at_ip(&self) -> Option<Breakpoint> {
+        if self.breakpoints.is_empty() {
+            return None;
+        }
+
+        let ip = self.program.ip();
+        let opcode = self
+            .current_instruction()
+            .map(|instruction| instruction.opcode);
+        self.breakpoints
+            .iter()
+            .copied()
+            .find(|&breakpoint| match breakpoint {
+                Breakpoint::Address(address) => address == ip,
+                Breakpoint::Opcode(op) => Some(op) == opcode,
+            })
+    }
+
+    // Runs until something stops execution, or for at most the program's step limit so that a
+    // program stuck in a loop hands control back. A breakpoint at the starting ip is stepped over
+    pub fn resume(&mut self) -> StopReason {
+        let step_limit = self.program.step_limit();
+        let mut steps = 0;
+        loop {
+            if steps > 0 {
+                if let Some(breakpoint) = self.breakpoint_at_ip() {
+                    return StopReason::Breakpoint(breakpoint);
+                }
+            }
+            if let Some(limit) = step_limit.filter(|&limit| steps >= limit) {
+                return StopReason::StepLimitReached(limit);
+            }
+            steps += 1;
+
+            match self.step() {
+                StopReason::Stepped => {}
+                reason => return reason,
+            }
+        }
+    }
+
+    pub fn format_registers(&self) -> String {
+        let instruction = match self.current_instruction() {
+            Some(instruction) => instruction.to_string(),
+            None => format!("<invalid: {}>", self.read(self.program.ip())),
+        };
+        format!(
+            "ip={} rb={} steps={}  next: {instruction}",
+            self.program.ip(),
+            self.program.relative_base(),
+            self.steps()
+        )
+    }
+
+    pub fn format_memory(&self, start: usize, count: usize) -> Result<String, SimpleError> {
+        check_count(count)?;
+        let end = start.checked_add(count).ok_or_else(|| {
+            SimpleError::usage(format!(
+                "{count} words from {start} run past the last address"
+            ))
+        })?;
+
+        let values: Vec<_> = (start..end)
+            .map(|address| self.read(address).to_string())
+            .collect();
+        Ok(format!("[{start}]: {}", values.join(", ")))
+    }
+
+    // Decodes count instructions in order, without following jumps
+    pub fn format_disassembly(&self, start: usize, count: usize) -> Result<String, SimpleError> {
+        check_count(count)?;
+        let memory = self.program.memory();
+
+        let mut lines = Vec::with_capacity(count);
+        let mut address = start;
+        for _ in 0..count {
+            if address >= memory.len() {
+                break;
+            }
+
+            let marker = if address == self.program.ip() {
+                ">"
+            } else {
+                " "
+            };
+            match memory.decode(address) {
+                Some(instruction) => {
+                    lines.push(format!("{marker}{address:>5}: {instruction}"));
+                    address += instruction.word_count();
+                }
+                None => {
+                    lines.push(format!(
+                        "{marker}{address:>5}: data {}",
+                        memory.get(address)
+                    ));
+                    address += 1;
+                }
+            }
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+fn check_count(count: usize) -> Result<(), SimpleError> {
+    if count > MAX_COUNT {
+        return Err(SimpleError::usage(format!(
+            "can only show up to {MAX_COUNT} at once, got {count}"
+        )));
+    }
+    Ok(())
+}
+
+// Formats the outputs produced since the last command, including them as text if they're ASCII
+fn format_outputs(outputs: &[i64]) -> Option<String> {
+    if outputs.is_empty() {
+        return None;
+    }
+
+    let values: Vec<_> = outputs.iter().map(i64::to_string).collect();
+    let mut s = format!("output: {}", values.join(", "));
+
+    let text: Option<String> = outputs
+        .iter()
+        .map(|&output| {
+            u8::try_from(output)
+                .ok()
+                .filter(|&b| b == b'\n' || (b' '..=b'~').contains(&b))
+                .map(char::from)
+        })
+        .collect();
+    if let Some(text) = text {
+        s.push_str(&format!("\n{}", text.trim_end()));
+    }
+
+    Some(s)
+}
+
+fn parse_usize(arg: Option<&str>, default: usize) -> Result<usize, SimpleError> {
+    match arg {
+        Some(arg) => Ok(arg.parse()?),
+        None => Ok(default),
+    }
+}
+
+fn required<'a>(arg: Option<&'a str>, name: &str) -> Result<&'a str, SimpleError> {
+    arg.ok_or_else(|| SimpleError::usage(format!("missing argument: {name}")))
+}
+
+// Runs a single REPL command, returning false if the debugger should exit
+fn run_command(
+    debugger: &mut Debugger,
+    line: &str,
+    out: &mut impl Write,
+) -> Result<bool, SimpleError> {
+    let line = line.trim();
+    let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let mut args = rest.split_whitespace();
+
+    match command {
+        "" => {}
+        "q" | "quit" => return Ok(false),
+        "h" | "help" => writeln!(out, "{HELP}")?,
+        "s" | "step" => {
+            let count = parse_usize(args.next(), 1)?;
+            let mut reason = StopReason::Stepped;
+            for _ in 0..count {
+                reason = debugger.step();
+                if reason != StopReason::Stepped {
+                    break;
+                }
+            }
+            if let Some(outputs) = format_outputs(&debugger.program_mut().fetch_outputs()) {
+                writeln!(out, "{outputs}")?;
+            }
+            if reason != StopReason::Stepped {
+                writeln!(out, "{reason}")?;
+            }
+            writeln!(out, "{}", debugger.format_registers())?;
+        }
+        "c" | "continue" => {
+            let reason = debugger.resume();
+            if let Some(outputs) = format_outputs(&debugger.program_mut().fetch_outputs()) {
+                writeln!(out, "{outputs}")?;
+            }
+            writeln!(out, "{reason}")?;
+            writeln!(out, "{}", debugger.format_registers())?;
+        }
+        "b" | "break" => {
+            let breakpoint = required(args.next(), "breakpoint")?.parse()?;
+            debugger.add_breakpoint(breakpoint);
+            writeln!(out, "breakpoint on {breakpoint}")?;
+        }
+        "d" | "delete" => {
+            let breakpoint = required(args.next(), "breakpoint")?.parse()?;
+            if !debugger.remove_breakpoint(breakpoint) {
+                writeln!(out, "no breakpoint on {breakpoint}")?;
+            }
+        }
+        "w" | "watch" => {
+            let address = required(args.next(), "address")?.parse()?;
+            debugger.add_watchpoint(address);
+            writeln!(out, "watching [{address}]")?;
+        }
+        "unwatch" => {
+            let address = required(args.next(), "address")?.parse()?;
+            if !debugger.remove_watchpoint(address) {
+                writeln!(out, "not watching [{address}]")?;
+            }
+        }
+        "r" | "regs" => writeln!(out, "{}", debugger.format_registers())?,
+        "x" => {
+            let start = required(args.next(), "address")?.parse()?;
+            let count = parse_usize(args.next(), DEFAULT_COUNT)?;
+            writeln!(out, "{}", debugger.format_memory(start, count)?)?;
+        }
+        "dis" => {
+            let start = parse_usize(args.next(), debugger.program().ip())?;
+            let count = parse_usize(args.next(), DEFAULT_COUNT)?;
+            writeln!(out, "{}", debugger.format_disassembly(start, count)?)?;
+        }
+        "in" => {
+            let values: Vec<i64> = args.map(str::parse).collect::<Result<_, _>>()?;
+            for value in values {
+                debugger.program_mut().push_input(value);
+            }
+        }
+        "line" => {
+            debugger.program_mut().push_line_as_ascii(rest)?;
+        }
+        "info" => {
+            for breakpoint in &debugger.breakpoints {
+                writeln!(out, "breakpoint on {breakpoint}")?;
+            }
+            for address in &debugger.watchpoints {
+                writeln!(out, "watching [{address}]")?;
+            }
+        }
+        "save" => {
+            let path = required(args.next(), "path")?;
+            fs::write(path, debugger.program().snapshot().to_string()).map_err(|err| {
+                SimpleError::with_source(ErrorKind::Runtime, format!("could not write {path}"), err)
+            })?;
+            writeln!(out, "saved to {path}")?;
+        }
+        "load" => {
+            let path = required(args.next(), "path")?;
+            let snapshot: Snapshot = fs::read_to_string(path)
+                .map_err(|err| {
+                    SimpleError::with_source(
+                        ErrorKind::Runtime,
+                        format!("could not read {path}"),
+                        err,
+                    )
+                })?
+                .parse()?;
+            debugger.program_mut().restore(&snapshot);
+            writeln!(out, "{}", debugger.format_registers())?;
+        }
+        _ => writeln!(out, "unknown command: {command}, try help")?,
+    }
+
+    Ok(true)
+}
+
+// Reads commands from input until it's exhausted or a quit command. Errors from individual
+// commands are printed rather than ending the session
+pub fn run_repl(
+    program: Vec<i64>,
+    input: impl BufRead,
+    mut out: impl Write,
+) -> Result<(), SimpleError> {
+    let mut debugger = Debugger::new(program);
+
+    writeln!(out, "{}", debugger.format_registers())?;
+    write!(out, "(intcode) ")?;
+    out.flush()?;
+
+    for line in input.lines() {
+        match run_command(&mut debugger, &line?, &mut out) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(err) => writeln!(out, "error: {err}")?,
+        }
+
+        write!(out, "(intcode) ")?;
+        out.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode::{assembler, IntcodeErrorReason};
+
+    const COUNTDOWN: &str = "\
+        in [n]
+loop:   out [n]
+        add [n], #-1, [n]
+        jt [n], #loop
+        hlt
+n:      data 0
+";
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = Debugger::new(assembler::assemble(COUNTDOWN).unwrap());
+        assert_eq!(StopReason::NeedsInput, debugger.resume());
+
+        debugger.program_mut().push_input(3);
+        debugger.add_breakpoint(Breakpoint::Address(4));
+        assert_eq!(
+            StopReason::Breakpoint(Breakpoint::Address(4)),
+            debugger.resume()
+        );
+        assert_eq!(vec![3], debugger.program_mut().fetch_outputs());
+
+        // Resuming from a breakpoint doesn't stop on it again right away
+        assert_eq!(
+            StopReason::Breakpoint(Breakpoint::Address(4)),
+            debugger.resume()
+        );
+        assert_eq!(vec![2], debugger.program_mut().fetch_outputs());
+
+        debugger.remove_breakpoint(Breakpoint::Address(4));
+        debugger.add_breakpoint(Breakpoint::Opcode(Opcode::Halt));
+        assert_eq!(
+            StopReason::Breakpoint(Breakpoint::Opcode(Opcode::Halt)),
+            debugger.resume()
+        );
+        assert_eq!(vec![1], debugger.program_mut().fetch_outputs());
+        assert_eq!(StopReason::Halted, debugger.resume());
+    }
+
+    #[test]
+    fn test_step_and_watch() {
+        let mut debugger = Debugger::new(assembler::assemble(COUNTDOWN).unwrap());
+        debugger.program_mut().push_input(2);
+
+        assert_eq!(StopReason::Stepped, debugger.step());
+        assert_eq!(2, debugger.program().ip());
+        assert_eq!(1, debugger.steps());
+
+        debugger.add_watchpoint(12);
+        assert_eq!(
+            StopReason::Watchpoint {
+                address: 12,
+                old: 2,
+                new: 1
+            },
+            debugger.resume()
+        );
+        assert_eq!(
+            "ip=8 rb=0 steps=3  next: jt [12], #2",
+            debugger.format_registers()
+        );
+        let mut debugger = Debugger::new(vec![104, 1, 42]);
+        assert_eq!(
+            StopReason::Error(IntcodeError {
+                ip: 2,
+                opcode: 42,
+                reason: IntcodeErrorReason::UnknownOpcode,
+            }),
+            debugger.resume()
+        );
+        assert_eq!(2, debugger.program().ip());
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let mut debugger = Debugger::new(vec![1105, 1, 0]);
+        debugger.program_mut().set_step_limit(Some(100));
+
+        // Every resume gets the full limit
+        assert_eq!(StopReason::StepLimitReached(100), debugger.resume());
+        assert_eq!(100, debugger.steps());
+        assert_eq!(StopReason::StepLimitReached(100), debugger.resume());
+        assert_eq!(200, debugger.steps());
+    }
+
+    #[test]
+    fn test_repl() {
+        let program = assembler::assemble(COUNTDOWN).unwrap();
+        let commands = "\
+in 2
+b out
+c
+c
+x 12 2
+x 18446744073709551615 8
+x 0 1000000
+dis 0 2
+dis 0 1000000
+bogus
+s 100
+q
+s
+";
+        let mut out = Vec::new();
+        run_repl(program, commands.as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        for expected in [
+            "breakpoint on opcode out",
+            "hit breakpoint on opcode out\nip=2 ",
+            "output: 2\nhit breakpoint on opcode out",
+            "[12]: 1, 0",
+            "error: 8 words from 18446744073709551615 run past the last address",
+            "error: can only show up to 10000 at once, got 1000000\n(intcode) ",
+            "     0: in [12]\n>    2: out [12]",
+            "unknown command: bogus",
+            "output: 1\nhalted",
+        ] {
+            assert!(out.contains(expected), "expected {expected:?} in:\n{out}");
+        }
+        assert_eq!(1, out.matches("halted").count());
+        assert_eq!(2, out.matches("can only show up to").count());
+    }
+
+    #[test]
+    fn test_repl_save_load() {
+        let path = std::env::temp_dir().join(format!("aoc-debugger-test-{}", std::process::id()));
+        let path = path.display();
+
+        let program = assembler::assemble(COUNTDOWN).unwrap();
+        let commands = format!("in 3\nb add\nc\nsave {path}\nc\nload {path}\nx 12 1\n");
+        let mut out = Vec::new();
+        run_repl(program, commands.as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        std::fs::remove_file(path.to_string()).unwrap();
+
+        assert!(out.contains(&format!("saved to {path}")), "{out}");
+        assert!(
+            out.ends_with(
+                "ip=4 rb=0 steps=2  next: add [12], #-1, [12]\n(intcode) [12]: 3\n(intcode) "
+            ),
+            "{out}"
+        );
+    }
+}
@@ -0,0 +1,541 @@
+//! Turns an Intcode program back into a readable listing.
+//!
+//! Code is found by following control flow from address 0 rather than by decoding every word in
+//! order, since data is mixed in with the code. Jumps with immediate targets are followed, and so
+//! are return addresses, i.e. immediate values that point just past an unconditional jump. Words
+//! that are never reached are listed as data.
+//!
+//! ```text
+//!     0: add #1, #0, [3]         ; 1101,1,0,3
+//!     4: jf #0, #10              ; 1106,0,10
+//!     7: data 72, 105, 10        ; "Hi\n"
+//!    10: hlt                     ; 99
+//! ```
+//!
+//! Parameters are rendered as `[x]` in position mode, `#x` in immediate mode, and `rb+x` in
+//! relative mode.
+
+use super::memory::Memory;
+use super::{
+    ADD_OPCODE, ADJUST_RELATIVE_BASE_OPCODE, EQUAL_OPCODE, HALT_OPCODE, IMMEDIATE_MODE,
+    INPUT_OPCODE, JUMP_IF_FALSE_OPCODE, JUMP_IF_TRUE_OPCODE, LESS_THAN_OPCODE, MULTIPLY_OPCODE,
+    OUTPUT_OPCODE, POSITION_MODE, RELATIVE_MODE,
+};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+const DATA_VALUES_PER_LINE: usize = 8;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Opcode {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equal,
+    AdjustRelativeBase,
+    Halt,
+}
+
+impl Opcode {
+    pub const ALL: [Self; 10] = [
+        Self::Add,
+        Self::Multiply,
+        Self::Input,
+        Self::Output,
+        Self::JumpIfTrue,
+        Self::JumpIfFalse,
+        Self::LessThan,
+        Self::Equal,
+        Self::AdjustRelativeBase,
+        Self::Halt,
+    ];
+
+    pub fn from_i64(opcode: i64) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.value() == opcode)
+    }
+
+    pub fn from_mnemonic(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.mnemonic() == s)
+    }
+
+    pub fn value(self) -> i64 {
+        match self {
+            Self::Add => ADD_OPCODE,
+            Self::Multiply => MULTIPLY_OPCODE,
+            Self::Input => INPUT_OPCODE,
+            Self::Output => OUTPUT_OPCODE,
+            Self::JumpIfTrue => JUMP_IF_TRUE_OPCODE,
+            Self::JumpIfFalse => JUMP_IF_FALSE_OPCODE,
+            Self::LessThan => LESS_THAN_OPCODE,
+            Self::Equal => EQUAL_OPCODE,
+            Self::AdjustRelativeBase => ADJUST_RELATIVE_BASE_OPCODE,
+            Self::Halt => HALT_OPCODE,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Multiply => "mul",
+            Self::Input => "in",
+            Self::Output => "out",
+            Self::JumpIfTrue => "jt",
+            Self::JumpIfFalse => "jf",
+            Self::LessThan => "lt",
+            Self::Equal => "eq",
+            Self::AdjustRelativeBase => "arb",
+            Self::Halt => "hlt",
+        }
+    }
+
+    pub fn parameter_count(self) -> usize {
+        match self {
+            Self::Add | Self::Multiply | Self::LessThan | Self::Equal => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::AdjustRelativeBase => 1,
+            Self::Halt => 0,
+        }
+    }
+
+    // The index of the parameter that gets written to, if any
+    pub fn write_parameter(self) -> Option<usize> {
+        match self {
+            Self::Add | Self::Multiply | Self::LessThan | Self::Equal => Some(2),
+            Self::Input => Some(0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Parameter {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+impl Parameter {
+    fn from_mode(mode: i64, value: i64) -> Option<Self> {
+        match mode {
+            POSITION_MODE => Some(Self::Position(value)),
+            IMMEDIATE_MODE => Some(Self::Immediate(value)),
+            RELATIVE_MODE => Some(Self::Relative(value)),
+            _ => None,
+        }
+    }
+
+    pub fn mode(self) -> i64 {
+        match self {
+            Self::Position(_) => POSITION_MODE,
+            Self::Immediate(_) => IMMEDIATE_MODE,
+            Self::Relative(_) => RELATIVE_MODE,
+        }
+    }
+
+    pub fn value(self) -> i64 {
+        match self {
+            Self::Position(value) | Self::Immediate(value) | Self::Relative(value) => value,
+        }
+    }
+
+    // The memory address this parameter refers to, or None for immediate values and addresses that
+    // are negative or overflow
+    pub fn address(self, relative_base: i64) -> Option<usize> {
+        match self {
+            Self::Position(address) => usize::try_from(address).ok(),
+            Self::Immediate(_) => None,
+            Self::Relative(offset) => relative_base
+                .checked_add(offset)
+                .and_then(|address| usize::try_from(address).ok()),
+        }
+    }
+
+    // The value this parameter reads
+    pub fn read(self, memory: &Memory, relative_base: i64) -> i64 {
+        match self {
+            Self::Immediate(value) => value,
+            _ => self
+                .address(relative_base)
+                .map_or(0, |address| memory.get(address)),
+        }
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Position(address) => write!(f, "[{address}]"),
+            Self::Immediate(value) => write!(f, "#{value}"),
+            Self::Relative(offset) if offset < 0 => write!(f, "rb-{}", offset.unsigned_abs()),
+            Self::Relative(offset) => write!(f, "rb+{offset}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub parameters: Vec<Parameter>,
+}
+
+impl Instruction {
+    // Decodes the instruction at the given address. Returns None if the words there are not a
+    // valid instruction, e.g. an unknown opcode, an unknown or unused parameter mode, an immediate
+    // mode write, or an instruction running past the end of the program
+    pub fn decode(program: &[i64], address: usize) -> Option<Self> {
+        let word = *program.get(address)?;
+        if word < 0 {
+            return None;
+        }
+
+        let opcode = Opcode::from_i64(word % 100)?;
+
+        let mut modes = word / 100;
+        let mut parameters = Vec::with_capacity(opcode.parameter_count());
+        for i in 0..opcode.parameter_count() {
+            let value = *program.get(address + 1 + i)?;
+            let parameter = Parameter::from_mode(modes % 10, value)?;
+            if opcode.write_parameter() == Some(i) && parameter.mode() == IMMEDIATE_MODE {
+                return None;
+            }
+
+            parameters.push(parameter);
+            modes /= 10;
+        }
+
+        if modes != 0 {
+            return None;
+        }
+
+        Some(Self { opcode, parameters })
+    }
+
+    pub fn word_count(&self) -> usize {
+        1 + self.parameters.len()
+    }
+
+    pub fn encode(&self) -> Vec<i64> {
+        let modes = self
+            .parameters
+            .iter()
+            .rev()
+            .fold(0, |modes, parameter| 10 * modes + parameter.mode());
+
+        let mut words = Vec::with_capacity(self.word_count());
+        words.push(100 * modes + self.opcode.value());
+        words.extend(self.parameters.iter().map(|parameter| parameter.value()));
+        words
+    }
+
+    // Whether a jump with an immediate condition is always taken (true) or never taken (false)
+    fn fixed_jump(&self) -> Option<bool> {
+        match (self.opcode, self.parameters.first()) {
+            (Opcode::JumpIfTrue, Some(&Parameter::Immediate(value))) => Some(value != 0),
+            (Opcode::JumpIfFalse, Some(&Parameter::Immediate(value))) => Some(value == 0),
+            _ => None,
+        }
+    }
+
+    // Where execution can continue after this instruction, given the address it was decoded at.
+    // Jumps are only followed if their target is an immediate value
+    fn successors(&self, address: usize) -> Vec<usize> {
+        let next = address + self.word_count();
+        match (self.opcode, self.parameters.as_slice()) {
+            (Opcode::Halt, _) => vec![],
+            (Opcode::JumpIfTrue | Opcode::JumpIfFalse, &[_, target]) => {
+                let mut successors = Vec::new();
+                if self.fixed_jump() != Some(true) {
+                    successors.push(next);
+                }
+                if let (false, Parameter::Immediate(target)) =
+                    (self.fixed_jump() == Some(false), target)
+                {
+                    successors.extend(usize::try_from(target).ok());
+                }
+                successors
+            }
+            _ => vec![next],
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.opcode.mnemonic())?;
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{separator}{parameter}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Line {
+    Instruction {
+        address: usize,
+        instruction: Instruction,
+    },
+    Data {
+        address: usize,
+        values: Vec<i64>,
+    },
+}
+
+impl Line {
+    pub fn address(&self) -> usize {
+        match self {
+            Self::Instruction { address, .. } | Self::Data { address, .. } => *address,
+        }
+    }
+
+    pub fn words(&self) -> Vec<i64> {
+        match self {
+            Self::Instruction { instruction, .. } => instruction.encode(),
+            Self::Data { values, .. } => values.clone(),
+        }
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (text, comment) = match self {
+            Self::Instruction { instruction, .. } => {
+                let words: Vec<_> = instruction.encode().iter().map(i64::to_string).collect();
+                (instruction.to_string(), words.join(","))
+            }
+            Self::Data { values, .. } => {
+                let value_strs: Vec<_> = values.iter().map(i64::to_string).collect();
+                let text = format!("data {}", value_strs.join(", "));
+
+                // Show data that looks like text as a string
+                let ascii: Option<String> = values.iter().copied().map(ascii_char).collect();
+                let comment = ascii.map(|s| format!("{s:?}")).unwrap_or_default();
+                (text, comment)
+            }
+        };
+
+        let line = format!("{:>5}: {text}", self.address());
+        if comment.is_empty() {
+            write!(f, "{line}")
+        } else {
+            write!(f, "{line:<30} ; {comment}")
+        }
+    }
+}
+
+fn ascii_char(value: i64) -> Option<char> {
+    u8::try_from(value)
+        .ok()
+        .filter(|&b| b == b'\n' || (b' '..=b'~').contains(&b))
+        .map(char::from)
+}
+
+// Finds the start address of every instruction reachable from address 0
+fn find_code(program: &[i64]) -> BTreeSet<usize> {
+    let mut code = BTreeSet::new();
+    let mut seeds = vec![0];
+
+    loop {
+        while let Some(address) = seeds.pop() {
+            if code.contains(&address) {
+                continue;
+            }
+
+            if let Some(instruction) = Instruction::decode(program, address) {
+                code.insert(address);
+                seeds.extend(instruction.successors(address));
+            }
+        }
+
+        // Calls push the address after the jump as an immediate, so treat those as code too
+        let return_addresses: BTreeSet<_> = code
+            .iter()
+            .filter_map(|&address| {
+                let instruction = Instruction::decode(program, address)?;
+                (instruction.fixed_jump() == Some(true))
+                    .then_some(address + instruction.word_count())
+            })
+            .collect();
+
+        seeds = code
+            .iter()
+            .filter_map(|&address| Instruction::decode(program, address))
+            .flat_map(|instruction| instruction.parameters)
+            .filter_map(|parameter| match parameter {
+                Parameter::Immediate(value) => usize::try_from(value).ok(),
+                _ => None,
+            })
+            .filter(|address| return_addresses.contains(address) && !code.contains(address))
+            .collect();
+
+        if seeds.is_empty() {
+            return code;
+        }
+    }
+}
+
+pub fn disassemble(program: &[i64]) -> Vec<Line> {
+    let code = find_code(program);
+
+    let mut lines = Vec::new();
+    let mut data_start: Option<usize> = None;
+    let mut address = 0;
+    while address < program.len() {
+        // Instructions may overlap when a jump lands inside of another instruction, the first
+        // one listed wins
+        let instruction = code
+            .contains(&address)
+            .then(|| Instruction::decode(program, address))
+            .flatten();
+
+        match instruction {
+            Some(instruction) => {
+                if let Some(start) = data_start.take() {
+                    push_data(&mut lines, program, start, address);
+                }
+
+                let len = instruction.word_count();
+                lines.push(Line::Instruction {
+                    address,
+                    instruction,
+                });
+                address += len;
+            }
+            None => {
+                data_start.get_or_insert(address);
+                address += 1;
+            }
+        }
+    }
+
+    if let Some(start) = data_start {
+        push_data(&mut lines, program, start, program.len());
+    }
+
+    lines
+}
+
+fn push_data(lines: &mut Vec<Line>, program: &[i64], start: usize, end: usize) {
+    for chunk_start in (start..end).step_by(DATA_VALUES_PER_LINE) {
+        let chunk_end = (chunk_start + DATA_VALUES_PER_LINE).min(end);
+        lines.push(Line::Data {
+            address: chunk_start,
+            values: program[chunk_start..chunk_end].to_vec(),
+        });
+    }
+}
+
+pub fn format_listing(lines: &[Line]) -> String {
+    let mut s = String::new();
+    for line in lines {
+        s.push_str(&line.to_string());
+        s.push('\n');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let program = [1101, 1, -2, 3, 21202, 4, 5, -6, 99, 10103, 2];
+
+        assert_eq!(
+            Some(Instruction {
+                opcode: Opcode::Add,
+                parameters: vec![
+                    Parameter::Immediate(1),
+                    Parameter::Immediate(-2),
+                    Parameter::Position(3),
+                ],
+            }),
+            Instruction::decode(&program, 0)
+        );
+        assert_eq!(
+            "mul rb+4, #5, rb-6",
+            Instruction::decode(&program, 4).unwrap().to_string()
+        );
+        assert_eq!("hlt", Instruction::decode(&program, 8).unwrap().to_string());
+
+        // Immediate mode write, unused mode digit, and running past the end of the program
+        assert_eq!(None, Instruction::decode(&[11101, 1, 2, 3], 0));
+        assert_eq!(None, Instruction::decode(&[1099], 0));
+        assert_eq!(None, Instruction::decode(&program, 9));
+
+        assert_eq!(Some(7), Parameter::Relative(-3).address(10));
+        assert_eq!(None, Parameter::Relative(-3).address(1));
+        assert_eq!(None, Parameter::Relative(1).address(i64::MAX));
+    }
+
+    #[test]
+    fn test_encode() {
+        for program in [
+            vec![1101, 1, -2, 3],
+            vec![21202, 4, 5, -6],
+            vec![99],
+            vec![204, -1],
+        ] {
+            assert_eq!(program, Instruction::decode(&program, 0).unwrap().encode());
+        }
+    }
+
+    #[test]
+    fn test_disassemble() {
+        // Jumps over a data region holding "Hi\n", then outputs it
+        let program = vec![1106, 0, 6, 72, 105, 10, 4, 3, 4, 4, 4, 5, 99, 7];
+        let lines = disassemble(&program);
+
+        let expected = [
+            "    0: jf #0, #6               ; 1106,0,6",
+            "    3: data 72, 105, 10        ; \"Hi\\n\"",
+            "    6: out [3]                 ; 4,3",
+            "    8: out [4]                 ; 4,4",
+            "   10: out [5]                 ; 4,5",
+            "   12: hlt                     ; 99",
+            "   13: data 7",
+        ];
+        assert_eq!(
+            expected.map(|line| format!("{line}\n")).concat(),
+            format_listing(&lines)
+        );
+
+        let words: Vec<_> = lines.iter().flat_map(Line::words).collect();
+        assert_eq!(program, words);
+    }
+
+    #[test]
+    fn test_disassemble_return_address() {
+        // Calls the function at 10 with the return address 9 on the stack
+        let program = vec![
+            109, 20, 21101, 9, 0, 0, 1105, 1, 10, 99, 2106, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let lines = disassemble(&program);
+
+        let instruction_addresses: Vec<_> = lines
+            .iter()
+            .filter(|line| matches!(line, Line::Instruction { .. }))
+            .map(Line::address)
+            .collect();
+        assert_eq!(vec![0, 2, 6, 9, 10], instruction_addresses);
+    }
+
+    #[test]
+    fn test_disassemble_day_9_sample() {
+        let program = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        let listing = format_listing(&disassemble(&program));
+
+        assert!(listing.contains("    0: arb #1 "));
+        assert!(listing.contains("    2: out rb-1 "));
+        assert!(listing.contains("    8: eq [100], #16, [101] "));
+        assert!(listing.contains("   12: jf [101], #0 "));
+    }
+}
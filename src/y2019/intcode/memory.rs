@@ -0,0 +1,413 @@
+//! Sparse, paged, copy-on-write memory for Intcode programs.
+//!
+//! Memory is allocated a page at a time as it's written, so a write to a high address only costs
+//! one page. Pages near the start of memory are found by index, and pages past that are kept in a
+//! map.
+//!
+//! Pages are shared between clones of a [`Memory`] until one of them writes to the page, which
+//! copies it unless no other clone still has it. Cloning never copies pages, so forking a program
+//! stays cheap even when thousands of forks are alive, since most of them only ever write to a few
+//! pages.
+//!
+//! Pages loaded from the program also keep every word decoded as an instruction, so executing an
+//! instruction doesn't need to split it into an opcode and parameter modes every time. The decoded
+//! words are shared by every copy of the page and never change, so a decoded word is only used
+//! while the page still holds the word it was decoded from. That way self-modifying programs see
+//! the new instruction as soon as it's written, and writes don't have to update anything.
+
+use super::disassembler::Instruction;
+use super::IntcodeErrorReason;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+pub const PAGE_SIZE: usize = 512;
+
+// Pages below this index are kept in a Vec, the rest in a map
+const DENSE_PAGES: usize = 4096;
+
+// Programs fail with MemoryLimitExceeded rather than allocating more than this many words, unless
+// given a different limit
+pub const DEFAULT_MEMORY_LIMIT: usize = 1 << 24;
+
+// An instruction's opcode and the parameter mode digits, which aren't checked until they're used
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Decoded {
+    pub opcode: u8,
+    pub modes: [u8; 3],
+}
+
+impl Decoded {
+    const ZERO: Option<Self> = Some(Self {
+        opcode: 0,
+        modes: [0; 3],
+    });
+
+    // None for negative words, which never have a valid opcode
+    pub fn from_word(word: i64) -> Option<Self> {
+        if word < 0 {
+            return None;
+        }
+
+        let digit = |divisor: i64| ((word / divisor) % 10) as u8;
+        Some(Self {
+            opcode: (word % 100) as u8,
+            modes: [digit(100), digit(1000), digit(10000)],
+        })
+    }
+}
+
+// A page's words as they were loaded, along with each of them decoded
+#[derive(Debug)]
+struct Predecoded {
+    words: [i64; PAGE_SIZE],
+    decoded: [Option<Decoded>; PAGE_SIZE],
+}
+
+#[derive(Debug, Clone)]
+struct Page {
+    words: [i64; PAGE_SIZE],
+    // None for pages that were allocated by a write rather than loaded
+    predecoded: Option<Arc<Predecoded>>,
+}
+
+impl Page {
+    #[inline]
+    fn decoded(&self, offset: usize) -> Option<Decoded> {
+        let word = self.words[offset];
+        match &self.predecoded {
+            Some(predecoded) if predecoded.words[offset] == word => predecoded.decoded[offset],
+            _ => Decoded::from_word(word),
+        }
+    }
+}
+
+const ZERO_PAGE: Page = Page {
+    words: [0; PAGE_SIZE],
+    predecoded: None,
+};
+
+#[derive(Debug, Clone)]
+pub struct Memory {
+    dense_pages: Vec<Option<Arc<Page>>>,
+    sparse_pages: BTreeMap<usize, Arc<Page>>,
+    allocated_pages: usize,
+    // One past the highest address that was loaded or written
+    len: usize,
+    // In words
+    limit: Option<usize>,
+}
+
+impl Memory {
+    pub fn new(program: &[i64]) -> Self {
+        let mut memory = Self {
+            dense_pages: Vec::new(),
+            sparse_pages: BTreeMap::new(),
+            allocated_pages: 0,
+            len: 0,
+            limit: None,
+        };
+
+        for (i, chunk) in program.chunks(PAGE_SIZE).enumerate() {
+            let mut words = [0; PAGE_SIZE];
+            words[..chunk.len()].copy_from_slice(chunk);
+            let mut decoded = [Decoded::ZERO; PAGE_SIZE];
+            for (decoded, &word) in decoded.iter_mut().zip(chunk) {
+                *decoded = Decoded::from_word(word);
+            }
+            let predecoded = Predecoded { words, decoded };
+
+            let page = Page {
+                words,
+                predecoded: Some(Arc::new(predecoded)),
+            };
+            memory.insert_page(i, Arc::new(page));
+        }
+        memory.len = program.len();
+
+        memory.limit = Some(DEFAULT_MEMORY_LIMIT.max(memory.allocated_words()));
+        memory
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn allocated_words(&self) -> usize {
+        self.allocated_pages * PAGE_SIZE
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    // Limits how many words can be allocated, or removes the limit if None. Memory that's already
+    // allocated is kept even if it's over the new limit
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    #[inline]
+    fn page(&self, index: usize) -> Option<&Page> {
+        let page = if index < DENSE_PAGES {
+            self.dense_pages.get(index)?.as_ref()
+        } else {
+            self.sparse_pages.get(&index)
+        };
+        page.map(|page| &**page)
+    }
+
+    fn insert_page(&mut self, index: usize, page: Arc<Page>) {
+        if index < DENSE_PAGES {
+            if index >= self.dense_pages.len() {
+                self.dense_pages.resize(index + 1, None);
+            }
+            self.dense_pages[index] = Some(page);
+        } else {
+            self.sparse_pages.insert(index, page);
+        }
+        self.allocated_pages += 1;
+    }
+
+    // Addresses that were never written read as 0
+    #[inline]
+    pub fn get(&self, address: usize) -> i64 {
+        match self.page(address / PAGE_SIZE) {
+            Some(page) => page.words[address % PAGE_SIZE],
+            None => 0,
+        }
+    }
+
+    // The instruction at address along with the words of its parameters, looking up the page
+    // once when they're all on it
+    #[inline]
+    pub fn fetch(&self, address: usize) -> (Option<Decoded>, [i64; 3]) {
+        let offset = address % PAGE_SIZE;
+        match self.page(address / PAGE_SIZE) {
+            Some(page) if offset + 4 <= PAGE_SIZE => {
+                let decoded = page.decoded(offset);
+                let mut parameters = [0; 3];
+                parameters.copy_from_slice(&page.words[offset + 1..offset + 4]);
+                (decoded, parameters)
+            }
+            _ => (
+                self.decoded(address),
+                std::array::from_fn(|i| self.get(address + 1 + i)),
+            ),
+        }
+    }
+
+    // The instruction's word at address and the words of its parameters, without using the decoded
+    // words. Only used to compare against fetch
+    #[inline]
+    pub fn fetch_words(&self, address: usize) -> [i64; 4] {
+        let offset = address % PAGE_SIZE;
+        match self.page(address / PAGE_SIZE) {
+            Some(page) if offset + 4 <= PAGE_SIZE => {
+                let mut words = [0; 4];
+                words.copy_from_slice(&page.words[offset..offset + 4]);
+                words
+            }
+            _ => std::array::from_fn(|i| self.get(address + i)),
+        }
+    }
+
+    // The word at address decoded as an instruction, the same as Decoded::from_word(get(address))
+    #[inline]
+    pub fn decoded(&self, address: usize) -> Option<Decoded> {
+        match self.page(address / PAGE_SIZE) {
+            Some(page) => page.decoded(address % PAGE_SIZE),
+            None => Decoded::ZERO,
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, address: usize, value: i64) -> Result<(), IntcodeErrorReason> {
+        let index = address / PAGE_SIZE;
+        if self.page(index).is_none() {
+            if let Some(limit) = self.limit {
+                if self.allocated_words() + PAGE_SIZE > limit {
+                    return Err(IntcodeErrorReason::MemoryLimitExceeded(limit));
+                }
+            }
+            self.insert_page(index, Arc::new(ZERO_PAGE));
+        }
+
+        let page = if index < DENSE_PAGES {
+            self.dense_pages[index].as_mut()
+        } else {
+            self.sparse_pages.get_mut(&index)
+        };
+        let page = Arc::make_mut(page.expect("page should have been allocated"));
+        page.words[address % PAGE_SIZE] = value;
+
+        self.len = self.len.max(address + 1);
+        Ok(())
+    }
+
+    // Makes memory at least len words long without allocating anything
+    pub(super) fn extend_to(&mut self, len: usize) {
+        self.len = self.len.max(len);
+    }
+
+    // Decodes the instruction at address, treating the end of memory as the end of the program
+    pub fn decode(&self, address: usize) -> Option<Instruction> {
+        let end = self.len.min(address + 4);
+        let words: Vec<_> = (address..end).map(|address| self.get(address)).collect();
+        Instruction::decode(&words, 0)
+    }
+
+    // Allocated pages and their starting addresses, in order. Memory outside of these is all 0
+    pub fn pages(&self) -> impl Iterator<Item = (usize, &[i64])> {
+        let dense = self
+            .dense_pages
+            .iter()
+            .enumerate()
+            .filter_map(|(index, page)| Some((index, page.as_ref()?)));
+        let sparse = self.sparse_pages.iter().map(|(&index, page)| (index, page));
+
+        dense
+            .chain(sparse)
+            .map(|(index, page)| (index * PAGE_SIZE, &page.words[..]))
+    }
+
+    // All len words of memory. Callers should check len first, since a single write to a high
+    // address makes this huge
+    pub fn to_vec(&self) -> Vec<i64> {
+        (0..self.len).map(|address| self.get(address)).collect()
+    }
+
+    // How many pages are still shared with other, i.e. haven't been copied by a write to either
+    pub fn shared_pages(&self, other: &Self) -> usize {
+        let dense = self
+            .dense_pages
+            .iter()
+            .zip(&other.dense_pages)
+            .filter(|(a, b)| matches!((a, b), (Some(a), Some(b)) if Arc::ptr_eq(a, b)))
+            .count();
+        let sparse = self
+            .sparse_pages
+            .iter()
+            .filter(|(index, a)| {
+                other
+                    .sparse_pages
+                    .get(index)
+                    .is_some_and(|b| Arc::ptr_eq(a, b))
+            })
+            .count();
+
+        dense + sparse
+    }
+}
+
+impl PartialEq for Memory {
+    // Compares contents, so unallocated pages are equal to pages of zeros
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        let indices: BTreeSet<_> = self
+            .pages()
+            .chain(other.pages())
+            .map(|(address, _)| address / PAGE_SIZE)
+            .collect();
+        indices.into_iter().all(|index| {
+            let a = self.page(index).map_or(&ZERO_PAGE, |page| page);
+            let b = other.page(index).map_or(&ZERO_PAGE, |page| page);
+            a.words == b.words
+        })
+    }
+}
+
+impl Eq for Memory {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_on_write() {
+        let program: Vec<_> = (0..2000).collect();
+        let mut memory = Memory::new(&program);
+        assert_eq!(2000, memory.len());
+        assert_eq!(1999, memory.get(1999));
+        assert_eq!(0, memory.get(5000));
+
+        let fork = memory.clone();
+        assert_eq!(4, memory.shared_pages(&fork));
+
+        memory.set(600, -1).unwrap();
+        assert_eq!(3, memory.shared_pages(&fork));
+        assert_eq!(-1, memory.get(600));
+        assert_eq!(600, fork.get(600));
+        assert_ne!(memory, fork);
+
+        memory.set(5000, 7).unwrap();
+        assert_eq!(5001, memory.len());
+        assert_eq!(7, memory.to_vec()[5000]);
+        assert_eq!(0, memory.to_vec()[4999]);
+    }
+
+    #[test]
+    fn test_decoded() {
+        let mut memory = Memory::new(&[1002, -1, 21107]);
+        let decoded = |opcode, modes| Some(Decoded { opcode, modes });
+
+        assert_eq!(decoded(2, [0, 1, 0]), memory.decoded(0));
+        assert_eq!(None, memory.decoded(1));
+        assert_eq!(decoded(7, [1, 1, 2]), memory.decoded(2));
+        assert_eq!(decoded(0, [0, 0, 0]), memory.decoded(1 << 40));
+        assert_eq!([-1, 21107, 0, 0], memory.fetch_words(1));
+
+        // Writes replace the decoded word, including in pages shared with a fork
+        let fork = memory.clone();
+        memory.set(0, 399).unwrap();
+        assert_eq!(decoded(99, [3, 0, 0]), memory.decoded(0));
+        assert_eq!(decoded(2, [0, 1, 0]), fork.decoded(0));
+    }
+
+    #[test]
+    fn test_sparse() {
+        let mut memory = Memory::new(&[1, 2, 3]);
+        memory.set(1 << 40, 5).unwrap();
+        memory.set((1 << 40) + 1, 6).unwrap();
+
+        assert_eq!(5, memory.get(1 << 40));
+        assert_eq!(6, memory.get((1 << 40) + 1));
+        assert_eq!(0, memory.get(1 << 39));
+        assert_eq!((1 << 40) + 2, memory.len());
+        assert_eq!(2 * PAGE_SIZE, memory.allocated_words());
+
+        let starts: Vec<_> = memory.pages().map(|(address, _)| address).collect();
+        assert_eq!(vec![0, 1 << 40], starts);
+
+        // Writing zeros allocates pages but doesn't change the contents
+        let mut zeroed = memory.clone();
+        zeroed.set(1 << 30, 0).unwrap();
+        assert_eq!(memory, zeroed);
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut memory = Memory::new(&[1, 2, 3]);
+        memory.set_limit(Some(2 * PAGE_SIZE));
+
+        memory.set(PAGE_SIZE - 1, 1).unwrap();
+        memory.set(1 << 20, 1).unwrap();
+        assert_eq!(
+            Err(IntcodeErrorReason::MemoryLimitExceeded(2 * PAGE_SIZE)),
+            memory.set(1 << 21, 1)
+        );
+        assert_eq!(0, memory.get(1 << 21));
+
+        // Writes to allocated pages still work
+        memory.set((1 << 20) + 1, 1).unwrap();
+
+        memory.set_limit(None);
+        memory.set(1 << 21, 1).unwrap();
+    }
+}
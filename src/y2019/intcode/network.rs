@@ -0,0 +1,531 @@
+//! Networks of Intcode machines that send each other packets, like the 2019 day 7 amplifiers and
+//! the day 23 NICs.
+//!
+//! Machines are identified by address and run one at a time in address order, each until it needs
+//! input that hasn't arrived yet or halts. A [`Framing`] says how a machine's outputs are split into
+//! packets, and a [`Router`] decides where each packet goes:
+//! ```text
+//! let mut network = Network::new(Framing::Addressed { payload_len: 2 }, NatRouter::new(255, 0));
+//! network.set_idle_input(Some(-1));
+//! for address in 0..50 {
+//!     network.add_machine(address, program.clone(), &[address])?;
+//! }
+//! network.run_until_idle()?;
+//! network.wake()?; // NAT sends its last packet to address 0
+//! ```
+//!
+//! The network is idle once every machine is halted or waiting for input with nothing to read and
+//! no packets are in flight. Every packet sent is kept in a log, in the order it was sent.
+
+use super::{InteractiveIntcodeProgram, RunState};
+use crate::SimpleError;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Packet {
+    pub source: i64,
+    // None if the machine's outputs don't include an address, in which case the router decides
+    pub destination: Option<i64>,
+    pub payload: Vec<i64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Framing {
+    // Every output is a packet on its own
+    Unaddressed,
+    // Packets are a destination address followed by payload_len values
+    Addressed { payload_len: usize },
+}
+
+pub trait Router {
+    // Decides where a packet goes, returning the packets to deliver. Delivered packets need the
+    // address of a machine in addresses as their destination
+    fn route(&mut self, packet: Packet, addresses: &[i64]) -> Result<Vec<Packet>, SimpleError>;
+
+    // Returns packets to deliver when the network is idle, or nothing to leave it idle
+    fn idle(&mut self) -> Result<Vec<Packet>, SimpleError> {
+        Ok(Vec::new())
+    }
+}
+
+// Delivers packets to their destination. Unaddressed packets go to the machine their source is
+// linked to
+#[derive(Debug, Clone, Default)]
+pub struct DirectRouter {
+    links: HashMap<i64, i64>,
+}
+
+impl DirectRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn link(&mut self, source: i64, destination: i64) {
+        self.links.insert(source, destination);
+    }
+}
+
+impl Router for DirectRouter {
+    fn route(
+        &mut self,
+        mut packet: Packet,
+        _addresses: &[i64],
+    ) -> Result<Vec<Packet>, SimpleError> {
+        if packet.destination.is_none() {
+            packet.destination = self.links.get(&packet.source).copied();
+        }
+        Ok(vec![packet])
+    }
+}
+
+// Delivers packets to their destination, except that packets sent to the broadcast address (and
+// unaddressed packets) go to every machine other than the sender
+#[derive(Debug, Clone)]
+pub struct BroadcastRouter {
+    address: i64,
+}
+
+impl BroadcastRouter {
+    pub fn new(address: i64) -> Self {
+        Self { address }
+    }
+}
+
+impl Router for BroadcastRouter {
+    fn route(&mut self, packet: Packet, addresses: &[i64]) -> Result<Vec<Packet>, SimpleError> {
+        if packet
+            .destination
+            .is_some_and(|destination| destination != self.address)
+        {
+            return Ok(vec![packet]);
+        }
+
+        Ok(addresses
+            .iter()
+            .filter(|&&address| address != packet.source)
+            .map(|&address| Packet {
+                destination: Some(address),
+                ..packet.clone()
+            })
+            .collect())
+    }
+}
+
+// Delivers packets to their destination, except for packets sent to the NAT's own address. The
+// NAT holds on to the last of those and sends it to its target when the network is idle
+#[derive(Debug, Clone)]
+pub struct NatRouter {
+    address: i64,
+    target: i64,
+    last_payload: Option<Vec<i64>>,
+}
+
+impl NatRouter {
+    pub fn new(address: i64, target: i64) -> Self {
+        Self {
+            address,
+            target,
+            last_payload: None,
+        }
+    }
+
+    pub fn last_payload(&self) -> Option<&[i64]> {
+        self.last_payload.as_deref()
+    }
+}
+
+impl Router for NatRouter {
+    fn route(&mut self, packet: Packet, _addresses: &[i64]) -> Result<Vec<Packet>, SimpleError> {
+        if packet.destination == Some(self.address) {
+            self.last_payload = Some(packet.payload);
+            return Ok(Vec::new());
+        }
+        Ok(vec![packet])
+    }
+
+    fn idle(&mut self) -> Result<Vec<Packet>, SimpleError> {
+        Ok(self
+            .last_payload
+            .iter()
+            .map(|payload| Packet {
+                source: self.address,
+                destination: Some(self.target),
+                payload: payload.clone(),
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug)]
+struct Machine {
+    program: InteractiveIntcodeProgram,
+    inbox: VecDeque<i64>,
+    // Outputs that don't make up a full packet yet
+    frame: Vec<i64>,
+    waiting: bool,
+    halted: bool,
+}
+
+#[derive(Debug)]
+pub struct Network<R: Router> {
+    machines: Vec<Machine>,
+    addresses: Vec<i64>,
+    indices: HashMap<i64, usize>,
+    framing: Framing,
+    router: R,
+    idle_input: Option<i64>,
+    log: Vec<Packet>,
+}
+
+impl<R: Router> Network<R> {
+    pub fn new(framing: Framing, router: R) -> Self {
+        Self {
+            machines: Vec::new(),
+            addresses: Vec::new(),
+            indices: HashMap::new(),
+            framing,
+            router,
+            idle_input: None,
+            log: Vec::new(),
+        }
+    }
+
+    // Adds a machine with the given inputs already queued, e.g. its address or phase setting
+    pub fn add_machine(
+        &mut self,
+        address: i64,
+        program: Vec<i64>,
+        inputs: &[i64],
+    ) -> Result<(), SimpleError> {
+        if self.indices.contains_key(&address) {
+            return Err(SimpleError::runtime(format!(
+                "duplicate machine address: {address}"
+            )));
+        }
+
+        self.indices.insert(address, self.machines.len());
+        self.addresses.push(address);
+        self.machines.push(Machine {
+            program: InteractiveIntcodeProgram::new(program),
+            inbox: inputs.iter().copied().collect(),
+            frame: Vec::new(),
+            waiting: false,
+            halted: false,
+        });
+
+        Ok(())
+    }
+
+    // The value a machine reads when it needs input and none has arrived, if machines poll for
+    // input rather than waiting for it
+    pub fn set_idle_input(&mut self, idle_input: Option<i64>) {
+        self.idle_input = idle_input;
+    }
+
+    pub fn set_step_limit(&mut self, step_limit: Option<u64>) {
+        for machine in &mut self.machines {
+            machine.program.set_step_limit(step_limit);
+        }
+    }
+
+    pub fn addresses(&self) -> &[i64] {
+        &self.addresses
+    }
+
+    pub fn program(&self, address: i64) -> Option<&InteractiveIntcodeProgram> {
+        let &index = self.indices.get(&address)?;
+        Some(&self.machines[index].program)
+    }
+
+    pub fn router(&self) -> &R {
+        &self.router
+    }
+
+    pub fn router_mut(&mut self) -> &mut R {
+        &mut self.router
+    }
+
+    pub fn log(&self) -> &[Packet] {
+        &self.log
+    }
+
+    pub fn all_halted(&self) -> bool {
+        self.machines.iter().all(|machine| machine.halted)
+    }
+
+    // Runs every machine that has something to do once, returning whether the network was idle
+    pub fn run_round(&mut self) -> Result<bool, SimpleError> {
+        let mut idle = true;
+        for index in 0..self.machines.len() {
+            let machine = &mut self.machines[index];
+            if machine.halted {
+                continue;
+            }
+
+            if machine.waiting && machine.inbox.is_empty() {
+                match self.idle_input {
+                    Some(input) => machine.program.push_input(input),
+                    None => continue,
+                }
+            } else {
+                idle = false;
+                for input in machine.inbox.drain(..) {
+                    machine.program.push_input(input);
+                }
+            }
+
+            let state = machine.program.execute()?.check_step_limit()?;
+            machine.waiting = true;
+            if state == RunState::Halted {
+                machine.halted = true;
+                idle = false;
+            }
+
+            let packets = self.take_packets(index)?;
+            if !packets.is_empty() {
+                idle = false;
+            }
+            for packet in packets {
+                self.log.push(packet.clone());
+                for packet in self.router.route(packet, &self.addresses)? {
+                    self.deliver(packet)?;
+                }
+            }
+        }
+
+        Ok(idle)
+    }
+
+    pub fn run_until_idle(&mut self) -> Result<(), SimpleError> {
+        while !self.run_round()? {}
+        Ok(())
+    }
+
+    // Delivers whatever the router sends when the network is idle, returning those packets. If
+    // there are none then the network stays idle
+    pub fn wake(&mut self) -> Result<Vec<Packet>, SimpleError> {
+        let packets = self.router.idle()?;
+        for packet in &packets {
+            self.log.push(packet.clone());
+            self.deliver(packet.clone())?;
+        }
+        Ok(packets)
+    }
+
+    fn take_packets(&mut self, index: usize) -> Result<Vec<Packet>, SimpleError> {
+        let source = self.addresses[index];
+        let machine = &mut self.machines[index];
+        let outputs = machine.program.fetch_outputs();
+
+        let packets = match self.framing {
+            Framing::Unaddressed => outputs
+                .into_iter()
+                .map(|output| Packet {
+                    source,
+                    destination: None,
+                    payload: vec![output],
+                })
+                .collect(),
+            Framing::Addressed { payload_len } => {
+                let mut packets = Vec::new();
+                for output in outputs {
+                    machine.frame.push(output);
+                    if machine.frame.len() == payload_len + 1 {
+                        packets.push(Packet {
+                            source,
+                            destination: Some(machine.frame[0]),
+                            payload: machine.frame.split_off(1),
+                        });
+                        machine.frame.clear();
+                    }
+                }
+                packets
+            }
+        };
+
+        if machine.halted && !machine.frame.is_empty() {
+            return Err(SimpleError::runtime(format!(
+                "machine {source} halted partway through sending a packet: {:?}",
+                machine.frame
+            )));
+        }
+
+        Ok(packets)
+    }
+
+    fn deliver(&mut self, packet: Packet) -> Result<(), SimpleError> {
+        let destination = packet.destination.ok_or_else(|| {
+            SimpleError::runtime(format!(
+                "packet from {} has no destination: {:?}",
+                packet.source, packet.payload
+            ))
+        })?;
+        let &index = self.indices.get(&destination).ok_or_else(|| {
+            SimpleError::runtime(format!(
+                "packet from {} sent to unknown address {destination}: {:?}",
+                packet.source, packet.payload
+            ))
+        })?;
+
+        self.machines[index].inbox.extend(packet.payload);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode::assembler;
+
+    // Adds one to every value it reads and passes it on, halting once it has sent 10 or more
+    const COUNTER: &str = "\
+loop:   in [v]
+        add [v], #1, [v]
+        out [v]
+        lt [v], #10, [t]
+        jt [t], #loop
+        hlt
+v:      data 0
+t:      data 0
+";
+
+    // Reads its address, sends (255, address, 42) and then polls for packets forever
+    const NAT_CLIENT: &str = "\
+        in [a]
+        out #255
+        out [a]
+        out #42
+poll:   in [x]
+        eq [x], #-1, [t]
+        jt [t], #poll
+        in [y]
+        jt #1, #poll
+a:      data 0
+x:      data 0
+y:      data 0
+t:      data 0
+";
+
+    // Machine 0 broadcasts (0, 5) to address 9, and everyone else replies to it with their address
+    // and the second value
+    const BROADCAST_CLIENT: &str = "\
+        in [a]
+        jt [a], #poll
+        out #9
+        out #0
+        out #5
+poll:   in [x]
+        eq [x], #-1, [t]
+        jt [t], #poll
+        in [y]
+        jf [a], #poll
+        out #0
+        out [a]
+        out [y]
+        jt #1, #poll
+a:      data 0
+x:      data 0
+y:      data 0
+t:      data 0
+";
+
+    #[test]
+    fn test_direct_ring() {
+        let program = assembler::assemble(COUNTER).unwrap();
+
+        let mut router = DirectRouter::new();
+        router.link(0, 1);
+        router.link(1, 0);
+
+        let mut network = Network::new(Framing::Unaddressed, router);
+        network.add_machine(0, program.clone(), &[0]).unwrap();
+        network.add_machine(1, program, &[]).unwrap();
+        network.run_until_idle().unwrap();
+
+        assert!(network.all_halted());
+        let values: Vec<_> = network
+            .log()
+            .iter()
+            .map(|packet| packet.payload[0])
+            .collect();
+        assert_eq!((1..=11).collect::<Vec<_>>(), values);
+        assert_eq!(0, network.log()[10].source);
+    }
+
+    #[test]
+    fn test_nat() {
+        let program = assembler::assemble(NAT_CLIENT).unwrap();
+
+        let mut network = Network::new(
+            Framing::Addressed { payload_len: 2 },
+            NatRouter::new(255, 0),
+        );
+        network.set_idle_input(Some(-1));
+        for address in 0..2 {
+            network
+                .add_machine(address, program.clone(), &[address])
+                .unwrap();
+        }
+
+        network.run_until_idle().unwrap();
+        assert_eq!(2, network.log().len());
+        assert_eq!(Some(&[1, 42][..]), network.router().last_payload());
+
+        let woken = network.wake().unwrap();
+        assert_eq!(
+            vec![Packet {
+                source: 255,
+                destination: Some(0),
+                payload: vec![1, 42],
+            }],
+            woken
+        );
+        network.run_until_idle().unwrap();
+        assert_eq!(3, network.log().len());
+        assert!(!network.all_halted());
+
+        assert!(network.add_machine(1, Vec::new(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let program = assembler::assemble(BROADCAST_CLIENT).unwrap();
+
+        let mut network = Network::new(
+            Framing::Addressed { payload_len: 2 },
+            BroadcastRouter::new(9),
+        );
+        network.set_idle_input(Some(-1));
+        for address in 0..3 {
+            network
+                .add_machine(address, program.clone(), &[address])
+                .unwrap();
+        }
+        network.run_until_idle().unwrap();
+
+        let sent: Vec<_> = network
+            .log()
+            .iter()
+            .map(|packet| (packet.source, packet.destination, packet.payload.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, Some(9), vec![0, 5]),
+                (1, Some(0), vec![1, 5]),
+                (2, Some(0), vec![2, 5]),
+            ],
+            sent
+        );
+    }
+
+    #[test]
+    fn test_unknown_address() {
+        let program = assembler::assemble(NAT_CLIENT).unwrap();
+
+        let mut network = Network::new(Framing::Addressed { payload_len: 2 }, DirectRouter::new());
+        network.set_idle_input(Some(-1));
+        network.add_machine(0, program, &[0]).unwrap();
+
+        assert!(network.run_until_idle().is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Text screens for Intcode programs that draw tiles, like the 2019 day 13 arcade cabinet.
+//!
+//! Programs output `(x, y, tile)` triples, with `(-1, 0, score)` setting the score instead of
+//! drawing a tile. A [`TileScreen`] keeps track of what's been drawn and renders it as text using a
+//! palette with one character per tile ID, with y increasing down the screen:
+//! ```text
+//! score: 1200
+//! █████
+//! █ # █
+//! █ ● █
+//! █ ▬ █
+//! ```
+//!
+//! Screens can also write frames as they go with [`TileScreen::write_frame`]. Each frame starts
+//! with a form feed, so a file of them can be played back with [`replay`].
+
+use crate::SimpleError;
+use std::collections::HashMap;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+pub const SCORE_POSITION: (i64, i64) = (-1, 0);
+
+const FRAME_SEPARATOR: char = '\x0c';
+
+#[derive(Debug, Clone)]
+pub struct TileScreen {
+    tiles: HashMap<(i64, i64), i64>,
+    // Where each tile ID was most recently drawn, e.g. to follow the ball in day 13
+    last_drawn: HashMap<i64, (i64, i64)>,
+    palette: Vec<char>,
+    score: Option<i64>,
+    // Outputs that don't make up a full triple yet
+    pending: Vec<i64>,
+}
+
+impl TileScreen {
+    // Creates a screen that draws tile ID i as the ith char of palette. Tile 0 is the background
+    pub fn new(palette: &str) -> Self {
+        Self {
+            tiles: HashMap::new(),
+            last_drawn: HashMap::new(),
+            palette: palette.chars().collect(),
+            score: None,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn draw(&mut self, x: i64, y: i64, tile: i64) {
+        self.tiles.insert((x, y), tile);
+        self.last_drawn.insert(tile, (x, y));
+    }
+
+    // Consumes a program's outputs as (x, y, tile) triples. A partial triple at the end is kept
+    // until the rest of it arrives
+    pub fn extend(&mut self, outputs: &[i64]) -> Result<(), SimpleError> {
+        for &output in outputs {
+            self.pending.push(output);
+            if self.pending.len() < 3 {
+                continue;
+            }
+
+            let (x, y, value) = (self.pending[0], self.pending[1], self.pending[2]);
+            self.pending.clear();
+
+            if (x, y) == SCORE_POSITION {
+                self.score = Some(value);
+                continue;
+            }
+
+            if usize::try_from(value).map_or(true, |tile| tile >= self.palette.len()) {
+                return Err(SimpleError::runtime(format!(
+                    "invalid tile {value} at ({x}, {y})"
+                )));
+            }
+            self.draw(x, y, value);
+        }
+
+        Ok(())
+    }
+
+    pub fn tile(&self, x: i64, y: i64) -> i64 {
+        self.tiles.get(&(x, y)).copied().unwrap_or(0)
+    }
+
+    // Number of positions that have been drawn on, including with the background tile
+    pub fn drawn_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn count(&self, tile: i64) -> usize {
+        self.tiles.values().filter(|&&t| t == tile).count()
+    }
+
+    pub fn last_drawn(&self, tile: i64) -> Option<(i64, i64)> {
+        self.last_drawn.get(&tile).copied()
+    }
+
+    pub fn score(&self) -> Option<i64> {
+        self.score
+    }
+
+    pub fn pending_outputs(&self) -> &[i64] {
+        &self.pending
+    }
+
+    // Renders the smallest rectangle containing every tile other than the background, preceded by
+    // the score if there is one. Lines are separated by newlines with none at the end
+    pub fn render(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(score) = self.score {
+            lines.push(format!("score: {score}"));
+        }
+
+        let drawn = self.tiles.iter().filter(|(_, &tile)| tile != 0);
+        let bounds = drawn.fold(None, |bounds, (&(x, y), _)| match bounds {
+            None => Some((x, x, y, y)),
+            Some((min_x, max_x, min_y, max_y)) => {
+                Some((min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y)))
+            }
+        });
+
+        if let Some((min_x, max_x, min_y, max_y)) = bounds {
+            for y in min_y..=max_y {
+                let line = (min_x..=max_x)
+                    .map(|x| {
+                        let tile = usize::try_from(self.tile(x, y)).ok();
+                        tile.and_then(|tile| self.palette.get(tile))
+                            .copied()
+                            .unwrap_or('?')
+                    })
+                    .collect();
+                lines.push(line);
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    // Writes the current screen to out as a frame that replay() can play back
+    pub fn write_frame(&self, out: &mut dyn Write) -> Result<(), SimpleError> {
+        writeln!(out, "{FRAME_SEPARATOR}{}", self.render())?;
+        Ok(())
+    }
+}
+
+pub fn parse_frames(s: &str) -> Vec<&str> {
+    s.split(FRAME_SEPARATOR)
+        .filter(|frame| !frame.is_empty())
+        .collect()
+}
+
+// Plays back a frame dump, clearing the terminal before each frame
+pub fn replay(dump: &str, fps: u32, mut out: impl Write) -> Result<(), SimpleError> {
+    let delay = Duration::from_secs(1) / fps.max(1);
+    for frame in parse_frames(dump) {
+        write!(out, "\x1b[H\x1b[2J{frame}")?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut screen = TileScreen::new(" █#");
+        screen
+            .extend(&[0, 0, 1, 1, 0, 1, 2, 0, 1, 1, 1, 2, -1, 0, 500, 2, 2])
+            .unwrap();
+
+        assert_eq!("score: 500\n███\n # ", screen.render());
+        assert_eq!(Some(500), screen.score());
+        assert_eq!(&[2, 2], screen.pending_outputs());
+        assert_eq!(1, screen.count(2));
+        assert_eq!(Some((1, 1)), screen.last_drawn(2));
+
+        screen.extend(&[1]).unwrap();
+        assert_eq!("score: 500\n███\n # \n  █", screen.render());
+        assert_eq!(5, screen.drawn_count());
+
+        assert!(screen.extend(&[0, 0, 3]).is_err());
+        assert!(screen.extend(&[0, 0, -1]).is_err());
+    }
+
+    #[test]
+    fn test_write_frame() {
+        let mut screen = TileScreen::new(" #");
+        let mut out = Vec::new();
+        screen.extend(&[0, 0, 1]).unwrap();
+        screen.write_frame(&mut out).unwrap();
+        screen.extend(&[-1, 0, 7]).unwrap();
+        screen.write_frame(&mut out).unwrap();
+
+        let dump = String::from_utf8(out).unwrap();
+        assert_eq!(vec!["#\n", "score: 7\n#\n"], parse_frames(&dump));
+    }
+
+    #[test]
+    fn test_parse_frames() {
+        let dump = "\x0cscore: 0\n#\n\x0cscore: 1\n.\n";
+        assert_eq!(vec!["score: 0\n#\n", "score: 1\n.\n"], parse_frames(dump));
+    }
+}
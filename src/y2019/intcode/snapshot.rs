@@ -0,0 +1,315 @@
+//! Saved states of interactive Intcode programs.
+//!
+//! A [`Snapshot`] shares memory pages with the program it was taken from, so taking one is cheap.
+//! Snapshots can also be written out as text and parsed back, to resume a long session later:
+//! ```text
+//! intcode snapshot 1
+//! ip 0
+//! rb 0
+//! steps 8
+//! inputs -2
+//! outputs 5,12
+//! memory 14
+//! page 0 3,13,1,12,13,12,4,12,1005,13,0,99,12,7
+//! ```
+//! Memory is written as its length followed by the contents of each allocated page, without
+//! trailing zeros. Everything else in memory is 0.
+
+use super::memory::{Memory, DEFAULT_MEMORY_LIMIT};
+use crate::SimpleError;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const HEADER: &str = "intcode snapshot 1";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Snapshot {
+    pub(super) memory: Memory,
+    pub(super) ip: usize,
+    pub(super) relative_base: i64,
+    pub(super) steps: u64,
+    pub(super) inputs: VecDeque<i64>,
+    pub(super) outputs: VecDeque<i64>,
+}
+
+impl Snapshot {
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+}
+
+fn join(values: impl IntoIterator<Item = i64>) -> String {
+    let values: Vec<_> = values.into_iter().map(|value| value.to_string()).collect();
+    values.join(",")
+}
+
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "ip {}", self.ip)?;
+        writeln!(f, "rb {}", self.relative_base)?;
+        writeln!(f, "steps {}", self.steps)?;
+        writeln!(f, "inputs {}", join(self.inputs.iter().copied()))?;
+        writeln!(f, "outputs {}", join(self.outputs.iter().copied()))?;
+        writeln!(f, "memory {}", self.memory.len())?;
+        for (address, page) in self.memory.pages() {
+            let end = page
+                .iter()
+                .rposition(|&value| value != 0)
+                .map_or(0, |i| i + 1);
+            if end > 0 {
+                writeln!(f, "page {address} {}", join(page[..end].iter().copied()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Parses the value of the next line, which must start with key
+fn field<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    key: &str,
+) -> Result<(usize, &'a str, &'a str), SimpleError> {
+    let (i, line) = lines
+        .next()
+        .ok_or_else(|| SimpleError::new(format!("snapshot is missing {key}")))?;
+
+    let value = line
+        .strip_prefix(key)
+        .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+        .ok_or_else(|| SimpleError::new(format!("expected {key}")).at_line(i + 1, line))?;
+
+    Ok((i + 1, line, value.trim_start()))
+}
+
+fn parse_value<'a, T>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    key: &str,
+) -> Result<T, SimpleError>
+where
+    T: FromStr,
+    SimpleError: From<T::Err>,
+{
+    let (line_number, line, value) = field(lines, key)?;
+    crate::simpleerror::parse_token(line, value).map_err(|err| err.at_line(line_number, line))
+}
+
+fn parse_values<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    key: &str,
+) -> Result<Vec<i64>, SimpleError> {
+    let (line_number, line, values) = field(lines, key)?;
+    values
+        .split(',')
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            crate::simpleerror::parse_token(line, value)
+                .map_err(|err| err.at_line(line_number, line))
+        })
+        .collect()
+}
+
+impl FromStr for Snapshot {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate();
+
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            Some((_, line)) => {
+                return Err(
+                    SimpleError::new(format!("not an Intcode snapshot, expected {HEADER}"))
+                        .at_line(1, line),
+                )
+            }
+            None => return Err(SimpleError::new(String::from("snapshot is empty"))),
+        }
+
+        let ip = parse_value(&mut lines, "ip")?;
+        let relative_base = parse_value(&mut lines, "rb")?;
+        let steps = parse_value(&mut lines, "steps")?;
+        let inputs = parse_values(&mut lines, "inputs")?.into();
+        let outputs = parse_values(&mut lines, "outputs")?.into();
+        let len = parse_value(&mut lines, "memory")?;
+
+        let mut memory = Memory::new(&[]);
+        memory.set_limit(Some(DEFAULT_MEMORY_LIMIT));
+        for (i, line) in lines {
+            let (address, values) = line
+                .strip_prefix("page ")
+                .and_then(|rest| rest.split_once(' '))
+                .ok_or_else(|| {
+                    SimpleError::new(String::from("expected page")).at_line(i + 1, line)
+                })?;
+            let address: usize = crate::simpleerror::parse_token(line, address)
+                .map_err(|err| err.at_line(i + 1, line))?;
+
+            for (offset, value) in values.split(',').enumerate() {
+                let value = crate::simpleerror::parse_token(line, value)
+                    .map_err(|err| err.at_line(i + 1, line))?;
+                // Memory's length is one past the last address, so that has to fit too
+                let end = address.checked_add(offset + 1).ok_or_else(|| {
+                    SimpleError::new(String::from("page runs past the end of memory"))
+                        .at_line(i + 1, line)
+                })?;
+                memory.set(end - 1, value).map_err(|reason| {
+                    SimpleError::new(format!("could not restore memory: {reason}"))
+                        .at_line(i + 1, line)
+                })?;
+            }
+        }
+
+        if memory.len() > len {
+            return Err(SimpleError::new(format!(
+                "snapshot has pages past the end of memory ({len} words)"
+            )));
+        }
+        memory.extend_to(len);
+
+        Ok(Self {
+            memory,
+            ip,
+            relative_base,
+            steps,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode::memory::PAGE_SIZE;
+    use crate::y2019::intcode::{InteractiveIntcodeProgram, RunState};
+
+    // Adds up inputs until it reads a 0, outputting the running total each time
+    const ADDER: [i64; 14] = [3, 13, 1, 12, 13, 12, 4, 12, 1005, 13, 0, 99, 0, 0];
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut program = InteractiveIntcodeProgram::new(ADDER.to_vec());
+        program.push_input(5);
+        assert_eq!(Ok(RunState::NeedsInput), program.execute());
+
+        let snapshot = program.snapshot();
+        program.push_input(10);
+        program.push_input(0);
+        assert_eq!(Ok(RunState::Halted), program.execute());
+        assert_eq!(vec![5, 15, 15], program.fetch_outputs());
+
+        program.restore(&snapshot);
+        assert_eq!(snapshot, program.snapshot());
+        program.push_input(1);
+        assert_eq!(Ok(RunState::NeedsInput), program.execute());
+        assert_eq!(vec![5, 6], program.fetch_outputs());
+
+        let mut fork = InteractiveIntcodeProgram::from_snapshot(&snapshot);
+        assert_eq!(vec![5], fork.fetch_outputs());
+        fork.push_input(2);
+        assert_eq!(Ok(RunState::NeedsInput), fork.execute());
+        assert_eq!(vec![7], fork.fetch_outputs());
+    }
+
+    #[test]
+    fn test_fork_shares_memory() {
+        let program = InteractiveIntcodeProgram::new(vec![99; 2000]);
+        let fork = program.fork();
+        assert_eq!(4, program.memory().shared_pages(fork.memory()));
+
+        // Pages the program has already written to are still shared until one of them writes again
+        let mut adder = ADDER.to_vec();
+        adder.resize(2000, 0);
+        let mut adder = InteractiveIntcodeProgram::new(adder);
+        adder.push_input(1);
+        assert_eq!(Ok(RunState::NeedsInput), adder.execute());
+        let adder_fork = adder.fork();
+        assert_eq!(4, adder.memory().shared_pages(adder_fork.memory()));
+
+        adder.push_input(1);
+        assert_eq!(Ok(RunState::NeedsInput), adder.execute());
+        assert_eq!(3, adder.memory().shared_pages(adder_fork.memory()));
+        assert_eq!(1, adder_fork.memory().get(12));
+        assert_eq!(2, adder.memory().get(12));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut program = InteractiveIntcodeProgram::new(ADDER.to_vec());
+        program.push_input(5);
+        program.push_input(7);
+        assert_eq!(Ok(RunState::NeedsInput), program.execute());
+        program.push_input(-2);
+
+        let snapshot = program.snapshot();
+        let s = snapshot.to_string();
+        assert_eq!(
+            [
+                HEADER,
+                "ip 0",
+                "rb 0",
+                "steps 8",
+                "inputs -2",
+                "outputs 5,12",
+                "memory 14",
+                "page 0 3,13,1,12,13,12,4,12,1005,13,0,99,12,7",
+            ]
+            .map(|line| format!("{line}\n"))
+            .concat(),
+            s
+        );
+        assert_eq!(Ok(snapshot), s.parse());
+
+        let err = s.replace("rb 0", "rb x").parse::<Snapshot>().unwrap_err();
+        assert_eq!(3, err.location().unwrap().line.unwrap());
+        assert_eq!(4, err.location().unwrap().column);
+
+        assert!("intcode snapshot 2".parse::<Snapshot>().is_err());
+        assert!(s.replace("inputs", "input").parse::<Snapshot>().is_err());
+        assert!(s
+            .replace("memory 14", "memory 13")
+            .parse::<Snapshot>()
+            .is_err());
+
+        let err = s
+            .replace("page 0", "page x")
+            .parse::<Snapshot>()
+            .unwrap_err();
+        assert_eq!(8, err.location().unwrap().line.unwrap());
+
+        let last_address = usize::MAX.to_string();
+        let err = s
+            .replace("page 0 3,13", &format!("page {last_address} 3,13"))
+            .parse::<Snapshot>()
+            .unwrap_err();
+        assert_eq!(8, err.location().unwrap().line.unwrap());
+
+        // Restored memory has the same limit as a new program's
+        assert_eq!(
+            Some(DEFAULT_MEMORY_LIMIT),
+            s.parse::<Snapshot>().unwrap().memory().limit()
+        );
+    }
+
+    #[test]
+    fn test_serialize_sparse() {
+        // Writes 7 to a high address and then to 0
+        let mut program =
+            InteractiveIntcodeProgram::new(vec![1101, 3, 4, 1_000_000, 1101, 0, 0, 0, 99]);
+        assert_eq!(Ok(RunState::Halted), program.execute());
+
+        let snapshot = program.snapshot();
+        let s = snapshot.to_string();
+        let high_page = format!("{}7", "0,".repeat(1_000_000 % PAGE_SIZE));
+        assert!(s.ends_with(&format!(
+            "memory 1000001\npage 0 0,3,4,1000000,1101,0,0,0,99\npage 999936 {high_page}\n"
+        )));
+        assert_eq!(Ok(snapshot), s.parse());
+    }
+}
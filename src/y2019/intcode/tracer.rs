@@ -0,0 +1,484 @@
+//! Opt-in tracing and profiling of Intcode execution.
+//!
+//! A [`Tracer`] attached with `set_tracer` records every executed instruction, with the values its
+//! parameters read and the value it wrote, either to a file or to an in-memory ring buffer. Every
+//! tracer also keeps a [`Profile`] of how many times each address was executed.
+//!
+//! [`record`] profiles every program created on the current thread without changing the code
+//! that runs them, which is how the `--profile` flag works:
+//! ```text
+//!        count       %     ip  instruction
+//!       183046   12.4%    317  add rb+1, #-1, rb+1
+//! ```
+
+use super::disassembler::Instruction;
+use super::memory::Memory;
+use crate::simpleerror::ErrorKind;
+use crate::SimpleError;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::mem;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TraceEntry {
+    // 0-based count of instructions executed before this one
+    pub step: u64,
+    pub ip: usize,
+    pub relative_base: i64,
+    pub instruction: Instruction,
+    // The values read by each parameter that isn't written to, in order
+    pub operands: Vec<i64>,
+    // The address and value written, if the instruction writes to memory
+    pub write: Option<(usize, i64)>,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let instruction = self.instruction.to_string();
+        write!(f, "{:>10} {:>6}: {instruction:<30} ;", self.step, self.ip)?;
+
+        let operands: Vec<_> = self.operands.iter().map(i64::to_string).collect();
+        if !operands.is_empty() {
+            write!(f, " {}", operands.join(", "))?;
+        }
+        if let Some((address, value)) = self.write {
+            write!(f, " -> [{address}] = {value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// How many times each address was executed, along with the program it was recorded against so
+// that hot addresses can be disassembled
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Profile {
+    counts: Vec<u64>,
+    program: Vec<i64>,
+}
+
+impl Profile {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self {
+            counts: Vec::new(),
+            program,
+        }
+    }
+
+    fn record(&mut self, ip: usize) {
+        if ip >= self.counts.len() {
+            self.counts.resize(ip + 1, 0);
+        }
+        self.counts[ip] += 1;
+    }
+
+    pub fn count(&self, ip: usize) -> u64 {
+        self.counts.get(ip).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    // Adds the counts from another profile of the same program
+    pub fn merge(&mut self, other: &Self) {
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (count, &other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other_count;
+        }
+
+        if self.program.is_empty() {
+            self.program.clone_from(&other.program);
+        }
+    }
+
+    // The most executed addresses and their counts, most executed first
+    pub fn hottest(&self, limit: usize) -> Vec<(usize, u64)> {
+        let mut hottest: Vec<_> = self
+            .counts
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        hottest
+            .sort_by(|(a_ip, a_count), (b_ip, b_count)| b_count.cmp(a_count).then(a_ip.cmp(b_ip)));
+        hottest.truncate(limit);
+        hottest
+    }
+
+    pub fn format_hottest(&self, limit: usize) -> String {
+        let total = self.total();
+
+        let mut s = format!("{:>12} {:>7} {:>6}  instruction\n", "count", "%", "ip");
+        for (ip, count) in self.hottest(limit) {
+            let instruction = Instruction::decode(&self.program, ip)
+                .map(|instruction| instruction.to_string())
+                .unwrap_or_else(|| String::from("?"));
+            let percent = 100.0 * count as f64 / total.max(1) as f64;
+            s.push_str(&format!(
+                "{count:>12} {percent:>6.1}% {ip:>6}  {instruction}\n"
+            ));
+        }
+        s.push_str(&format!("{total:>12} total instructions\n"));
+        s
+    }
+}
+
+type SharedWriter = Arc<Mutex<dyn Write + Send>>;
+
+#[derive(Clone)]
+enum Sink {
+    // Only the profile is kept
+    Discard,
+    Buffer {
+        entries: VecDeque<TraceEntry>,
+        capacity: usize,
+    },
+    // Shared so that a cloned program keeps writing to the same trace
+    Writer(SharedWriter),
+}
+
+impl Debug for Sink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Discard => write!(f, "Discard"),
+            Self::Buffer { entries, capacity } => f
+                .debug_struct("Buffer")
+                .field("entries", &entries.len())
+                .field("capacity", capacity)
+                .finish(),
+            Self::Writer(_) => write!(f, "Writer"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Tracer {
+    sink: Sink,
+    profile: Profile,
+    steps: u64,
+    // The first write failure. Tracing stops after it, and finish() reports it
+    error: Option<String>,
+    // Whether this tracer was attached by record() and should be merged into its profile
+    scoped: bool,
+}
+
+impl Tracer {
+    fn with_sink(sink: Sink) -> Self {
+        Self {
+            sink,
+            profile: Profile::default(),
+            steps: 0,
+            error: None,
+            scoped: false,
+        }
+    }
+
+    // Counts executed addresses without recording individual instructions
+    pub fn profile_only() -> Self {
+        Self::with_sink(Sink::Discard)
+    }
+
+    // Keeps the last capacity instructions in memory
+    pub fn ring_buffer(capacity: usize) -> Self {
+        Self::with_sink(Sink::Buffer {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        })
+    }
+
+    // Writes one line per instruction
+    pub fn to_writer(writer: impl Write + Send + 'static) -> Self {
+        Self::with_sink(Sink::Writer(Arc::new(Mutex::new(writer))))
+    }
+
+    pub fn to_file(path: impl AsRef<Path>) -> Result<Self, SimpleError> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|err| {
+            SimpleError::with_source(
+                ErrorKind::Runtime,
+                format!("could not create trace file {}", path.display()),
+                err,
+            )
+        })?;
+        Ok(Self::to_writer(BufWriter::new(file)))
+    }
+
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    // The buffered instructions, oldest first. Empty unless this is a ring buffer tracer
+    pub fn entries(&self) -> impl Iterator<Item = &TraceEntry> {
+        let entries = match &self.sink {
+            Sink::Buffer { entries, .. } => Some(entries.iter()),
+            _ => None,
+        };
+        entries.into_iter().flatten()
+    }
+
+    // Flushes the trace, reporting the first write error if there was one
+    pub fn finish(&mut self) -> Result<(), SimpleError> {
+        if let Sink::Writer(writer) = &self.sink {
+            if let Err(err) = lock(writer).flush() {
+                self.error.get_or_insert(err.to_string());
+            }
+        }
+
+        match &self.error {
+            Some(err) => Err(SimpleError::runtime(format!(
+                "could not write trace: {err}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    // Called when the tracer is attached to a program
+    pub(super) fn attach(&mut self, program: &Memory) {
+        if self.profile.program.is_empty() {
+            // Memory is sparse, so only copy as much of it as could have been allocated
+            let len = program.len().min(program.allocated_words());
+            self.profile.program = (0..len).map(|address| program.get(address)).collect();
+        }
+    }
+
+    // Whether record() needs a full entry, or only the ip
+    pub(super) fn wants_entries(&self) -> bool {
+        !matches!(self.sink, Sink::Discard)
+    }
+
+    pub(super) fn record_ip(&mut self, ip: usize) {
+        self.profile.record(ip);
+        self.steps += 1;
+    }
+
+    pub(super) fn record(&mut self, entry: TraceEntry) {
+        self.record_ip(entry.ip);
+
+        match &mut self.sink {
+            Sink::Discard => {}
+            Sink::Buffer { entries, capacity } => {
+                if entries.len() == *capacity {
+                    entries.pop_front();
+                }
+                if *capacity > 0 {
+                    entries.push_back(entry);
+                }
+            }
+            Sink::Writer(writer) => {
+                let result = writeln!(lock(writer), "{entry}");
+                if let Err(err) = result {
+                    self.error = Some(err.to_string());
+                    self.sink = Sink::Discard;
+                }
+            }
+        }
+    }
+}
+
+impl Clone for Tracer {
+    // A clone of a tracer attached by record() starts from an empty profile, so that its counts
+    // aren't merged twice
+    fn clone(&self) -> Self {
+        let profile = if self.scoped {
+            Profile::new(self.profile.program.clone())
+        } else {
+            self.profile.clone()
+        };
+
+        Self {
+            sink: self.sink.clone(),
+            profile,
+            steps: self.steps,
+            error: self.error.clone(),
+            scoped: self.scoped,
+        }
+    }
+}
+
+fn lock(writer: &SharedWriter) -> std::sync::MutexGuard<'_, dyn Write + Send + 'static> {
+    writer.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+thread_local! {
+    static PROFILE: RefCell<Option<Profile>> = const { RefCell::new(None) };
+}
+
+// A profile-only tracer if the current thread is inside of record()
+pub(super) fn scoped_tracer() -> Option<Tracer> {
+    let recording = PROFILE.with(|profile| profile.borrow().is_some());
+    recording.then(|| Tracer {
+        scoped: true,
+        ..Tracer::profile_only()
+    })
+}
+
+// Merges a tracer attached by scoped_tracer() back into the recorded profile. It's no longer scoped
+// afterwards, so taking it off a program and then dropping it doesn't merge its counts twice
+pub(super) fn detach(tracer: &mut Tracer) {
+    if mem::take(&mut tracer.scoped) {
+        PROFILE.with(|profile| {
+            if let Some(profile) = profile.borrow_mut().as_mut() {
+                profile.merge(&tracer.profile);
+            }
+        });
+    }
+}
+
+// Runs f with every Intcode program created on the current thread profiled. The profiles are
+// merged by address, so this is most useful for days that run a single program, possibly many
+// times
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Profile) {
+    let previous = PROFILE.with(|profile| profile.replace(Some(Profile::default())));
+
+    let result = f();
+
+    let profile = PROFILE.with(|profile| profile.replace(previous));
+    (result, profile.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2019::intcode::{self, assembler, InteractiveIntcodeProgram, RunState};
+
+    const COUNTDOWN: &str = "\
+        in [n]
+loop:   out [n]
+        add [n], #-1, [n]
+        jt [n], #loop
+        hlt
+n:      data 0
+";
+
+    fn countdown_program() -> InteractiveIntcodeProgram {
+        let mut program = InteractiveIntcodeProgram::new(assembler::assemble(COUNTDOWN).unwrap());
+        program.push_input(3);
+        program
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut program = countdown_program();
+        program.set_tracer(Tracer::ring_buffer(2));
+        assert_eq!(Ok(RunState::Halted), program.execute());
+
+        let tracer = program.take_tracer().unwrap();
+        assert_eq!(10, tracer.steps());
+
+        let entries: Vec<_> = tracer.entries().map(TraceEntry::to_string).collect();
+        assert_eq!(
+            vec![
+                format!(
+                    "{:>10} {:>6}: {:<30} ; 1, -1 -> [12] = 0",
+                    8, 4, "add [12], #-1, [12]"
+                ),
+                format!("{:>10} {:>6}: {:<30} ; 0, 2", 9, 8, "jt [12], #2"),
+            ],
+            entries
+        );
+    }
+
+    #[test]
+    fn test_writer() {
+        #[derive(Clone, Default)]
+        struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for SharedBuffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = SharedBuffer::default();
+        let mut program = countdown_program();
+        program.set_tracer(Tracer::to_writer(buffer.clone()));
+        assert_eq!(Ok(RunState::Halted), program.execute());
+        program.take_tracer().unwrap().finish().unwrap();
+
+        let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = trace.lines().collect();
+        assert_eq!(10, lines.len());
+        assert!(lines[0].ends_with("in [12]                        ; -> [12] = 3"));
+        assert!(lines[1].ends_with("out [12]                       ; 3"));
+    }
+
+    #[test]
+    fn test_profile() {
+        let mut program = countdown_program();
+        program.set_tracer(Tracer::profile_only());
+        assert_eq!(Ok(RunState::Halted), program.execute());
+
+        let profile = program.take_tracer().unwrap().profile().clone();
+        assert_eq!(1, profile.count(0));
+        assert_eq!(3, profile.count(2));
+        assert_eq!(0, profile.count(3));
+        assert_eq!(10, profile.total());
+        assert_eq!(vec![(2, 3), (4, 3), (8, 3)], profile.hottest(3));
+        assert_eq!(
+            [
+                "       count       %     ip  instruction",
+                "           3   30.0%      2  out [12]",
+                "          10 total instructions",
+                "",
+            ]
+            .join("\n"),
+            profile.format_hottest(1)
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let code = assembler::assemble(COUNTDOWN).unwrap();
+
+        let (outputs, profile) = record(|| {
+            let mut outputs = Vec::new();
+            for n in [2, 3] {
+                let mut program = code.clone();
+                intcode::execute(
+                    &mut program,
+                    intcode::iterator_input_fn([n].into_iter()),
+                    |output| outputs.push(output),
+                )
+                .unwrap();
+            }
+            outputs
+        });
+
+        assert_eq!(vec![2, 1, 3, 2, 1], outputs);
+        assert_eq!(2, profile.count(0));
+        assert_eq!(5, profile.count(2));
+
+        // Tracers taken off a program are merged when they're taken, including from forks
+        let ((), profile) = record(|| {
+            let mut program = countdown_program();
+            let mut fork = program.fork();
+            assert_eq!(Ok(RunState::Halted), program.execute());
+            assert_eq!(Ok(RunState::Halted), fork.execute());
+            assert_eq!(10, program.take_tracer().unwrap().profile().total());
+            assert_eq!(10, fork.take_tracer().unwrap().profile().total());
+        });
+        assert_eq!(20, profile.total());
+
+        // Nothing is recorded outside of record()
+        let mut program = countdown_program();
+        assert_eq!(Ok(RunState::Halted), program.execute());
+        assert!(program.take_tracer().is_none());
+    }
+}
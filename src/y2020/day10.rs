@@ -3,11 +3,10 @@
 //! <https://adventofcode.com/2020/day/10>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::num::ParseIntError;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let mut numbers = parse_input(input)?;
+    let mut numbers = crate::timing::parse(|| parse_input(input))?;
 
     numbers.push(0);
     numbers.sort();
@@ -27,7 +26,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let mut numbers = parse_input(input)?;
+    let mut numbers = crate::timing::parse(|| parse_input(input))?;
 
     numbers.push(0);
     numbers.push(*numbers.iter().max().unwrap() + 3);
@@ -58,9 +57,9 @@ fn parse_input(input: &str) -> Result<Vec<u32>, ParseIntError> {
     input.lines().map(|line| line.parse::<u32>()).collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2020/day/11>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Space {
@@ -13,7 +12,7 @@ enum Space {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let mut map = parse_input(input)?;
+    let mut map = crate::timing::parse(|| parse_input(input))?;
 
     loop {
         let next_map = simulate_iteration(&map, count_neighbors_adjacent, 4);
@@ -27,7 +26,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let mut map = parse_input(input)?;
+    let mut map = crate::timing::parse(|| parse_input(input))?;
 
     loop {
         let next_map = simulate_iteration(&map, count_neighbors_line_of_sight, 5);
@@ -167,9 +166,9 @@ fn parse_input(input: &str) -> Result<Vec<Vec<Space>>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -2,8 +2,7 @@
 //!
 //! <https://adventofcode.com/2020/day/12>
 
-use crate::SimpleError;
-use std::error::Error;
+use crate::simpleerror::{self, SimpleError};
 use std::mem;
 use std::str::FromStr;
 
@@ -22,8 +21,10 @@ impl FromStr for Instruction {
     type Err = SimpleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let n: i32 = s[1..].parse()?;
-        match s.chars().next() {
+        let mut chars = s.chars();
+        let action = chars.next();
+        let n: i32 = simpleerror::parse_token(s, chars.as_str())?;
+        match action {
             Some('N') => Ok(Self::North(n)),
             Some('S') => Ok(Self::South(n)),
             Some('E') => Ok(Self::East(n)),
@@ -31,13 +32,13 @@ impl FromStr for Instruction {
             Some('L') => Ok(Self::Left(n)),
             Some('R') => Ok(Self::Right(n)),
             Some('F') => Ok(Self::Forward(n)),
-            _ => Err(SimpleError::new(format!("invalid instruction string: {s}"))),
+            _ => Err(SimpleError::new(format!("invalid instruction string: {s}")).at_token(s, s)),
         }
     }
 }
 
 fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut x = 0;
     let mut y = 0;
@@ -96,7 +97,7 @@ fn solve_part_1(input: &str) -> Result<i32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut x = 0;
     let mut y = 0;
@@ -155,12 +156,12 @@ fn solve_part_2(input: &str) -> Result<i32, SimpleError> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
-    input.lines().map(Instruction::from_str).collect()
+    crate::parse_lines(input, Instruction::from_str)
 }
 
-pub fn solve(input: &str) -> Result<(i32, i32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i32, i32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,45 +3,27 @@
 //! <https://adventofcode.com/2020/day/13>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
     let earliest_departure: u64 = crate::read_single_line(input)?.parse()?;
 
-    let second_line = input
-        .lines()
-        .nth(1)
-        .ok_or_else(|| SimpleError::new(String::from("input should have 2 lines")))?;
-
-    let buses: Vec<_> = second_line
-        .split(',')
-        .filter(|&s| s != "x")
-        .map(|s| s.parse::<u64>())
-        .collect::<Result<_, _>>()?;
+    let buses = parse_buses(input)?;
 
     for t in earliest_departure.. {
-        for &bus in &buses {
+        for &(_, bus) in &buses {
             if t % bus == 0 {
                 return Ok(bus * (t - earliest_departure));
             }
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn solve_part_2(input: &str) -> Result<i128, SimpleError> {
-    let second_line = input
-        .lines()
-        .nth(1)
-        .ok_or_else(|| SimpleError::new(String::from("input should have 2 lines")))?;
-
-    let buses_with_indices: Vec<_> = second_line
-        .split(',')
-        .enumerate()
-        .filter(|(_, s)| *s != "x")
-        .map(|(i, s)| s.parse::<i64>().map(|n| (i as i64, n)))
-        .collect::<Result<_, _>>()?;
+    let buses_with_indices = parse_buses(input)?;
 
     // To find the solution, we treat the list of buses as a system of linear congruences of the
     // form:
@@ -51,7 +33,7 @@ fn solve_part_2(input: &str) -> Result<i128, SimpleError> {
     // Rewrite the list as (a, N) pairs such that x ≡ a (mod N) and 0 <= a < N
     let linear_congruences = buses_with_indices
         .into_iter()
-        .map(|(index, bus)| ((bus - index) as i128, bus as i128));
+        .map(|(index, bus)| (bus as i128 - index as i128, bus as i128));
 
     // https://en.wikipedia.org/wiki/Chinese_remainder_theorem
     //
@@ -106,9 +88,33 @@ fn extended_euclidean_algorithm(a: i128, b: i128) -> (i128, i128) {
     (s, t)
 }
 
-pub fn solve(input: &str) -> Result<(u64, i128), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+fn parse_buses(input: &str) -> Result<Vec<(usize, u64)>, SimpleError> {
+    let second_line = input
+        .lines()
+        .nth(1)
+        .ok_or_else(|| SimpleError::new(String::from("input should have 2 lines")))?;
+
+    let buses_with_indices: Vec<_> = second_line
+        .split(',')
+        .enumerate()
+        .filter(|(_, s)| *s != "x")
+        .map(|(i, s)| s.parse::<u64>().map(|n| (i, n)))
+        .collect::<Result<_, _>>()?;
+
+    if buses_with_indices.is_empty() {
+        return Err(SimpleError::new(String::from("input has no bus IDs")));
+    }
+
+    if buses_with_indices.iter().any(|&(_, bus)| bus == 0) {
+        return Err(SimpleError::new(String::from("bus IDs should be positive")));
+    }
+
+    Ok(buses_with_indices)
+}
+
+pub fn solve(input: &str) -> Result<(u64, i128), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -133,4 +139,10 @@ mod tests {
         assert_eq!(Ok(1261476), solve_part_2("\n67,7,x,59,61"));
         assert_eq!(Ok(1202161486), solve_part_2("\n1789,37,47,1889"));
     }
+
+    #[test]
+    fn test_invalid_buses() {
+        assert!(solve_part_1("939\nx,x").is_err());
+        assert!(solve_part_2("\n7,0,x").is_err());
+    }
 }
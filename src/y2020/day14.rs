@@ -2,9 +2,8 @@
 //!
 //! <https://adventofcode.com/2020/day/14>
 
-use crate::SimpleError;
+use crate::simpleerror::{self, SimpleError};
 use std::collections::HashMap;
-use std::error::Error;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -19,20 +18,22 @@ impl FromStr for Instruction {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let split: Vec<_> = s.split(' ').collect();
         if split.len() != 3 || split[1] != "=" {
-            return Err(SimpleError::new(format!("invalid instruction format: {s}")));
+            return Err(SimpleError::new(format!("invalid instruction format: {s}")).at_token(s, s));
         }
 
         if split[0] == "mask" {
             Ok(Self::MaskSet(String::from(split[2])))
-        } else if split[0].starts_with("mem[") {
-            let address = split[0];
-            let address = address[4..address.len() - 1].parse()?;
+        } else if let Some(address) = split[0]
+            .strip_prefix("mem[")
+            .and_then(|address| address.strip_suffix(']'))
+        {
+            let address = simpleerror::parse_token(s, address)?;
 
-            let value = split[2].parse()?;
+            let value = simpleerror::parse_token(s, split[2])?;
 
             Ok(Self::MemSet { address, value })
         } else {
-            Err(SimpleError::new(format!("invalid instruction format: {s}")))
+            Err(SimpleError::new(format!("invalid instruction format: {s}")).at_token(s, s))
         }
     }
 }
@@ -104,7 +105,7 @@ impl FromStr for BitMask {
 }
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut bit_mask = BitMask::new();
     let mut memory = HashMap::new();
@@ -124,7 +125,7 @@ fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     let mut bit_mask = BitMask::new();
     let mut memory = HashMap::new();
@@ -146,12 +147,12 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
-    input.lines().map(Instruction::from_str).collect()
+    crate::parse_lines(input, Instruction::from_str)
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
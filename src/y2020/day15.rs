@@ -3,12 +3,12 @@
 //! <https://adventofcode.com/2020/day/15>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str, nth_number: usize) -> Result<usize, SimpleError> {
-    let starting_numbers = parse_input(input)?;
+    let starting_numbers = crate::timing::parse(|| parse_input(input))?;
 
-    let mut last_spoken = vec![0; nth_number];
+    let max_starting_number = starting_numbers.iter().copied().max().unwrap_or_default();
+    let mut last_spoken = vec![0; nth_number.max(max_starting_number + 1)];
     for (i, &n) in starting_numbers.iter().enumerate() {
         last_spoken[n] = i + 1;
     }
@@ -34,9 +34,9 @@ fn parse_input(input: &str) -> Result<Vec<usize>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 2020)?;
-    let solution2 = solve_part_1(input, 30000000)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 2020))?;
+    let solution2 = crate::timing::part_2(|| solve_part_1(input, 30000000))?;
 
     Ok((solution1, solution2))
 }
@@ -49,4 +49,9 @@ mod tests {
     fn test_sample_input_part_1() {
         assert_eq!(Ok(436), solve_part_1("0,3,6", 2020));
     }
+
+    #[test]
+    fn test_large_starting_number() {
+        assert_eq!(Ok(2), solve_part_1("0,3,6,5000", 10));
+    }
 }
@@ -2,10 +2,8 @@
 //!
 //! <https://adventofcode.com/2020/day/16>
 
-use crate::SimpleError;
+use crate::simpleerror::{self, SimpleError};
 use std::collections::HashMap;
-use std::error::Error;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -52,18 +50,18 @@ impl FromStr for TicketField {
     type Err = SimpleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (name, ranges) = s
-            .split_once(": ")
-            .ok_or_else(|| SimpleError::new(format!("no ': ' in ticket field string: {s}")))?;
+        let (name, ranges) = s.split_once(": ").ok_or_else(|| {
+            SimpleError::new(format!("no ': ' in ticket field string: {s}")).at_token(s, s)
+        })?;
 
         let name = String::from(name);
 
-        let (range_a, range_b) = ranges
-            .split_once(" or ")
-            .ok_or_else(|| SimpleError::new(format!("no ' or ' in ticket field string: {s}")))?;
+        let (range_a, range_b) = ranges.split_once(" or ").ok_or_else(|| {
+            SimpleError::new(format!("no ' or ' in ticket field string: {s}")).at_token(s, ranges)
+        })?;
 
-        let range_a = range_a.parse()?;
-        let range_b = range_b.parse()?;
+        let range_a = simpleerror::parse_token(s, range_a)?;
+        let range_b = simpleerror::parse_token(s, range_b)?;
 
         Ok(Self {
             name,
@@ -85,7 +83,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
         ticket_fields,
         nearby_tickets,
         ..
-    } = parse_input(input)?;
+    } = crate::timing::parse(|| parse_input(input))?;
 
     let invalid_value_sum = nearby_tickets
         .iter()
@@ -106,7 +104,7 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
         ticket_fields,
         your_ticket,
         nearby_tickets,
-    } = parse_input(input)?;
+    } = crate::timing::parse(|| parse_input(input))?;
 
     let valid_nearby_tickets: Vec<_> = nearby_tickets
         .into_iter()
@@ -154,9 +152,9 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
 }
 
 fn parse_input(input: &str) -> Result<Input, SimpleError> {
-    let lines: Vec<_> = input.lines().collect();
+    let lines: Vec<_> = input.lines().enumerate().collect();
 
-    let split: Vec<_> = lines.split(|s| s.is_empty()).collect();
+    let split: Vec<_> = lines.split(|(_, s)| s.is_empty()).collect();
     if split.len() != 3 {
         return Err(SimpleError::new(String::from(
             "there should be 2 blank lines in input",
@@ -165,7 +163,7 @@ fn parse_input(input: &str) -> Result<Input, SimpleError> {
 
     let ticket_fields: Vec<_> = split[0]
         .iter()
-        .map(|&line| TicketField::from_str(line))
+        .map(|&(i, line)| TicketField::from_str(line).map_err(|err| err.at_line(i + 1, line)))
         .collect::<Result<_, _>>()?;
 
     if split[1].len() != 2 {
@@ -174,11 +172,11 @@ fn parse_input(input: &str) -> Result<Input, SimpleError> {
         )));
     }
 
-    let your_ticket = parse_ticket(split[1][1])?;
+    let your_ticket = parse_ticket(&split[1][1])?;
 
     let nearby_tickets: Vec<_> = split[2][1..]
         .iter()
-        .map(|&line| parse_ticket(line))
+        .map(parse_ticket)
         .collect::<Result<_, _>>()?;
 
     Ok(Input {
@@ -188,13 +186,15 @@ fn parse_input(input: &str) -> Result<Input, SimpleError> {
     })
 }
 
-fn parse_ticket(line: &str) -> Result<Vec<u32>, ParseIntError> {
-    line.split(',').map(|s| s.parse::<u32>()).collect()
+fn parse_ticket(&(i, line): &(usize, &str)) -> Result<Vec<u32>, SimpleError> {
+    line.split(',')
+        .map(|s| simpleerror::parse_token(line, s).map_err(|err| err.at_line(i + 1, line)))
+        .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp;
 use std::collections::HashSet;
-use std::error::Error;
 use std::hash::Hash;
 
 trait Point: Eq + Hash + Copy {
@@ -167,7 +166,7 @@ impl Point for Point4D {
 }
 
 fn solve_part<P: Point>(input: &str) -> Result<usize, SimpleError> {
-    let mut active_points = parse_input(input)?;
+    let mut active_points = crate::timing::parse(|| parse_input(input))?;
 
     for _ in 0..6 {
         let minimums = P::minimum_coordinates(active_points.iter().copied());
@@ -209,9 +208,9 @@ fn parse_input<P: Point>(input: &str) -> Result<HashSet<P>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part::<Point3D>(input)?;
-    let solution2 = solve_part::<Point4D>(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part::<Point3D>(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part::<Point4D>(input))?;
 
     Ok((solution1, solution2))
 }
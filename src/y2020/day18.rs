@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2020/day/18>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Operator {
@@ -128,14 +127,13 @@ fn evaluate_add_first(expression: &str) -> Result<u64, SimpleError> {
 
                 match prev_level_operator {
                     Operator::Add => {
-                        if operands.is_empty() {
+                        let Some(operand) = operands.pop() else {
                             return Err(SimpleError::new(format!(
                                 "no value/expression before '+': {expression}"
                             )));
-                        }
+                        };
 
-                        let value = operands.pop().unwrap() + level_product;
-                        operands.push(value);
+                        operands.push(operand + level_product);
                     }
                     Operator::Multiply => {
                         operands.push(level_product);
@@ -147,14 +145,13 @@ fn evaluate_add_first(expression: &str) -> Result<u64, SimpleError> {
 
                 match last_operator {
                     Operator::Add => {
-                        if operands.is_empty() {
+                        let Some(operand) = operands.pop() else {
                             return Err(SimpleError::new(format!(
                                 "'+' preceded by no value: {expression}"
                             )));
-                        }
+                        };
 
-                        let value = operands.pop().unwrap() + n;
-                        operands.push(value);
+                        operands.push(operand + n);
                     }
                     Operator::Multiply => {
                         operands.push(n);
@@ -179,9 +176,9 @@ fn evaluate_add_first(expression: &str) -> Result<u64, SimpleError> {
     Ok(operands.into_iter().product())
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2020/day/19>
 
 use crate::SimpleError;
-use std::error::Error;
 use std::iter;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -48,18 +47,20 @@ fn solve_part(input: &str, replace_8_11_rules: bool) -> Result<usize, SimpleErro
         a_rule_index,
         b_rule_index,
         messages,
-    } = parse_input(input)?;
+    } = crate::timing::parse(|| parse_input(input))?;
 
     let rules = sort_rules(&rules);
 
     let rules = if replace_8_11_rules {
         let mut rules = rules;
-        replace_rules_for_part_two(&mut rules);
+        replace_rules_for_part_two(&mut rules, a_rule_index, b_rule_index)?;
         rules
     } else {
         rules
     };
 
+    check_left_recursion(&rules, a_rule_index, b_rule_index)?;
+
     let initial_states = generate_states_for_step(
         StateStep {
             rule_index: 0,
@@ -101,7 +102,26 @@ fn solve_part(input: &str, replace_8_11_rules: bool) -> Result<usize, SimpleErro
     Ok(valid_count)
 }
 
-fn replace_rules_for_part_two(rules: &mut [Rule]) {
+fn replace_rules_for_part_two(
+    rules: &mut Vec<Rule>,
+    a_rule_index: usize,
+    b_rule_index: usize,
+) -> Result<(), SimpleError> {
+    for index in [42, 31] {
+        let is_defined = index == a_rule_index
+            || index == b_rule_index
+            || rules.get(index).is_some_and(|rule| rule.index == index);
+        if !is_defined {
+            return Err(SimpleError::new(format!(
+                "rule {index} is needed for part 2 but is missing"
+            )));
+        }
+    }
+
+    if rules.len() <= 11 {
+        rules.resize(12, Rule::empty());
+    }
+
     rules[8] = Rule {
         index: 8,
         branches: vec![vec![42], vec![42, 8]],
@@ -110,6 +130,50 @@ fn replace_rules_for_part_two(rules: &mut [Rule]) {
         index: 11,
         branches: vec![vec![42, 31], vec![42, 11, 31]],
     };
+
+    Ok(())
+}
+
+// Matching a rule whose first sub-rule leads back to itself would recurse forever
+fn check_left_recursion(
+    rules: &[Rule],
+    a_rule_index: usize,
+    b_rule_index: usize,
+) -> Result<(), SimpleError> {
+    let defined_rules: Vec<_> = rules
+        .iter()
+        .filter(|rule| !rule.branches.is_empty())
+        .collect();
+
+    let mut resolved = vec![false; rules.len()];
+    loop {
+        let newly_resolved: Vec<_> = defined_rules
+            .iter()
+            .filter(|rule| {
+                !resolved[rule.index]
+                    && rule.branches.iter().all(|branch| {
+                        branch[0] == a_rule_index
+                            || branch[0] == b_rule_index
+                            || resolved[branch[0]]
+                    })
+            })
+            .map(|rule| rule.index)
+            .collect();
+        if newly_resolved.is_empty() {
+            break;
+        }
+        for index in newly_resolved {
+            resolved[index] = true;
+        }
+    }
+
+    match defined_rules.iter().find(|rule| !resolved[rule.index]) {
+        Some(rule) => Err(SimpleError::unsupported_input(format!(
+            "rule {} is left-recursive",
+            rule.index
+        ))),
+        None => Ok(()),
+    }
 }
 
 fn advance_state(
@@ -238,23 +302,41 @@ fn parse_input(input: &str) -> Result<Input, SimpleError> {
         }
     }
 
-    if a_rule_index.is_none() || b_rule_index.is_none() {
+    let (Some(a_rule_index), Some(b_rule_index)) = (a_rule_index, b_rule_index) else {
         return Err(SimpleError::new(String::from(
             "input did not contain both \"a\" and \"b\" rules",
         )));
+    };
+
+    if !rules.iter().any(|rule| rule.index == 0) {
+        return Err(SimpleError::new(String::from("input has no rule 0")));
+    }
+
+    for rule in &rules {
+        for &sub_rule_index in rule.branches.iter().flatten() {
+            let is_defined = sub_rule_index == a_rule_index
+                || sub_rule_index == b_rule_index
+                || rules.iter().any(|rule| rule.index == sub_rule_index);
+            if !is_defined {
+                return Err(SimpleError::new(format!(
+                    "rule {} refers to missing rule {sub_rule_index}",
+                    rule.index
+                )));
+            }
+        }
     }
 
     Ok(Input {
         rules,
-        a_rule_index: a_rule_index.unwrap(),
-        b_rule_index: b_rule_index.unwrap(),
+        a_rule_index,
+        b_rule_index,
         messages,
     })
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part(input, false)?;
-    let solution2 = solve_part(input, true)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part(input, false))?;
+    let solution2 = crate::timing::part_2(|| solve_part(input, true))?;
 
     Ok((solution1, solution2))
 }
@@ -275,4 +357,11 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(12), solve_part(SAMPLE_INPUT_2, true));
     }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(solve_part("0: 1 2\n1: \"a\"\n2: \"b\"\n\nab", true).is_err());
+        assert!(solve_part("0: 1 3\n1: \"a\"\n2: \"b\"\n\nab", false).is_err());
+        assert!(solve_part("0: 0 1\n1: \"a\"\n2: \"b\"\n\nab", false).is_err());
+    }
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2020/day/2>
 
 use crate::SimpleError;
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct Password {
@@ -14,7 +13,7 @@ struct Password {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let passwords = parse_input(input)?;
+    let passwords = crate::timing::parse(|| parse_input(input))?;
 
     let valid_count = passwords
         .iter()
@@ -33,7 +32,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let passwords = parse_input(input)?;
+    let passwords = crate::timing::parse(|| parse_input(input))?;
 
     let valid_count = passwords
         .iter()
@@ -83,9 +82,9 @@ fn parse_input(input: &str) -> Result<Vec<Password>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,8 +3,7 @@
 //! <https://adventofcode.com/2020/day/20>
 
 use crate::SimpleError;
-use std::collections::HashMap;
-use std::error::Error;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct Edge(Vec<bool>);
@@ -173,8 +172,8 @@ impl Image {
     fn count_occurrences(&self, other: &Self) -> usize {
         let mut count = 0;
 
-        for corner_i in 0..(self.0.len() - other.0.len()) {
-            for corner_j in 0..(self.0[0].len() - other.0[0].len()) {
+        for corner_i in 0..self.0.len().saturating_sub(other.0.len()) {
+            for corner_j in 0..self.0[0].len().saturating_sub(other.0[0].len()) {
                 let mut matches = true;
 
                 'outer: for i in 0..other.0.len() {
@@ -219,7 +218,7 @@ struct Tile {
 }
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
-    let tiles = parse_input(input)?;
+    let tiles = crate::timing::parse(|| parse_input(input))?;
 
     let mut edge_to_count = HashMap::new();
     for (_, edge) in tiles
@@ -253,9 +252,9 @@ fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let tiles = parse_input(input)?;
+    let tiles = crate::timing::parse(|| parse_input(input))?;
 
-    let connected_tiles = connect_tiles(&tiles);
+    let connected_tiles = connect_tiles(&tiles)?;
     let connected_images_without_border: Vec<Vec<_>> = connected_tiles
         .into_iter()
         .map(|row| {
@@ -305,7 +304,7 @@ fn count_sea_monsters(image: &Image) -> usize {
     0
 }
 
-fn connect_tiles(tiles: &[Tile]) -> Vec<Vec<Tile>> {
+fn connect_tiles(tiles: &[Tile]) -> Result<Vec<Vec<Tile>>, SimpleError> {
     let mut edge_to_ids: HashMap<CanonicalizedEdge, Vec<u64>> = HashMap::new();
     for tile in tiles {
         for (_, edge) in tile.image.get_edges_with_direction() {
@@ -330,9 +329,9 @@ fn connect_tiles(tiles: &[Tile]) -> Vec<Vec<Tile>> {
             unmatched_edge_count == 2
         })
         .cloned()
-        .unwrap();
+        .ok_or_else(|| SimpleError::unsupported_input(String::from("no corner tile found")))?;
 
-    loop {
+    for rotations in 0.. {
         let top_edge = top_left_tile.image.get_top_edge().canonicalize();
         let left_edge = top_left_tile.image.get_left_edge().canonicalize();
 
@@ -342,6 +341,13 @@ fn connect_tiles(tiles: &[Tile]) -> Vec<Vec<Tile>> {
             break;
         }
 
+        if rotations == 3 {
+            return Err(SimpleError::unsupported_input(format!(
+                "unmatched edges of corner tile {} are on opposite sides",
+                top_left_tile.id
+            )));
+        }
+
         top_left_tile = Tile {
             id: top_left_tile.id,
             image: top_left_tile.image.rotated_left(),
@@ -363,13 +369,7 @@ fn connect_tiles(tiles: &[Tile]) -> Vec<Vec<Tile>> {
                 let target_tile = &connected_tiles[connected_tiles.len() - 1][0];
                 let target_edge = target_tile.image.get_bottom_edge();
 
-                let matching_tile_id = edge_to_ids
-                    .get(&target_edge.canonicalize())
-                    .unwrap()
-                    .iter()
-                    .copied()
-                    .find(|&id| id != target_tile.id)
-                    .unwrap();
+                let matching_tile_id = find_matching_tile(&edge_to_ids, target_tile, &target_edge)?;
                 let matching_tile = *id_to_tile.get(&matching_tile_id).unwrap();
 
                 let reoriented_image = matching_tile
@@ -380,16 +380,10 @@ fn connect_tiles(tiles: &[Tile]) -> Vec<Vec<Tile>> {
                     image: reoriented_image,
                 });
             } else {
-                let target_tile = current_row.last().unwrap();
+                let target_tile = &current_row[current_row.len() - 1];
                 let target_edge = target_tile.image.get_right_edge();
 
-                let matching_tile_id = edge_to_ids
-                    .get(&target_edge.canonicalize())
-                    .unwrap()
-                    .iter()
-                    .copied()
-                    .find(|&id| id != target_tile.id)
-                    .unwrap();
+                let matching_tile_id = find_matching_tile(&edge_to_ids, target_tile, &target_edge)?;
                 let matching_tile = *id_to_tile.get(&matching_tile_id).unwrap();
 
                 let reoriented_image = matching_tile
@@ -405,13 +399,29 @@ fn connect_tiles(tiles: &[Tile]) -> Vec<Vec<Tile>> {
         current_row = Vec::new();
     }
 
-    connected_tiles
+    Ok(connected_tiles)
+}
+
+fn find_matching_tile(
+    edge_to_ids: &HashMap<CanonicalizedEdge, Vec<u64>>,
+    target_tile: &Tile,
+    target_edge: &Edge,
+) -> Result<u64, SimpleError> {
+    edge_to_ids
+        .get(&target_edge.canonicalize())
+        .and_then(|ids| ids.iter().copied().find(|&id| id != target_tile.id))
+        .ok_or_else(|| {
+            SimpleError::unsupported_input(format!(
+                "no tile connects to an edge of tile {}",
+                target_tile.id
+            ))
+        })
 }
 
 fn parse_input(input: &str) -> Result<Vec<Tile>, SimpleError> {
     let lines: Vec<_> = input.lines().collect();
 
-    lines
+    let tiles: Vec<Tile> = lines
         .split(|s| s.is_empty())
         .filter(|tile_lines| !tile_lines.is_empty())
         .map(|tile_lines| {
@@ -422,16 +432,18 @@ fn parse_input(input: &str) -> Result<Vec<Tile>, SimpleError> {
                 )));
             }
 
-            let (_, tile_id) = tile_lines[0].split_once(' ').ok_or_else(|| {
-                SimpleError::new(format!(
-                    "tile id line not in expected format: {}",
-                    tile_lines[0]
-                ))
-            })?;
-
-            let tile_id = tile_id[..tile_id.len() - 1].parse()?;
-
-            let image = tile_lines[1..]
+            let tile_id = tile_lines[0]
+                .strip_prefix("Tile ")
+                .and_then(|s| s.strip_suffix(':'))
+                .ok_or_else(|| {
+                    SimpleError::new(format!(
+                        "tile id line not in expected format: {}",
+                        tile_lines[0]
+                    ))
+                })?
+                .parse()?;
+
+            let image: Vec<Vec<_>> = tile_lines[1..]
                 .iter()
                 .map(|line| {
                     line.chars()
@@ -444,17 +456,47 @@ fn parse_input(input: &str) -> Result<Vec<Tile>, SimpleError> {
                 })
                 .collect::<Result<_, _>>()?;
 
+            if image.len() < 3 || image.iter().any(|row| row.len() != image.len()) {
+                return Err(SimpleError::new(format!(
+                    "tile {tile_id} should be a square of at least 3x3"
+                )));
+            }
+
             Ok(Tile {
                 id: tile_id,
                 image: Image(image),
             })
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    let side_len = (tiles.len() as f64).sqrt().round() as usize;
+    if tiles.is_empty() || side_len * side_len != tiles.len() {
+        return Err(SimpleError::new(format!(
+            "tiles should form a square, found {} tiles",
+            tiles.len()
+        )));
+    }
+
+    if tiles
+        .iter()
+        .any(|tile| tile.image.0.len() != tiles[0].image.0.len())
+    {
+        return Err(SimpleError::new(String::from(
+            "tiles should all be the same size",
+        )));
+    }
+
+    let tile_ids: HashSet<_> = tiles.iter().map(|tile| tile.id).collect();
+    if tile_ids.len() != tiles.len() {
+        return Err(SimpleError::new(String::from("tile IDs should be unique")));
+    }
+
+    Ok(tiles)
 }
 
-pub fn solve(input: &str) -> Result<(u64, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u64, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -474,4 +516,11 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(273), solve_part_2(SAMPLE_INPUT));
     }
+
+    #[test]
+    fn test_invalid_tiles() {
+        assert!(solve_part_1("Tile 1:\n#..\n...\n..#\n\nTile 2:\n...\n...\n...").is_err());
+        assert!(solve_part_1("Tile 1:\n#..\n...").is_err());
+        assert!(solve_part_2("Tile 1:\n#..\n...\n..#").is_err());
+    }
 }
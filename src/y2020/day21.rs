@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct FoodItem<'a> {
@@ -13,7 +12,7 @@ struct FoodItem<'a> {
 }
 
 fn solve_both_parts(input: &str) -> Result<(usize, String), SimpleError> {
-    let food = parse_input(input)?;
+    let food = crate::timing::parse(|| parse_input(input))?;
 
     let num_allergens = food
         .iter()
@@ -112,7 +111,7 @@ fn parse_input(input: &str) -> Result<Vec<FoodItem>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(usize, String), Box<dyn Error>> {
+pub fn solve(input: &str) -> Result<(usize, String), SimpleError> {
     let (solution1, solution2) = solve_both_parts(input)?;
 
     Ok((solution1, solution2))
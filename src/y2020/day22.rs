@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::{HashSet, VecDeque};
-use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Winner {
@@ -13,7 +12,7 @@ enum Winner {
 }
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let (p1_deck, p2_deck) = parse_input(input)?;
+    let (p1_deck, p2_deck) = crate::timing::parse(|| parse_input(input))?;
 
     let mut p1_deck = VecDeque::from(p1_deck);
     let mut p2_deck = VecDeque::from(p2_deck);
@@ -43,7 +42,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let (p1_deck, p2_deck) = parse_input(input)?;
+    let (p1_deck, p2_deck) = crate::timing::parse(|| parse_input(input))?;
 
     let (winner, p1_deck, p2_deck) =
         play_recursive_combat(VecDeque::from(p1_deck), VecDeque::from(p2_deck));
@@ -132,9 +131,9 @@ fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>), SimpleError> {
     Ok((p1_deck, p2_deck))
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::VecDeque;
-use std::error::Error;
 use std::fmt::Debug;
 
 #[derive(Debug, Clone)]
@@ -58,13 +57,7 @@ impl From<Vec<u32>> for CircularLinkedHashMap {
 }
 
 fn solve_part_1(input: &str, moves: usize) -> Result<String, SimpleError> {
-    let mut numbers: VecDeque<_> = crate::read_single_line(input)?
-        .chars()
-        .map(|c| {
-            c.to_digit(10)
-                .ok_or_else(|| SimpleError::new(format!("not a digit: {c}")))
-        })
-        .collect::<Result<_, _>>()?;
+    let mut numbers = VecDeque::from(crate::timing::parse(|| parse_input(input))?);
 
     let min = numbers.iter().copied().min().unwrap();
     let max = numbers.iter().copied().max().unwrap();
@@ -111,13 +104,7 @@ fn solve_part_1(input: &str, moves: usize) -> Result<String, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let mut numbers: Vec<_> = crate::read_single_line(input)?
-        .chars()
-        .map(|c| {
-            c.to_digit(10)
-                .ok_or_else(|| SimpleError::new(format!("not a digit: {c}")))
-        })
-        .collect::<Result<_, _>>()?;
+    let mut numbers = crate::timing::parse(|| parse_input(input))?;
 
     let initial_max = *numbers.iter().max().unwrap();
 
@@ -165,9 +152,30 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
     Ok(a as u64 * b as u64)
 }
 
-pub fn solve(input: &str) -> Result<(String, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 100)?;
-    let solution2 = solve_part_2(input)?;
+fn parse_input(input: &str) -> Result<Vec<u32>, SimpleError> {
+    let numbers: Vec<_> = crate::read_single_line(input)?
+        .chars()
+        .map(|c| {
+            c.to_digit(10)
+                .ok_or_else(|| SimpleError::new(format!("not a digit: {c}")))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut sorted = numbers.clone();
+    sorted.sort();
+    if numbers.len() < 4 || sorted.iter().copied().ne(1..=numbers.len() as u32) {
+        return Err(SimpleError::new(format!(
+            "cup labels should be 1 through N with N >= 4, each used once: {}",
+            input.trim()
+        )));
+    }
+
+    Ok(numbers)
+}
+
+pub fn solve(input: &str) -> Result<(String, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 100))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -186,4 +194,11 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(149245887792), solve_part_2("389125467"));
     }
+
+    #[test]
+    fn test_invalid_labels() {
+        assert!(solve_part_1("123", 10).is_err());
+        assert!(solve_part_1("3891254", 10).is_err());
+        assert!(solve_part_1("3891254677", 10).is_err());
+    }
 }
@@ -5,7 +5,6 @@
 use crate::SimpleError;
 use std::cmp;
 use std::collections::HashSet;
-use std::error::Error;
 use std::ops::{Add, AddAssign};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -127,20 +126,20 @@ fn find_black_points(input: &str) -> Result<HashSet<HexPoint>, SimpleError> {
         let mut position = HexPoint::new(0, 0);
 
         let mut remaining = line;
-        while !remaining.is_empty() {
-            let direction = match remaining.chars().next().unwrap() {
-                'n' => match &remaining[..2] {
-                    "ne" => HexDirection::Northeast,
-                    "nw" => HexDirection::Northwest,
+        while let Some(c) = remaining.chars().next() {
+            let direction = match c {
+                'n' => match remaining.get(..2) {
+                    Some("ne") => HexDirection::Northeast,
+                    Some("nw") => HexDirection::Northwest,
                     _ => {
                         return Err(SimpleError::new(format!(
                             "invalid n* sequence in line: {line}"
                         )))
                     }
                 },
-                's' => match &remaining[..2] {
-                    "se" => HexDirection::Southeast,
-                    "sw" => HexDirection::Southwest,
+                's' => match remaining.get(..2) {
+                    Some("se") => HexDirection::Southeast,
+                    Some("sw") => HexDirection::Southwest,
                     _ => {
                         return Err(SimpleError::new(format!(
                             "invalid s* sequence in line: {line}"
@@ -169,9 +168,9 @@ fn find_black_points(input: &str) -> Result<HashSet<HexPoint>, SimpleError> {
     Ok(black_points)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -191,4 +190,9 @@ mod tests {
     fn test_sample_input_part_2() {
         assert_eq!(Ok(2208), solve_part_2(SAMPLE_INPUT));
     }
+
+    #[test]
+    fn test_truncated_direction() {
+        assert!(solve_part_1("esen").is_err());
+    }
 }
@@ -3,7 +3,6 @@
 //! <https://adventofcode.com/2020/day/25>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u64, SimpleError> {
     let mut lines = input.lines();
@@ -38,13 +37,13 @@ fn find_loop_size(public_key: u64) -> Result<u32, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(format!(
+    Err(SimpleError::unsupported_input(format!(
         "no loop size found for public key {public_key}"
     )))
 }
 
-pub fn solve(input: &str) -> Result<(u64, String), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
+pub fn solve(input: &str) -> Result<(u64, String), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
 
     Ok((solution1, String::new()))
 }
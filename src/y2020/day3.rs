@@ -3,10 +3,9 @@
 //! <https://adventofcode.com/2020/day/3>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
-    let map = parse_input(input)?;
+    let map = crate::timing::parse(|| parse_input(input))?;
 
     let mut tree_count = 0;
     let mut i = 0;
@@ -24,7 +23,7 @@ fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
-    let map = parse_input(input)?;
+    let map = crate::timing::parse(|| parse_input(input))?;
 
     let mut tree_count_product = 1;
     for (di, dj) in [(1, 1), (1, 3), (1, 5), (1, 7), (2, 1)] {
@@ -61,9 +60,9 @@ fn parse_input(input: &str) -> Result<Vec<Vec<bool>>, SimpleError> {
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
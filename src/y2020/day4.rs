@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -117,7 +116,7 @@ impl FromStr for PassportField {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let passports = parse_input(input)?;
+    let passports = crate::timing::parse(|| parse_input(input))?;
 
     let valid_count = passports
         .iter()
@@ -128,7 +127,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<usize, SimpleError> {
-    let passports = parse_input(input)?;
+    let passports = crate::timing::parse(|| parse_input(input))?;
 
     let valid_count = passports
         .iter()
@@ -156,14 +155,15 @@ fn has_all_required_fields(passport: &[PassportField]) -> bool {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Vec<PassportField>>, SimpleError> {
-    let lines: Vec<_> = input.lines().collect();
+    let lines: Vec<_> = input.lines().enumerate().collect();
 
     let mut passports = Vec::new();
-    for line_group in lines.split(|s| s.is_empty()) {
+    for line_group in lines.split(|(_, s)| s.is_empty()) {
         let mut passport_fields = Vec::new();
-        for &line in line_group {
+        for &(i, line) in line_group {
             for passport_field in line.split(' ') {
-                let passport_field = PassportField::from_str(passport_field)?;
+                let passport_field = PassportField::from_str(passport_field)
+                    .map_err(|err| err.at_token(line, passport_field).at_line(i + 1, line))?;
                 passport_fields.push(passport_field);
             }
         }
@@ -173,9 +173,9 @@ fn parse_input(input: &str) -> Result<Vec<Vec<PassportField>>, SimpleError> {
     Ok(passports)
 }
 
-pub fn solve(input: &str) -> Result<(usize, usize), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(usize, usize), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -3,12 +3,14 @@
 //! <https://adventofcode.com/2020/day/5>
 
 use crate::SimpleError;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
     let seat_ids: Vec<_> = input.lines().map(seat_id).collect::<Result<_, _>>()?;
 
-    Ok(seat_ids.into_iter().max().unwrap())
+    seat_ids
+        .into_iter()
+        .max()
+        .ok_or_else(|| SimpleError::new(String::from("input is empty")))
 }
 
 fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
@@ -16,13 +18,15 @@ fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
 
     seat_ids.sort();
 
-    for i in 1..seat_ids.len() - 1 {
+    for i in 1..seat_ids.len().saturating_sub(1) {
         if seat_ids[i + 1] != seat_ids[i] + 1 {
             return Ok(seat_ids[i] + 1);
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn seat_id(seat: &str) -> Result<u32, SimpleError> {
@@ -71,9 +75,9 @@ fn seat_id(seat: &str) -> Result<u32, SimpleError> {
     Ok(8 * row_start + col_start)
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
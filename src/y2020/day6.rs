@@ -4,7 +4,6 @@
 
 use crate::SimpleError;
 use std::collections::HashSet;
-use std::error::Error;
 
 fn solve_part_1(input: &str) -> Result<u32, SimpleError> {
     let lines: Vec<_> = input.lines().collect();
@@ -33,17 +32,16 @@ fn solve_part_2(input: &str) -> Result<u32, SimpleError> {
                 .iter()
                 .map(|line| line.chars().collect::<HashSet<_>>())
                 .reduce(|a, b| a.intersection(&b).copied().collect::<HashSet<_>>())
-                .unwrap()
-                .len() as u32
+                .map_or(0, |answers| answers.len() as u32)
         })
         .sum();
 
     Ok(total_all_answered)
 }
 
-pub fn solve(input: &str) -> Result<(u32, u32), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(u32, u32), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
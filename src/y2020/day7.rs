@@ -3,8 +3,7 @@
 //! <https://adventofcode.com/2020/day/7>
 
 use crate::SimpleError;
-use std::collections::HashMap;
-use std::error::Error;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct InnerBagRule {
@@ -19,7 +18,7 @@ struct Bag {
 }
 
 fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
-    let bag_rules = parse_input(input)?;
+    let bag_rules = crate::timing::parse(|| parse_input(input))?;
 
     let color_to_rule: HashMap<_, _> = bag_rules
         .iter()
@@ -40,7 +39,7 @@ fn solve_part_1(input: &str) -> Result<usize, SimpleError> {
 }
 
 fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
-    let bag_rules = parse_input(input)?;
+    let bag_rules = crate::timing::parse(|| parse_input(input))?;
 
     let color_to_rule: HashMap<_, _> = bag_rules
         .iter()
@@ -49,7 +48,9 @@ fn solve_part_2(input: &str) -> Result<u64, SimpleError> {
 
     let mut cache = HashMap::new();
 
-    let shiny_gold_bag = *color_to_rule.get("shiny gold").unwrap();
+    let shiny_gold_bag = *color_to_rule.get("shiny gold").ok_or_else(|| {
+        SimpleError::unsupported_input(String::from("no rule for shiny gold bags"))
+    })?;
     let shiny_gold_inner_count = count_inner_bags(shiny_gold_bag, &color_to_rule, &mut cache);
 
     Ok(shiny_gold_inner_count)
@@ -100,7 +101,7 @@ fn can_contain_shiny_gold<'a>(
 }
 
 fn parse_input(input: &str) -> Result<Vec<Bag>, SimpleError> {
-    input
+    let bags = input
         .lines()
         .map(|line| {
             let split: Vec<_> = line.splitn(5, ' ').collect();
@@ -136,12 +137,66 @@ fn parse_input(input: &str) -> Result<Vec<Bag>, SimpleError> {
 
             Ok(Bag { color, rules })
         })
-        .collect()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    validate_rules(&bags)?;
+
+    Ok(bags)
 }
 
-pub fn solve(input: &str) -> Result<(usize, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+fn validate_rules(bags: &[Bag]) -> Result<(), SimpleError> {
+    let colors: HashSet<_> = bags.iter().map(|bag| bag.color.as_str()).collect();
+    if colors.len() != bags.len() {
+        return Err(SimpleError::new(String::from(
+            "some bag colors have more than one rule",
+        )));
+    }
+
+    for bag in bags {
+        if let Some(inner_rule) = bag
+            .rules
+            .iter()
+            .find(|inner_rule| !colors.contains(inner_rule.color.as_str()))
+        {
+            return Err(SimpleError::new(format!(
+                "no rule for {} bags inside {} bags",
+                inner_rule.color, bag.color
+            )));
+        }
+    }
+
+    // Repeatedly resolve bags whose inner bags are all resolved; anything left over is in a cycle
+    let mut resolved: HashSet<&str> = HashSet::new();
+    loop {
+        let newly_resolved: Vec<_> = bags
+            .iter()
+            .filter(|bag| {
+                !resolved.contains(bag.color.as_str())
+                    && bag
+                        .rules
+                        .iter()
+                        .all(|inner_rule| resolved.contains(inner_rule.color.as_str()))
+            })
+            .map(|bag| bag.color.as_str())
+            .collect();
+        if newly_resolved.is_empty() {
+            break;
+        }
+        resolved.extend(newly_resolved);
+    }
+
+    if resolved.len() != bags.len() {
+        return Err(SimpleError::new(String::from(
+            "bags can't contain themselves",
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn solve(input: &str) -> Result<(usize, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -163,4 +218,14 @@ mod tests {
         assert_eq!(Ok(32), solve_part_2(SAMPLE_INPUT));
         assert_eq!(Ok(126), solve_part_2(SAMPLE_INPUT_2));
     }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(solve_part_1("shiny gold bags contain 1 dark red bag.").is_err());
+        assert!(solve_part_2(
+            "shiny gold bags contain 1 dark red bag.\n\
+             dark red bags contain 2 shiny gold bags."
+        )
+        .is_err());
+    }
 }
@@ -2,8 +2,7 @@
 //!
 //! <https://adventofcode.com/2020/day/8>
 
-use crate::SimpleError;
-use std::error::Error;
+use crate::simpleerror::{self, SimpleError};
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -20,18 +19,18 @@ impl FromStr for Instruction {
         let split: Vec<_> = s.split(' ').collect();
         match split.as_slice() {
             ["nop", n] => {
-                let n = n.parse()?;
+                let n = simpleerror::parse_token(s, n)?;
                 Ok(Self::NoOp(n))
             }
             ["acc", n] => {
-                let n = n.parse()?;
+                let n = simpleerror::parse_token(s, n)?;
                 Ok(Self::Accumulate(n))
             }
             ["jmp", n] => {
-                let n = n.parse()?;
+                let n = simpleerror::parse_token(s, n)?;
                 Ok(Self::Jump(n))
             }
-            _ => Err(SimpleError::new(format!("invalid instruction: {s}"))),
+            _ => Err(SimpleError::new(format!("invalid instruction: {s}")).at_token(s, s)),
         }
     }
 }
@@ -43,17 +42,19 @@ enum ExecutionResult {
 }
 
 fn solve_part_1(input: &str) -> Result<i64, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     if let ExecutionResult::InfiniteLoop(accumulator) = execute_program(&instructions) {
         Ok(accumulator)
     } else {
-        Err(SimpleError::new(String::from("no solution found")))
+        Err(SimpleError::unsupported_input(String::from(
+            "no solution found",
+        )))
     }
 }
 
 fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
-    let instructions = parse_input(input)?;
+    let instructions = crate::timing::parse(|| parse_input(input))?;
 
     for (i, &instruction) in instructions.iter().enumerate() {
         let replacement_instruction = match instruction {
@@ -72,7 +73,9 @@ fn solve_part_2(input: &str) -> Result<i64, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn execute_program(instructions: &[Instruction]) -> ExecutionResult {
@@ -105,12 +108,12 @@ fn execute_program(instructions: &[Instruction]) -> ExecutionResult {
 }
 
 fn parse_input(input: &str) -> Result<Vec<Instruction>, SimpleError> {
-    input.lines().map(Instruction::from_str).collect()
+    crate::parse_lines(input, Instruction::from_str)
 }
 
-pub fn solve(input: &str) -> Result<(i64, i64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input)?;
-    let solution2 = solve_part_2(input)?;
+pub fn solve(input: &str) -> Result<(i64, i64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input))?;
 
     Ok((solution1, solution2))
 }
@@ -4,11 +4,10 @@
 
 use crate::SimpleError;
 use std::cmp;
-use std::error::Error;
 use std::num::ParseIntError;
 
 fn solve_part_1(input: &str, preamble_size: usize) -> Result<u64, SimpleError> {
-    let numbers = parse_input(input)?;
+    let numbers = crate::timing::parse(|| parse_input(input))?;
 
     let (_, first_invalid_number) = find_first_invalid_number(&numbers, preamble_size)?;
 
@@ -16,7 +15,7 @@ fn solve_part_1(input: &str, preamble_size: usize) -> Result<u64, SimpleError> {
 }
 
 fn solve_part_2(input: &str, preamble_size: usize) -> Result<u64, SimpleError> {
-    let numbers = parse_input(input)?;
+    let numbers = crate::timing::parse(|| parse_input(input))?;
 
     let (first_invalid_index, first_invalid_number) =
         find_first_invalid_number(&numbers, preamble_size)?;
@@ -39,7 +38,9 @@ fn solve_part_2(input: &str, preamble_size: usize) -> Result<u64, SimpleError> {
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn find_first_invalid_number(
@@ -54,7 +55,9 @@ fn find_first_invalid_number(
         }
     }
 
-    Err(SimpleError::new(String::from("no solution found")))
+    Err(SimpleError::unsupported_input(String::from(
+        "no solution found",
+    )))
 }
 
 fn is_valid_sequence(preamble: &[u64], target: u64) -> bool {
@@ -73,9 +76,9 @@ fn parse_input(input: &str) -> Result<Vec<u64>, ParseIntError> {
     input.lines().map(|line| line.parse::<u64>()).collect()
 }
 
-pub fn solve(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
-    let solution1 = solve_part_1(input, 25)?;
-    let solution2 = solve_part_2(input, 25)?;
+pub fn solve(input: &str) -> Result<(u64, u64), SimpleError> {
+    let solution1 = crate::timing::part_1(|| solve_part_1(input, 25))?;
+    let solution2 = crate::timing::part_2(|| solve_part_2(input, 25))?;
 
     Ok((solution1, solution2))
 }